@@ -1,6 +1,10 @@
 // Test vector sources:
 //
 // * ECB: NIST AESAVS Nov 15 2002 B.1
+// * CBC: NIST SP 800-38A F.2
+// * CTR: NIST SP 800-38A F.5
+// * CCM: NIST SP 800-38C C.1 - C.3
+// * CMAC: RFC 4493 section 4
 // * GCM: CAVS 14.0
 
 #![no_std]
@@ -10,7 +14,14 @@ use defmt::unwrap;
 use defmt_rtt as _; // global logger
 use hex_literal::hex;
 use panic_probe as _;
-use stm32wl_hal::{aes::Aes, cortex_m::peripheral::DWT, pac, rcc, util::reset_cycle_count};
+use stm32wl_hal::{
+    aes::{self, Aes, AesDma, Cmac, CtrDrbg},
+    cortex_m::peripheral::DWT,
+    dma::{AllDma, Dma1Ch1, Dma1Ch2},
+    pac, rcc,
+    rng::{rand_core::RngCore, Clk, Rng},
+    util::reset_cycle_count,
+};
 
 pub const ECB_PT_CT_128: [([u32; 4], [u32; 4]); 7] = [
     (
@@ -1463,6 +1474,91 @@ const GCM_256: [Gcm<8>; 60] = [
     },
 ];
 
+const CBC_IV: [u32; 4] = [0x00010203, 0x04050607, 0x08090a0b, 0x0c0d0e0f];
+const CBC_PT: [[u32; 4]; 4] = [
+    [0x6bc1bee2, 0x2e409f96, 0xe93d7e11, 0x7393172a],
+    [0xae2d8a57, 0x1e03ac9c, 0x9eb76fac, 0x45af8e51],
+    [0x30c81c46, 0xa35ce411, 0xe5fbc119, 0x1a0a52ef],
+    [0xf69f2445, 0xdf4f9b17, 0xad2b417b, 0xe66c3710],
+];
+const CBC_KEY_128: [u32; 4] = [0x2b7e1516, 0x28aed2a6, 0xabf71588, 0x09cf4f3c];
+const CBC_CT_128: [[u32; 4]; 4] = [
+    [0x7649abac, 0x8119b246, 0xcee98e9b, 0x12e9197d],
+    [0x5086cb9b, 0x507219ee, 0x95db113a, 0x917678b2],
+    [0x73bed6b8, 0xe3c1743b, 0x7116e69e, 0x22229516],
+    [0x3ff1caa1, 0x681fac09, 0x120eca30, 0x7586e1a7],
+];
+const CBC_KEY_256: [u32; 8] = [
+    0x603deb10, 0x15ca71be, 0x2b73aef0, 0x857d7781, 0x1f352c07, 0x3b6108d7, 0x2d9810a3, 0x0914dff4,
+];
+const CBC_CT_256: [[u32; 4]; 4] = [
+    [0xf58c4c04, 0xd6e5f1ba, 0x779eabfb, 0x5f7bfbd6],
+    [0x9cfc4e96, 0x7edb808d, 0x679f777b, 0xc6702c7d],
+    [0x39f23369, 0xa9d9bacf, 0xa530e263, 0x04231461],
+    [0xb2eb05e2, 0xc39be9fc, 0xda6c1907, 0x8c6a9d1b],
+];
+
+const CTR_COUNTER: [u32; 4] = [0xf0f1f2f3, 0xf4f5f6f7, 0xf8f9fafb, 0xfcfdfeff];
+const CTR_PT: [u8; 64] = hex!(
+    "6bc1bee22e409f96e93d7e117393172a"
+    "ae2d8a571e03ac9c9eb76fac45af8e51"
+    "30c81c46a35ce411e5fbc1191a0a52ef"
+    "f69f2445df4f9b17ad2b417be66c3710"
+);
+const CTR_CT_128: [u8; 64] = hex!(
+    "874d6191b620e3261bef6864990db6ce"
+    "9806f66b7970fdff8617187bb9fffdff"
+    "5ae4df3edbd5d35e5b4f09020db03eab"
+    "1e031dda2fbe03d1792170a0f3009cee"
+);
+const CTR_CT_256: [u8; 64] = hex!(
+    "601ec313775789a5b7a7f504bbf3d228"
+    "f443e3ca4d62b59aca84e990cacaf5c5"
+    "2b0930daa23de94ce87017ba2d84988d"
+    "dfc9c58db67aada613c2dd08457941a6"
+);
+
+struct Ccm {
+    nonce: &'static [u8],
+    aad: &'static [u8],
+    pt: &'static [u8],
+    ct: &'static [u8],
+    tag: &'static [u8],
+}
+
+const CCM_KEY: [u32; 4] = [0x40414243, 0x44454647, 0x48494a4b, 0x4c4d4e4f];
+const CCM: [Ccm; 3] = [
+    Ccm {
+        nonce: &hex!("10111213141516"),
+        aad: &hex!("0001020304050607"),
+        pt: &hex!("20212223"),
+        ct: &hex!("7162015b"),
+        tag: &hex!("4dac255d"),
+    },
+    Ccm {
+        nonce: &hex!("1011121314151617"),
+        aad: &hex!("000102030405060708090a0b0c0d0e0f"),
+        pt: &hex!("202122232425262728292a2b2c2d2e2f"),
+        ct: &hex!("d2a1f0e051ea5f62081a7792073d593d"),
+        tag: &hex!("1fc64fbfaccd"),
+    },
+    Ccm {
+        nonce: &hex!("101112131415161718191a1b"),
+        aad: &hex!("000102030405060708090a0b0c0d0e0f10111213"),
+        pt: &hex!("202122232425262728292a2b2c2d2e2f3031323334353637"),
+        ct: &hex!("e3b201a9f5b71a7a9b1ceaeccd97e70b6176aad9a4428aa5"),
+        tag: &hex!("484392fbc1b09951"),
+    },
+];
+
+// RFC 4493 message is the same as the CTR plaintext
+const CMAC_TAG: [(usize, [u32; 4]); 4] = [
+    (0, [0xbb1d6929, 0xe9593728, 0x7fa37d12, 0x9b756746]),
+    (16, [0x070a16b4, 0x6b4d4144, 0xf79bdd9d, 0xd04a287c]),
+    (40, [0xdfa66747, 0xde9ae630, 0x30ca3261, 0x1497c827]),
+    (64, [0x51f0bebf, 0x7e3b9d92, 0xfc497417, 0x79363cfe]),
+];
+
 const NUM_ECB_128: u32 = (ECB_PT_CT_128.len() + ECB_KEY_CT_128.len()) as u32;
 const NUM_ECB_256: u32 = (ECB_PT_CT_256.len() + ECB_KEY_CT_256.len()) as u32;
 const NUM_GCM_128: u32 = GCM_128.len() as u32;
@@ -1488,6 +1584,39 @@ where
     end.wrapping_sub(start)
 }
 
+// the AES driver is owned by the DRBG, steal a second driver and create a
+// RNG driver for each DRBG test
+fn ctr_drbg() -> CtrDrbg {
+    let mut dp: pac::Peripherals = unsafe { pac::Peripherals::steal() };
+    let aes: Aes = unsafe { Aes::steal() };
+    let rng: Rng = Rng::new(dp.RNG, Clk::MSI, &mut dp.RCC);
+    unwrap!(CtrDrbg::new(aes, rng, b"aes-testsuite"))
+}
+
+fn blocks_from_bytes(bytes: &[u8; 64]) -> [[u32; 4]; 4] {
+    let mut blocks: [[u32; 4]; 4] = [[0; 4]; 4];
+    bytes.chunks_exact(4).enumerate().for_each(|(idx, word)| {
+        blocks[idx / 4][idx % 4] = u32::from_be_bytes([word[0], word[1], word[2], word[3]])
+    });
+    blocks
+}
+
+#[cfg(feature = "rustcrypto")]
+fn be_bytes<const N: usize, const M: usize>(words: &[u32; N]) -> [u8; M] {
+    let mut bytes: [u8; M] = [0; M];
+    bytes
+        .chunks_exact_mut(4)
+        .zip(words.iter())
+        .for_each(|(chunk, word)| chunk.copy_from_slice(&word.to_be_bytes()));
+    bytes
+}
+
+// the DMA driver owns the AES driver, steal a second instance for DMA tests
+fn aes_dma() -> AesDma<Dma1Ch1, Dma1Ch2> {
+    let dma: AllDma = unsafe { AllDma::steal() };
+    unsafe { Aes::steal() }.with_dma((dma.d1.c1, dma.d1.c2))
+}
+
 #[defmt_test::tests]
 mod tests {
     use super::*;
@@ -1504,6 +1633,9 @@ mod tests {
         cp.DWT.enable_cycle_counter();
         reset_cycle_count(&mut cp.DWT);
 
+        AllDma::enable_clocks(&mut dp.RCC);
+        unsafe { AllDma::pulse_resets(&mut dp.RCC) };
+
         Aes::new(dp.AES, &mut dp.RCC)
     }
 
@@ -1607,6 +1739,300 @@ mod tests {
         );
     }
 
+    #[test]
+    fn encrypt_cbc_128(aes: &mut Aes) {
+        let mut ciphertext: [[u32; 4]; 4] = [[0; 4]; 4];
+        let elapsed: u32 =
+            stopwatch(|| unwrap!(aes.encrypt_cbc(&CBC_KEY_128, &CBC_IV, &CBC_PT, &mut ciphertext)));
+        defmt::assert_eq!(ciphertext, CBC_CT_128);
+        defmt::info!(
+            "Approximate cycles per 128-bit encrypt: {}",
+            elapsed / (CBC_PT.len() as u32)
+        );
+
+        let mut text: [[u32; 4]; 4] = CBC_PT;
+        unwrap!(aes.encrypt_cbc_inplace(&CBC_KEY_128, &CBC_IV, &mut text));
+        defmt::assert_eq!(text, CBC_CT_128);
+    }
+
+    #[test]
+    fn encrypt_cbc_256(aes: &mut Aes) {
+        let mut ciphertext: [[u32; 4]; 4] = [[0; 4]; 4];
+        let elapsed: u32 =
+            stopwatch(|| unwrap!(aes.encrypt_cbc(&CBC_KEY_256, &CBC_IV, &CBC_PT, &mut ciphertext)));
+        defmt::assert_eq!(ciphertext, CBC_CT_256);
+        defmt::info!(
+            "Approximate cycles per 256-bit encrypt: {}",
+            elapsed / (CBC_PT.len() as u32)
+        );
+
+        let mut text: [[u32; 4]; 4] = CBC_PT;
+        unwrap!(aes.encrypt_cbc_inplace(&CBC_KEY_256, &CBC_IV, &mut text));
+        defmt::assert_eq!(text, CBC_CT_256);
+    }
+
+    #[test]
+    fn decrypt_cbc_128(aes: &mut Aes) {
+        let mut plaintext: [[u32; 4]; 4] = [[0; 4]; 4];
+        let elapsed: u32 = stopwatch(|| {
+            unwrap!(aes.decrypt_cbc(&CBC_KEY_128, &CBC_IV, &CBC_CT_128, &mut plaintext))
+        });
+        defmt::assert_eq!(plaintext, CBC_PT);
+        defmt::info!(
+            "Approximate cycles per 128-bit decrypt: {}",
+            elapsed / (CBC_PT.len() as u32)
+        );
+
+        let mut text: [[u32; 4]; 4] = CBC_CT_128;
+        unwrap!(aes.decrypt_cbc_inplace(&CBC_KEY_128, &CBC_IV, &mut text));
+        defmt::assert_eq!(text, CBC_PT);
+    }
+
+    #[test]
+    fn decryption_key_ecb_128(aes: &mut Aes) {
+        let mut dk: aes::DecryptionKey = unwrap!(aes.decryption_key(&ZERO_16B));
+        let mut total_elapsed: u32 = 0;
+
+        for (plaintext, ciphertext) in ECB_PT_CT_128.iter() {
+            let mut output_plaintext: [u32; 4] = [0; 4];
+            total_elapsed +=
+                stopwatch(|| unwrap!(dk.decrypt_ecb(ciphertext, &mut output_plaintext)));
+            defmt::assert_eq!(&output_plaintext, plaintext);
+
+            let mut text: [u32; 4] = *ciphertext;
+            unwrap!(dk.decrypt_ecb_inplace(&mut text));
+            defmt::assert_eq!(&text, plaintext);
+        }
+
+        defmt::info!(
+            "Approximate cycles per 128-bit decrypt with a cached key: {}",
+            total_elapsed / (ECB_PT_CT_128.len() as u32)
+        );
+    }
+
+    #[test]
+    fn decryption_key_cbc_256(aes: &mut Aes) {
+        let mut dk: aes::DecryptionKey = unwrap!(aes.decryption_key(&CBC_KEY_256));
+
+        for _ in 0..2 {
+            let mut plaintext: [[u32; 4]; 4] = [[0; 4]; 4];
+            unwrap!(dk.decrypt_cbc(&CBC_IV, &CBC_CT_256, &mut plaintext));
+            defmt::assert_eq!(plaintext, CBC_PT);
+
+            let mut text: [[u32; 4]; 4] = CBC_CT_256;
+            unwrap!(dk.decrypt_cbc_inplace(&CBC_IV, &mut text));
+            defmt::assert_eq!(text, CBC_PT);
+        }
+    }
+
+    #[test]
+    fn decrypt_cbc_256(aes: &mut Aes) {
+        let mut plaintext: [[u32; 4]; 4] = [[0; 4]; 4];
+        let elapsed: u32 = stopwatch(|| {
+            unwrap!(aes.decrypt_cbc(&CBC_KEY_256, &CBC_IV, &CBC_CT_256, &mut plaintext))
+        });
+        defmt::assert_eq!(plaintext, CBC_PT);
+        defmt::info!(
+            "Approximate cycles per 256-bit decrypt: {}",
+            elapsed / (CBC_PT.len() as u32)
+        );
+
+        let mut text: [[u32; 4]; 4] = CBC_CT_256;
+        unwrap!(aes.decrypt_cbc_inplace(&CBC_KEY_256, &CBC_IV, &mut text));
+        defmt::assert_eq!(text, CBC_PT);
+    }
+
+    #[test]
+    fn encrypt_ctr_inplace_128(aes: &mut Aes) {
+        let mut counter: [u32; 4] = CTR_COUNTER;
+        let mut buf: [u8; 64] = CTR_PT;
+        let elapsed: u32 =
+            stopwatch(|| unwrap!(aes.encrypt_ctr_inplace(&CBC_KEY_128, &mut counter, &mut buf)));
+        defmt::assert_eq!(buf[..], CTR_CT_128[..]);
+        defmt::assert_eq!(counter[3], CTR_COUNTER[3].wrapping_add(4));
+        defmt::info!(
+            "Approximate cycles per 128-bit encrypt: {}",
+            elapsed / ((CTR_PT.len() / 16) as u32)
+        );
+    }
+
+    #[test]
+    fn encrypt_ctr_inplace_256(aes: &mut Aes) {
+        let mut counter: [u32; 4] = CTR_COUNTER;
+        let mut buf: [u8; 64] = CTR_PT;
+        let elapsed: u32 =
+            stopwatch(|| unwrap!(aes.encrypt_ctr_inplace(&CBC_KEY_256, &mut counter, &mut buf)));
+        defmt::assert_eq!(buf[..], CTR_CT_256[..]);
+        defmt::info!(
+            "Approximate cycles per 256-bit encrypt: {}",
+            elapsed / ((CTR_PT.len() / 16) as u32)
+        );
+    }
+
+    #[test]
+    fn decrypt_ctr_inplace_128(aes: &mut Aes) {
+        let mut counter: [u32; 4] = CTR_COUNTER;
+        let mut buf: [u8; 64] = CTR_CT_128;
+        unwrap!(aes.decrypt_ctr_inplace(&CBC_KEY_128, &mut counter, &mut buf));
+        defmt::assert_eq!(buf[..], CTR_PT[..]);
+    }
+
+    #[test]
+    fn ctr_inplace_resume(aes: &mut Aes) {
+        let mut counter: [u32; 4] = CTR_COUNTER;
+        let mut buf: [u8; 64] = CTR_PT;
+        let (first, second) = buf.split_at_mut(32);
+        unwrap!(aes.encrypt_ctr_inplace(&CBC_KEY_128, &mut counter, first));
+        unwrap!(aes.encrypt_ctr_inplace(&CBC_KEY_128, &mut counter, second));
+        defmt::assert_eq!(buf[..], CTR_CT_128[..]);
+    }
+
+    #[test]
+    fn ctr_inplace_all_sizes(aes: &mut Aes) {
+        for x in 0..CTR_PT.len() {
+            defmt::debug!("{}-length buffer", x);
+            let mut counter: [u32; 4] = CTR_COUNTER;
+            let mut buf: [u8; 64] = CTR_PT;
+            unwrap!(aes.encrypt_ctr_inplace(&CBC_KEY_128, &mut counter, &mut buf[..x]));
+            defmt::assert_eq!(buf[..x], CTR_CT_128[..x]);
+            defmt::assert_eq!(buf[x..], CTR_PT[x..]);
+        }
+    }
+
+    #[test]
+    fn encrypt_ccm_inplace(aes: &mut Aes) {
+        for ccm in CCM.iter() {
+            let mut buf: [u8; 24] = [0; 24];
+            buf[..ccm.pt.len()].copy_from_slice(ccm.pt);
+            let mut tag: [u8; 16] = [0; 16];
+
+            let elapsed: u32 = stopwatch(|| {
+                unwrap!(aes.encrypt_ccm_inplace(
+                    &CCM_KEY,
+                    ccm.nonce,
+                    ccm.aad,
+                    &mut buf[..ccm.pt.len()],
+                    &mut tag[..ccm.tag.len()]
+                ))
+            });
+
+            defmt::assert_eq!(&buf[..ccm.pt.len()], ccm.ct);
+            defmt::assert_eq!(&tag[..ccm.tag.len()], ccm.tag);
+            defmt::info!("Encrypting {} bytes: {} cycles", ccm.pt.len(), elapsed);
+        }
+    }
+
+    #[test]
+    fn decrypt_ccm_inplace(aes: &mut Aes) {
+        for ccm in CCM.iter() {
+            let mut buf: [u8; 24] = [0; 24];
+            buf[..ccm.ct.len()].copy_from_slice(ccm.ct);
+
+            let elapsed: u32 = stopwatch(|| {
+                unwrap!(aes.decrypt_ccm_inplace(
+                    &CCM_KEY,
+                    ccm.nonce,
+                    ccm.aad,
+                    &mut buf[..ccm.ct.len()],
+                    ccm.tag
+                ))
+            });
+
+            defmt::assert_eq!(&buf[..ccm.ct.len()], ccm.pt);
+            defmt::info!("Decrypting {} bytes: {} cycles", ccm.ct.len(), elapsed);
+        }
+    }
+
+    #[test]
+    fn decrypt_ccm_inplace_bad_tag(aes: &mut Aes) {
+        let ccm: &Ccm = &CCM[2];
+        let mut buf: [u8; 24] = [0; 24];
+        buf.copy_from_slice(ccm.ct);
+        let mut tag: [u8; 8] = [0; 8];
+        tag.copy_from_slice(ccm.tag);
+        tag[7] ^= 0x01;
+
+        defmt::assert_eq!(
+            aes.decrypt_ccm_inplace(&CCM_KEY, ccm.nonce, ccm.aad, &mut buf, &tag),
+            Err(aes::Error::Tag)
+        );
+        defmt::assert_eq!(buf, [0; 24]);
+    }
+
+    #[test]
+    fn ccm_inplace_256_partial_block(aes: &mut Aes) {
+        const KEY: [u32; 8] = [
+            0x00010203, 0x04050607, 0x08090a0b, 0x0c0d0e0f, 0x10111213, 0x14151617, 0x18191a1b,
+            0x1c1d1e1f,
+        ];
+        const NONCE: [u8; 13] = hex!("000102030405060708090a0b0c");
+        const PT: &[u8; 45] = b"Lorem ipsum dolor sit amet, consectetur adipi";
+        const CT: [u8; 45] = hex!(
+            "5068d903618813f8b2a5bb107eb57987cddf6750e6c70cde8b0ecc4fc2"
+            "73c7230adf14119960c6fb79d1edd6e0"
+        );
+        const TAG: [u8; 16] = hex!("9ccc7dda6aeab7b36e605095606868b0");
+
+        let mut buf: [u8; 45] = *PT;
+        let mut tag: [u8; 16] = [0; 16];
+        unwrap!(aes.encrypt_ccm_inplace(&KEY, &NONCE, &[], &mut buf, &mut tag));
+        defmt::assert_eq!(buf[..], CT[..]);
+        defmt::assert_eq!(tag, TAG);
+
+        unwrap!(aes.decrypt_ccm_inplace(&KEY, &NONCE, &[], &mut buf, &TAG));
+        defmt::assert_eq!(buf[..], PT[..]);
+    }
+
+    #[test]
+    fn cmac(aes: &mut Aes) {
+        let mut cmac: Cmac = unwrap!(Cmac::new(aes, &CBC_KEY_128));
+        for (len, tag) in CMAC_TAG.iter() {
+            let elapsed: u32 = stopwatch(|| {
+                unwrap!(cmac.update(aes, &CTR_PT[..*len]));
+                defmt::assert_eq!(&unwrap!(cmac.finish(aes)), tag);
+            });
+            defmt::info!("MAC of {} bytes: {} cycles", len, elapsed);
+        }
+    }
+
+    #[test]
+    fn cmac_streaming(aes: &mut Aes) {
+        let mut cmac: Cmac = unwrap!(Cmac::new(aes, &CBC_KEY_128));
+        for (len, tag) in CMAC_TAG.iter() {
+            for chunk_size in 1..=17 {
+                for chunk in CTR_PT[..*len].chunks(chunk_size) {
+                    unwrap!(cmac.update(aes, chunk));
+                }
+                defmt::assert_eq!(&unwrap!(cmac.finish(aes)), tag);
+            }
+        }
+    }
+
+    #[test]
+    fn cmac_update_block(aes: &mut Aes) {
+        let (len, tag) = CMAC_TAG[3];
+        let mut cmac: Cmac = unwrap!(Cmac::new(aes, &CBC_KEY_128));
+        unwrap!(cmac.update_block(aes, &CBC_PT[0]));
+        unwrap!(cmac.update_block(aes, &CBC_PT[1]));
+        unwrap!(cmac.update(aes, &CTR_PT[32..len]));
+        defmt::assert_eq!(unwrap!(cmac.finish(aes)), tag);
+    }
+
+    #[test]
+    fn cmac_verify(aes: &mut Aes) {
+        let (len, tag) = CMAC_TAG[2];
+        let mut cmac: Cmac = unwrap!(Cmac::new(aes, &CBC_KEY_128));
+
+        // truncated 32-bit MIC
+        let mic: [u8; 4] = tag[0].to_be_bytes();
+        unwrap!(cmac.update(aes, &CTR_PT[..len]));
+        unwrap!(cmac.verify(aes, &mic));
+
+        let bad_mic: [u8; 4] = (tag[0] ^ 1).to_be_bytes();
+        unwrap!(cmac.update(aes, &CTR_PT[..len]));
+        defmt::assert_eq!(cmac.verify(aes, &bad_mic), Err(aes::Error::Tag));
+    }
+
     #[test]
     fn encrypt_gcm_inplace_128(aes: &mut Aes) {
         let mut total_elapsed: u32 = 0;
@@ -1837,6 +2263,110 @@ mod tests {
         );
     }
 
+    #[test]
+    fn gcm_streaming_128(aes: &mut Aes) {
+        for gcm in GCM_128.iter() {
+            let mut buf: [u8; 16] = [0; 16];
+            buf[..gcm.pt.len()].copy_from_slice(gcm.pt);
+
+            let mut ctx: aes::Gcm = unwrap!(aes::Gcm::init_encrypt(aes, &gcm.key, &gcm.iv));
+            for byte in gcm.aad.chunks(1) {
+                unwrap!(ctx.update_aad(byte));
+            }
+            unwrap!(ctx.update(&mut buf[..gcm.pt.len()]));
+            defmt::assert_eq!(unwrap!(ctx.finish_encrypt()), gcm.tag);
+            defmt::assert_eq!(&buf[..gcm.pt.len()], gcm.ct);
+
+            let mut ctx: aes::Gcm = unwrap!(aes::Gcm::init_decrypt(aes, &gcm.key, &gcm.iv));
+            unwrap!(ctx.update_aad(gcm.aad));
+            unwrap!(ctx.update(&mut buf[..gcm.ct.len()]));
+            unwrap!(ctx.finish_decrypt(&gcm.tag));
+            defmt::assert_eq!(&buf[..gcm.ct.len()], gcm.pt);
+        }
+    }
+
+    #[test]
+    fn gcm_streaming_chunks(aes: &mut Aes) {
+        const AAD: &[u8; 45] = b"Lorem ipsum dolor sit amet, consectetur adipi";
+        const IV: [u32; 3] = [0; 3];
+
+        let mut expected: [u8; 64] = CTR_PT;
+        let mut expected_tag: [u32; 4] = [0; 4];
+        unwrap!(aes.encrypt_gcm_inplace(
+            &ZERO_16B,
+            &IV,
+            AAD,
+            &mut expected[..61],
+            &mut expected_tag
+        ));
+
+        let mut buf: [u8; 64] = CTR_PT;
+        let mut ctx: aes::Gcm = unwrap!(aes::Gcm::init_encrypt(aes, &ZERO_16B, &IV));
+        unwrap!(ctx.update_aad(&AAD[..7]));
+        unwrap!(ctx.update_aad(&AAD[7..30]));
+        unwrap!(ctx.update_aad(&AAD[30..]));
+        let (first, second) = buf[..61].split_at_mut(32);
+        unwrap!(ctx.update(first));
+        unwrap!(ctx.update(second));
+        defmt::assert_eq!(unwrap!(ctx.finish_encrypt()), expected_tag);
+        defmt::assert_eq!(buf[..61], expected[..61]);
+
+        let mut ctx: aes::Gcm = unwrap!(aes::Gcm::init_decrypt(aes, &ZERO_16B, &IV));
+        unwrap!(ctx.update_aad(AAD));
+        for chunk in buf[..61].chunks_mut(16) {
+            unwrap!(ctx.update(chunk));
+        }
+        unwrap!(ctx.finish_decrypt(&expected_tag));
+        defmt::assert_eq!(buf[..61], CTR_PT[..61]);
+    }
+
+    #[test]
+    fn gcm_suspend_resume(aes: &mut Aes) {
+        const AAD: &[u8; 45] = b"Lorem ipsum dolor sit amet, consectetur adipi";
+        const IV: [u32; 3] = [0; 3];
+
+        let mut expected: [u8; 64] = CTR_PT;
+        let mut expected_tag: [u32; 4] = [0; 4];
+        unwrap!(aes.encrypt_gcm_inplace(&ZERO_16B, &IV, AAD, &mut expected, &mut expected_tag));
+
+        // simulates a high priority task that pre-empts the GCM operation
+        let preempt = || {
+            let mut aes: Aes = unsafe { Aes::steal() };
+            let ctx: aes::Context = aes.suspend();
+            let mut cmac: Cmac = unwrap!(Cmac::new(&mut aes, &CBC_KEY_128));
+            unwrap!(cmac.update(&mut aes, &CTR_PT[..CMAC_TAG[2].0]));
+            defmt::assert_eq!(unwrap!(cmac.finish(&mut aes)), CMAC_TAG[2].1);
+            unwrap!(aes.resume(&ZERO_16B, &ctx));
+        };
+
+        let mut buf: [u8; 64] = CTR_PT;
+        let mut ctx: aes::Gcm = unwrap!(aes::Gcm::init_encrypt(aes, &ZERO_16B, &IV));
+        unwrap!(ctx.update_aad(&AAD[..32]));
+        preempt();
+        unwrap!(ctx.update_aad(&AAD[32..]));
+        let (first, second) = buf.split_at_mut(32);
+        unwrap!(ctx.update(first));
+        preempt();
+        unwrap!(ctx.update(second));
+        defmt::assert_eq!(unwrap!(ctx.finish_encrypt()), expected_tag);
+        defmt::assert_eq!(buf[..], expected[..]);
+    }
+
+    #[test]
+    fn gcm_streaming_bad_tag(aes: &mut Aes) {
+        let gcm: &Gcm<4> = &GCM_128[GCM_128.len() - 1];
+        let mut buf: [u8; 16] = [0; 16];
+        buf[..gcm.ct.len()].copy_from_slice(gcm.ct);
+
+        let mut tag: [u32; 4] = gcm.tag;
+        tag[3] ^= 1;
+
+        let mut ctx: aes::Gcm = unwrap!(aes::Gcm::init_decrypt(aes, &gcm.key, &gcm.iv));
+        unwrap!(ctx.update_aad(gcm.aad));
+        unwrap!(ctx.update(&mut buf[..gcm.ct.len()]));
+        defmt::assert_eq!(ctx.finish_decrypt(&tag), Err(aes::Error::Tag));
+    }
+
     #[test]
     fn gcm_inplace_normal_use(aes: &mut Aes) {
         const ASSOCIATED_DATA: &[u8; 13] = b"Hello, World!";
@@ -1921,4 +2451,259 @@ mod tests {
             defmt::assert_eq!(encrypt_tag, decrypt_tag);
         }
     }
+
+    #[test]
+    fn dma_ecb_128() {
+        let mut aes: AesDma<Dma1Ch1, Dma1Ch2> = aes_dma();
+
+        let mut buf: [[u32; 4]; ECB_PT_CT_128.len()] = [[0; 4]; ECB_PT_CT_128.len()];
+        buf.iter_mut()
+            .zip(ECB_PT_CT_128.iter())
+            .for_each(|(block, (plaintext, _))| *block = *plaintext);
+
+        let elapsed: u32 = stopwatch(|| unwrap!(aes.encrypt_ecb_inplace(&ZERO_16B, &mut buf)));
+        buf.iter()
+            .zip(ECB_PT_CT_128.iter())
+            .for_each(|(block, (_, ciphertext))| defmt::assert_eq!(block, ciphertext));
+        defmt::info!(
+            "Approximate cycles per 128-bit encrypt: {}",
+            elapsed / (buf.len() as u32)
+        );
+
+        unwrap!(aes.decrypt_ecb_inplace(&ZERO_16B, &mut buf));
+        buf.iter()
+            .zip(ECB_PT_CT_128.iter())
+            .for_each(|(block, (plaintext, _))| defmt::assert_eq!(block, plaintext));
+    }
+
+    #[test]
+    fn dma_cbc_256() {
+        let mut aes: AesDma<Dma1Ch1, Dma1Ch2> = aes_dma();
+
+        let mut buf: [[u32; 4]; 4] = CBC_PT;
+        unwrap!(aes.encrypt_cbc_inplace(&CBC_KEY_256, &CBC_IV, &mut buf));
+        defmt::assert_eq!(buf, CBC_CT_256);
+        unwrap!(aes.decrypt_cbc_inplace(&CBC_KEY_256, &CBC_IV, &mut buf));
+        defmt::assert_eq!(buf, CBC_PT);
+    }
+
+    #[test]
+    fn dma_ctr_128() {
+        let mut aes: AesDma<Dma1Ch1, Dma1Ch2> = aes_dma();
+
+        let mut counter: [u32; 4] = CTR_COUNTER;
+        let mut buf: [[u32; 4]; 4] = blocks_from_bytes(&CTR_PT);
+        unwrap!(aes.encrypt_ctr_inplace(&CBC_KEY_128, &mut counter, &mut buf[..1]));
+        unwrap!(aes.encrypt_ctr_inplace(&CBC_KEY_128, &mut counter, &mut buf[1..]));
+        defmt::assert_eq!(buf, blocks_from_bytes(&CTR_CT_128));
+
+        let mut counter: [u32; 4] = CTR_COUNTER;
+        unwrap!(aes.decrypt_ctr_inplace(&CBC_KEY_128, &mut counter, &mut buf));
+        defmt::assert_eq!(buf, blocks_from_bytes(&CTR_PT));
+    }
+
+    #[test]
+    fn dma_gcm_128() {
+        let mut aes: AesDma<Dma1Ch1, Dma1Ch2> = aes_dma();
+
+        for gcm in GCM_128.iter().filter(|gcm| gcm.pt.len() % 16 == 0) {
+            let mut buf: [[u32; 4]; 1] = [[0; 4]; 1];
+            let blocks: usize = gcm.pt.len() / 16;
+            gcm.pt.chunks_exact(4).enumerate().for_each(|(idx, word)| {
+                buf[0][idx] = u32::from_be_bytes([word[0], word[1], word[2], word[3]])
+            });
+
+            let mut tag: [u32; 4] = [0; 4];
+            unwrap!(aes.encrypt_gcm_inplace(
+                &gcm.key,
+                &gcm.iv,
+                gcm.aad,
+                &mut buf[..blocks],
+                &mut tag
+            ));
+            defmt::assert_eq!(tag, gcm.tag);
+            buf[..blocks]
+                .iter()
+                .flat_map(|block| block.iter())
+                .zip(gcm.ct.chunks_exact(4))
+                .for_each(|(word, ct)| {
+                    defmt::assert_eq!(word.to_be_bytes(), [ct[0], ct[1], ct[2], ct[3]])
+                });
+
+            let mut tag: [u32; 4] = [0; 4];
+            unwrap!(aes.decrypt_gcm_inplace(
+                &gcm.key,
+                &gcm.iv,
+                gcm.aad,
+                &mut buf[..blocks],
+                &mut tag
+            ));
+            defmt::assert_eq!(tag, gcm.tag);
+        }
+    }
+
+    #[test]
+    fn dma_gcm_4k(aes: &mut Aes) {
+        const AAD: &[u8; 45] = b"Lorem ipsum dolor sit amet, consectetur adipi";
+        const IV: [u32; 3] = [0x01020304, 0x05060708, 0x090a0b0c];
+        static mut PAGE: [[u32; 4]; 256] = [[0; 4]; 256];
+        static mut EXPECTED: [u8; 4096] = [0; 4096];
+
+        let page: &mut [[u32; 4]; 256] = unsafe { &mut PAGE };
+        let expected: &mut [u8; 4096] = unsafe { &mut EXPECTED };
+        page.iter_mut()
+            .flat_map(|block| block.iter_mut())
+            .enumerate()
+            .for_each(|(idx, word)| *word = idx as u32);
+        expected
+            .chunks_exact_mut(4)
+            .enumerate()
+            .for_each(|(idx, word)| word.copy_from_slice(&(idx as u32).to_be_bytes()));
+
+        let mut expected_tag: [u32; 4] = [0; 4];
+        let polling_elapsed: u32 = stopwatch(|| {
+            unwrap!(aes.encrypt_gcm_inplace(&ZERO_16B, &IV, AAD, expected, &mut expected_tag))
+        });
+
+        let mut aes_dma: AesDma<Dma1Ch1, Dma1Ch2> = aes_dma();
+        let mut tag: [u32; 4] = [0; 4];
+        let dma_elapsed: u32 =
+            stopwatch(|| unwrap!(aes_dma.encrypt_gcm_inplace(&ZERO_16B, &IV, AAD, page, &mut tag)));
+        defmt::assert_eq!(tag, expected_tag);
+        page.iter()
+            .flat_map(|block| block.iter())
+            .zip(expected.chunks_exact(4))
+            .for_each(|(word, ct)| {
+                defmt::assert_eq!(word.to_be_bytes(), [ct[0], ct[1], ct[2], ct[3]])
+            });
+
+        defmt::info!(
+            "Approximate cycles per 4 KiB GCM encrypt: {} polling, {} DMA",
+            polling_elapsed,
+            dma_elapsed
+        );
+    }
+
+    #[test]
+    fn ctr_drbg_generate() {
+        let mut drbg: CtrDrbg = ctr_drbg();
+
+        const NUM_BLK: usize = 64;
+        let mut a: [u8; NUM_BLK * 16] = [0; NUM_BLK * 16];
+        let mut b: [u8; NUM_BLK * 16] = [0; NUM_BLK * 16];
+        let elapsed: u32 = stopwatch(|| unwrap!(drbg.try_fill_bytes(&mut a).ok()));
+        unwrap!(drbg.generate(&mut b, b"additional input"));
+        defmt::assert_ne!(a.as_ref(), b.as_ref());
+        defmt::assert_ne!(a.as_ref(), [0; NUM_BLK * 16].as_ref());
+
+        defmt::info!(
+            "Approximate cycles per [u32; 4]: {}",
+            elapsed / (NUM_BLK as u32)
+        );
+
+        drbg.free();
+    }
+
+    // NIST CAVP CTR_DRBG AES-256 use df, no prediction resistance,
+    // reseed before the first generate, 512-bit returned bits, COUNT = 0
+    #[test]
+    fn ctr_drbg_cavp() {
+        const ENTROPY: [u8; 32] =
+            hex!("2d4c9f46b981c6a0b2b5d8c69391e569ff13851437ebc0fc00d616340252fed5");
+        const NONCE: [u8; 16] = hex!("0bf814b411f65ec4866be1abb59d3c32");
+        const ENTROPY_RESEED: [u8; 32] =
+            hex!("93500fae4fa32b86033b7a7bac9d37e710dcc67ca266bc8607d665937766d207");
+        const RETURNED: [u8; 64] = hex!(
+            "322dd28670e75c0ea638f3cb68d6a9d6e50ddfd052b772a7b1d78263a7b8978b"
+            "6740c2b65a9550c3a76325866fa97e16d74006bc96f26249b9f0a90d076f08e5"
+        );
+
+        let mut dp: pac::Peripherals = unsafe { pac::Peripherals::steal() };
+        let aes: Aes = unsafe { Aes::steal() };
+        let rng: Rng = Rng::new(dp.RNG, Clk::MSI, &mut dp.RCC);
+        let mut drbg: CtrDrbg = unwrap!(CtrDrbg::with_entropy(aes, rng, &ENTROPY, &NONCE, &[]));
+
+        let mut buf: [u8; 64] = [0; 64];
+        unwrap!(drbg.reseed_with_entropy(&ENTROPY_RESEED, &[]));
+        unwrap!(drbg.generate(&mut buf, &[]));
+        unwrap!(drbg.generate(&mut buf, &[]));
+        defmt::assert_eq!(buf, RETURNED);
+
+        drbg.free();
+    }
+
+    #[test]
+    fn ctr_drbg_reseed() {
+        let mut drbg: CtrDrbg = ctr_drbg();
+
+        drbg.set_reseed_interval(1);
+        defmt::assert_eq!(drbg.reseed_interval(), 1);
+        let mut a: [u8; 32] = [0; 32];
+        let mut b: [u8; 32] = [0; 32];
+        unwrap!(drbg.generate(&mut a, &[]));
+        unwrap!(drbg.generate(&mut b, &[]));
+        defmt::assert_ne!(a, b);
+
+        drbg.set_prediction_resistance(true);
+        defmt::assert!(drbg.prediction_resistance());
+        unwrap!(drbg.generate(&mut a, &[]));
+        defmt::assert_ne!(a, b);
+
+        unwrap!(drbg.reseed(b"reseed"));
+        unwrap!(drbg.generate(&mut b, &[]));
+        defmt::assert_ne!(a, b);
+
+        drbg.free();
+    }
+
+    #[cfg(feature = "rustcrypto")]
+    #[test]
+    fn rustcrypto_block_cipher(aes: &mut Aes) {
+        use stm32wl_hal::aes::rustcrypto::{
+            cipher::{BlockDecrypt, BlockEncrypt},
+            Aes128, Aes256,
+        };
+
+        let aes128: Aes128 = Aes128::new(aes, &[0; 16].into());
+        for (plaintext, ciphertext) in ECB_PT_CT_128.iter() {
+            let mut block = be_bytes::<4, 16>(plaintext).into();
+            aes128.encrypt_block(&mut block);
+            defmt::assert_eq!(block.as_slice(), be_bytes::<4, 16>(ciphertext));
+            aes128.decrypt_block(&mut block);
+            defmt::assert_eq!(block.as_slice(), be_bytes::<4, 16>(plaintext));
+        }
+
+        let aes256: Aes256 = Aes256::new(aes, &[0; 32].into());
+        for (plaintext, ciphertext) in ECB_PT_CT_256.iter() {
+            let mut block = be_bytes::<4, 16>(plaintext).into();
+            aes256.encrypt_block(&mut block);
+            defmt::assert_eq!(block.as_slice(), be_bytes::<4, 16>(ciphertext));
+            aes256.decrypt_block(&mut block);
+            defmt::assert_eq!(block.as_slice(), be_bytes::<4, 16>(plaintext));
+        }
+    }
+
+    #[cfg(feature = "rustcrypto")]
+    #[test]
+    fn rustcrypto_gcm_128(aes: &mut Aes) {
+        use stm32wl_hal::aes::rustcrypto::{aead::AeadInPlace, Aes128Gcm};
+
+        for gcm in GCM_128.iter() {
+            let cipher: Aes128Gcm = Aes128Gcm::new(aes, &be_bytes::<4, 16>(&gcm.key).into());
+            let nonce: [u8; 12] = be_bytes(&gcm.iv);
+            let mut buf: [u8; 16] = [0; 16];
+            buf[..gcm.pt.len()].copy_from_slice(gcm.pt);
+
+            let tag = unwrap!(cipher
+                .encrypt_in_place_detached(&nonce.into(), gcm.aad, &mut buf[..gcm.pt.len()])
+                .ok());
+            defmt::assert_eq!(tag.as_slice(), be_bytes::<4, 16>(&gcm.tag));
+            defmt::assert_eq!(&buf[..gcm.pt.len()], gcm.ct);
+
+            unwrap!(cipher
+                .decrypt_in_place_detached(&nonce.into(), gcm.aad, &mut buf[..gcm.pt.len()], &tag)
+                .ok());
+            defmt::assert_eq!(&buf[..gcm.pt.len()], gcm.pt);
+        }
+    }
 }
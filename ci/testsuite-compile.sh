@@ -15,3 +15,9 @@ do
         --target thumbv7em-none-eabi \
         --no-run
 done
+
+cargo test \
+    -p aes-testsuite \
+    --target thumbv7em-none-eabi \
+    --features rustcrypto \
+    --no-run
@@ -0,0 +1,265 @@
+use super::{be_bytes_from_words, ct_eq, words_from_be_bytes, Aes, Error, Mode};
+
+/// Subkey generation doubling in GF(2^128), RFC 4493 section 2.3.
+const fn dbl(x: &[u32; 4]) -> [u32; 4] {
+    // constant-time reduction, no branch on the secret MSB
+    let carry: u32 = x[0] >> 31;
+    [
+        (x[0] << 1) | (x[1] >> 31),
+        (x[1] << 1) | (x[2] >> 31),
+        (x[2] << 1) | (x[3] >> 31),
+        (x[3] << 1) ^ (0x87 * carry),
+    ]
+}
+
+/// Cipher-based message authentication code (CMAC) context.
+///
+/// This implements AES-CMAC (RFC 4493, NIST SP 800-38B) on top of the [`Aes`]
+/// peripheral using the CBC algorithm.
+///
+/// The context does not borrow the AES driver, every method that requires
+/// the hardware takes the driver as an argument.
+/// The subkeys are derived once in [`new`](Self::new), the context can be
+/// re-used for multiple messages with the same key after calling
+/// [`finish`](Self::finish) or [`verify`](Self::verify).
+///
+/// # Example
+///
+/// Compute a LoRaWAN MIC, the B0 block is passed with
+/// [`update_block`](Self::update_block) to avoid copying bytes.
+///
+/// ```no_run
+/// use stm32wl_hal::{
+///     aes::{Aes, Cmac},
+///     pac,
+/// };
+///
+/// let mut dp: pac::Peripherals = pac::Peripherals::take().unwrap();
+/// let mut aes: Aes = Aes::new(dp.AES, &mut dp.RCC);
+///
+/// const NWK_S_KEY: [u32; 4] = [0; 4];
+///
+/// let msg: [u8; 13] = [
+///     0x40, 0x04, 0x03, 0x02, 0x01, 0x80, 0x01, 0x00, 0x01, 0xa6, 0x94, 0x64, 0x26,
+/// ];
+/// let b0: [u32; 4] = [0x49000000, 0x00000403, 0x02010100, 0x0000000d];
+///
+/// let mut cmac: Cmac = Cmac::new(&mut aes, &NWK_S_KEY)?;
+/// cmac.update_block(&mut aes, &b0)?;
+/// cmac.update(&mut aes, &msg)?;
+/// let mic: u32 = cmac.finish(&mut aes)?[0];
+/// # Ok::<(), stm32wl_hal::aes::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct Cmac<'k> {
+    key: &'k [u32],
+    k1: [u32; 4],
+    k2: [u32; 4],
+    state: [u32; 4],
+    buf: [u8; 16],
+    buf_len: usize,
+}
+
+impl<'k> Cmac<'k> {
+    /// Create a new CMAC context, deriving the subkeys from the key.
+    ///
+    /// # Panics
+    ///
+    /// * Key is not 128-bits long `[u32; 4]` or 256-bits long `[u32; 8]`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use stm32wl_hal::{
+    ///     aes::{Aes, Cmac},
+    ///     pac,
+    /// };
+    ///
+    /// let mut dp: pac::Peripherals = pac::Peripherals::take().unwrap();
+    /// let mut aes: Aes = Aes::new(dp.AES, &mut dp.RCC);
+    ///
+    /// const KEY: [u32; 4] = [0; 4];
+    /// let mut cmac: Cmac = Cmac::new(&mut aes, &KEY)?;
+    /// # Ok::<(), stm32wl_hal::aes::Error>(())
+    /// ```
+    pub fn new(aes: &mut Aes, key: &'k [u32]) -> Result<Self, Error> {
+        let mut l: [u32; 4] = [0; 4];
+        aes.encrypt_ecb_inplace(key, &mut l)?;
+        let k1: [u32; 4] = dbl(&l);
+        let k2: [u32; 4] = dbl(&k1);
+        Ok(Cmac {
+            key,
+            k1,
+            k2,
+            state: [0; 4],
+            buf: [0; 16],
+            buf_len: 0,
+        })
+    }
+
+    /// Reset the context to start a new message with the same key.
+    ///
+    /// This is done for you by [`finish`](Self::finish) and
+    /// [`verify`](Self::verify).
+    pub fn reset(&mut self) {
+        self.state = [0; 4];
+        self.buf = [0; 16];
+        self.buf_len = 0;
+    }
+
+    // CBC-MAC over the buffered block and complete blocks in `data`
+    fn process(&mut self, aes: &mut Aes, data: &[u8]) -> Result<(), Error> {
+        debug_assert_eq!(self.buf_len, 16);
+        debug_assert_eq!(data.len() % 16, 0);
+
+        aes.cbc_init(Mode::Encryption, self.key, &self.state);
+        let mut state: [u32; 4] = [0; 4];
+        aes.set_din_block(&self.buf);
+        aes.poll_completion()?;
+        aes.dout(&mut state);
+        aes.clear_ccf();
+        for block in data.chunks_exact(16) {
+            aes.set_din_block(block);
+            aes.poll_completion()?;
+            aes.dout(&mut state);
+            aes.clear_ccf();
+        }
+        self.state = state;
+        self.buf_len = 0;
+        Ok(())
+    }
+
+    /// Update the CMAC with message data.
+    ///
+    /// This may be called multiple times with any length of data.
+    /// The last block of data is buffered until more data is provided or the
+    /// CMAC is finished.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use stm32wl_hal::{
+    ///     aes::{Aes, Cmac},
+    ///     pac,
+    /// };
+    ///
+    /// let mut dp: pac::Peripherals = pac::Peripherals::take().unwrap();
+    /// let mut aes: Aes = Aes::new(dp.AES, &mut dp.RCC);
+    ///
+    /// const KEY: [u32; 4] = [0; 4];
+    /// let mut cmac: Cmac = Cmac::new(&mut aes, &KEY)?;
+    /// cmac.update(&mut aes, b"Hello, ")?;
+    /// cmac.update(&mut aes, b"World!")?;
+    /// let tag: [u32; 4] = cmac.finish(&mut aes)?;
+    /// # Ok::<(), stm32wl_hal::aes::Error>(())
+    /// ```
+    pub fn update(&mut self, aes: &mut Aes, data: &[u8]) -> Result<(), Error> {
+        let n: usize = core::cmp::min(16 - self.buf_len, data.len());
+        self.buf[self.buf_len..self.buf_len + n].copy_from_slice(&data[..n]);
+        self.buf_len += n;
+        let data: &[u8] = &data[n..];
+
+        // the buffered block is only processed when more data follows
+        // because the final block requires the subkey
+        if data.is_empty() {
+            return Ok(());
+        }
+
+        let keep: usize = match data.len() % 16 {
+            0 => 16,
+            x => x,
+        };
+        let (blocks, remain) = data.split_at(data.len() - keep);
+        self.process(aes, blocks)?;
+        self.buf[..remain.len()].copy_from_slice(remain);
+        self.buf_len = remain.len();
+        Ok(())
+    }
+
+    /// Update the CMAC with a complete block.
+    ///
+    /// This is a fast path for word-aligned blocks, such as the LoRaWAN B0 and
+    /// B1 blocks that are prepended to the message.
+    /// If the message data provided so far is not a multiple of 16 bytes this
+    /// falls back to [`update`](Self::update).
+    pub fn update_block(&mut self, aes: &mut Aes, block: &[u32; 4]) -> Result<(), Error> {
+        match self.buf_len {
+            0 => {
+                self.buf = be_bytes_from_words(block);
+                self.buf_len = 16;
+                Ok(())
+            }
+            16 => {
+                self.process(aes, &[])?;
+                self.buf = be_bytes_from_words(block);
+                self.buf_len = 16;
+                Ok(())
+            }
+            _ => self.update(aes, &be_bytes_from_words(block)),
+        }
+    }
+
+    /// Finish the CMAC computation and return the tag.
+    ///
+    /// The context is reset for a new message with the same key.
+    ///
+    /// The tag may be truncated by the caller, LoRaWAN uses the first word
+    /// as the MIC.
+    pub fn finish(&mut self, aes: &mut Aes) -> Result<[u32; 4], Error> {
+        let subkey: &[u32; 4] = if self.buf_len == 16 {
+            &self.k1
+        } else {
+            // pad the incomplete block, RFC 4493 section 2.4
+            self.buf[self.buf_len] = 0x80;
+            self.buf[self.buf_len + 1..].iter_mut().for_each(|b| *b = 0);
+            &self.k2
+        };
+
+        let mut tag: [u32; 4] = words_from_be_bytes(&self.buf);
+        tag.iter_mut()
+            .zip(subkey.iter().zip(self.state.iter()))
+            .for_each(|(t, (k, s))| *t ^= k ^ s);
+
+        let result: Result<(), Error> = aes.encrypt_ecb_inplace(self.key, &mut tag);
+        self.reset();
+        result.map(|()| tag)
+    }
+
+    /// Finish the CMAC computation and verify the tag.
+    ///
+    /// The tag is compared in constant time, and may be truncated to any
+    /// length from 1 to 16 bytes.
+    /// The context is reset for a new message with the same key.
+    ///
+    /// # Panics
+    ///
+    /// * Tag is empty or longer than 16 bytes.
+    pub fn verify(&mut self, aes: &mut Aes, tag: &[u8]) -> Result<(), Error> {
+        assert!(
+            (1..=16).contains(&tag.len()),
+            "Tag must be 1 to 16 bytes long not {} bytes",
+            tag.len()
+        );
+        let computed: [u8; 16] = be_bytes_from_words(&self.finish(aes)?);
+        if ct_eq(&computed[..tag.len()], tag) {
+            Ok(())
+        } else {
+            Err(Error::Tag)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::dbl;
+
+    // RFC 4493 section 4, subkey generation
+    #[test]
+    fn subkeys() {
+        const L: [u32; 4] = [0x7df76b0c, 0x1ab899b3, 0x3e42f047, 0xb91b546f];
+        const K1: [u32; 4] = [0xfbeed618, 0x35713366, 0x7c85e08f, 0x7236a8de];
+        const K2: [u32; 4] = [0xf7ddac30, 0x6ae266cc, 0xf90bc11e, 0xe46d513b];
+        assert_eq!(dbl(&L), K1);
+        assert_eq!(dbl(&K1), K2);
+    }
+}
@@ -0,0 +1,220 @@
+use super::{Aes, Algorithm, Error, KeySize, Mode};
+use crate::pac;
+
+/// Saved AES peripheral context.
+///
+/// This is created by [`Aes::suspend`] and consumed by [`Aes::resume`].
+///
+/// The context contains the control register, the initialization vector
+/// registers, and the GCM/CCM suspend registers.
+/// The key registers are write-only, the key is not part of the context.
+///
+/// The `Debug` and `defmt::Format` implementations do not print the
+/// saved registers, the suspend registers hold intermediate cipher state.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Context {
+    en: bool,
+    datatype: u8,
+    mode: u8,
+    chmod: u8,
+    chmod2: bool,
+    ccfie: bool,
+    errie: bool,
+    gcmph: u8,
+    keysize: bool,
+    npblb: u8,
+    iv: [u32; 4],
+    susp: [u32; 8],
+}
+
+impl core::fmt::Debug for Context {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("Context { .. }")
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for Context {
+    fn format(&self, fmt: defmt::Formatter) {
+        defmt::write!(fmt, "Context {{ .. }}")
+    }
+}
+
+impl Context {
+    /// Returns `true` if the AES peripheral was enabled when the context was
+    /// saved.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use stm32wl_hal::{
+    ///     aes::{Aes, Context},
+    ///     pac,
+    /// };
+    ///
+    /// let mut dp: pac::Peripherals = pac::Peripherals::take().unwrap();
+    /// let mut aes: Aes = Aes::new(dp.AES, &mut dp.RCC);
+    ///
+    /// let ctx: Context = aes.suspend();
+    /// assert!(!ctx.enabled());
+    /// ```
+    pub const fn enabled(&self) -> bool {
+        self.en
+    }
+
+    // ECB and CBC decryption with a separate key derivation step,
+    // the decryption key must be derived again after the key is written
+    fn key_derivation(&self) -> Option<Algorithm> {
+        const DECRYPTION: u8 = Mode::Decryption.bits();
+
+        if self.chmod2 || self.mode != DECRYPTION {
+            None
+        } else if self.chmod == Algorithm::Ecb.chmod10() {
+            Some(Algorithm::Ecb)
+        } else if self.chmod == Algorithm::Cbc.chmod10() {
+            Some(Algorithm::Cbc)
+        } else {
+            None
+        }
+    }
+}
+
+impl Aes {
+    /// Suspend the current AES operation and save the peripheral context.
+    ///
+    /// This allows a higher priority task to pre-empt a long running AES
+    /// operation, such as a bulk encryption with the [`Gcm`](super::Gcm)
+    /// context, and use the AES peripheral for something else.
+    /// The suspended operation is continued after calling
+    /// [`resume`](Self::resume) with the saved context.
+    ///
+    /// The AES peripheral is disabled after saving the context.
+    ///
+    /// This must be called on a block boundary, the pre-empted code must not
+    /// be in the middle of writing or reading a block, and DMA transfers
+    /// must not be in progress.
+    /// With the drivers in this module this is the case between method
+    /// calls.
+    ///
+    /// # Panics
+    ///
+    /// * An AES DMA transfer is in progress.
+    ///
+    /// # Example
+    ///
+    /// Compute a LoRaWAN MIC while a firmware image is being decrypted with
+    /// a different key.
+    ///
+    /// ```no_run
+    /// use stm32wl_hal::{
+    ///     aes::{Aes, Cmac, Context},
+    ///     pac,
+    /// };
+    ///
+    /// // in a high priority interrupt handler
+    /// let mut aes: Aes = unsafe { Aes::steal() };
+    ///
+    /// const FW_KEY: [u32; 4] = [0; 4];
+    /// const NWK_S_KEY: [u32; 4] = [1; 4];
+    ///
+    /// let ctx: Context = aes.suspend();
+    ///
+    /// let mut cmac: Cmac = Cmac::new(&mut aes, &NWK_S_KEY)?;
+    /// cmac.update(&mut aes, b"LoRaWAN frame")?;
+    /// let mic: u32 = cmac.finish(&mut aes)?[0];
+    ///
+    /// aes.resume(&FW_KEY, &ctx)?;
+    /// # Ok::<(), stm32wl_hal::aes::Error>(())
+    /// ```
+    pub fn suspend(&mut self) -> Context {
+        let cr: pac::aes::cr::R = self.aes.cr.read();
+        assert!(
+            cr.dmainen().is_disabled() && cr.dmaouten().is_disabled(),
+            "AES DMA transfer in progress"
+        );
+
+        let ctx: Context = Context {
+            en: cr.en().bit_is_set(),
+            datatype: cr.datatype().bits(),
+            mode: cr.mode().bits(),
+            chmod: cr.chmod().bits(),
+            chmod2: cr.chmod2().bit_is_set(),
+            ccfie: cr.ccfie().bit_is_set(),
+            errie: cr.errie().bit_is_set(),
+            gcmph: cr.gcmph().bits(),
+            keysize: cr.keysize().bit_is_set(),
+            npblb: cr.npblb().bits(),
+            iv: [
+                self.aes.ivr3.read().bits(),
+                self.aes.ivr2.read().bits(),
+                self.aes.ivr1.read().bits(),
+                self.aes.ivr0.read().bits(),
+            ],
+            susp: [
+                self.aes.susp0r.read().bits(),
+                self.aes.susp1r.read().bits(),
+                self.aes.susp2r.read().bits(),
+                self.aes.susp3r.read().bits(),
+                self.aes.susp4r.read().bits(),
+                self.aes.susp5r.read().bits(),
+                self.aes.susp6r.read().bits(),
+                self.aes.susp7r.read().bits(),
+            ],
+        };
+
+        self.aes.cr.modify(|_, w| w.en().disabled());
+        ctx
+    }
+
+    /// Resume an AES operation from a saved context.
+    ///
+    /// The key must be the same key the suspended operation was started
+    /// with.
+    ///
+    /// # Panics
+    ///
+    /// * Key is not 128-bits long `[u32; 4]` or 256-bits long `[u32; 8]`.
+    ///
+    /// # Example
+    ///
+    /// See [`suspend`](Self::suspend).
+    pub fn resume(&mut self, key: &[u32], ctx: &Context) -> Result<(), Error> {
+        let keysize: KeySize = self.set_key(key);
+        if let Some(algo) = ctx.key_derivation() {
+            self.derive_decryption_key(algo, keysize)?;
+        }
+
+        self.restore_cr(ctx, false);
+        self.set_iv(&ctx.iv);
+        self.aes.susp0r.write(|w| unsafe { w.bits(ctx.susp[0]) });
+        self.aes.susp1r.write(|w| unsafe { w.bits(ctx.susp[1]) });
+        self.aes.susp2r.write(|w| unsafe { w.bits(ctx.susp[2]) });
+        self.aes.susp3r.write(|w| unsafe { w.bits(ctx.susp[3]) });
+        self.aes.susp4r.write(|w| unsafe { w.bits(ctx.susp[4]) });
+        self.aes.susp5r.write(|w| unsafe { w.bits(ctx.susp[5]) });
+        self.aes.susp6r.write(|w| unsafe { w.bits(ctx.susp[6]) });
+        self.aes.susp7r.write(|w| unsafe { w.bits(ctx.susp[7]) });
+        self.restore_cr(ctx, ctx.en);
+
+        Ok(())
+    }
+
+    fn restore_cr(&mut self, ctx: &Context, en: bool) {
+        #[rustfmt::skip]
+        self.aes.cr.write(|w|
+            w
+                .en().bit(en)
+                .datatype().bits(ctx.datatype)
+                .mode().bits(ctx.mode)
+                .chmod2().bit(ctx.chmod2)
+                .chmod().bits(ctx.chmod)
+                .ccfie().bit(ctx.ccfie)
+                .errie().bit(ctx.errie)
+                .dmainen().disabled()
+                .dmaouten().disabled()
+                .gcmph().bits(ctx.gcmph)
+                .keysize().bit(ctx.keysize)
+                .npblb().bits(ctx.npblb)
+        );
+    }
+}
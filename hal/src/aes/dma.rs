@@ -0,0 +1,440 @@
+use super::{Aes, Algorithm, Error, KeySize, Mode};
+use crate::{
+    dma::{self, DmaCh},
+    pac::{self, aes::cr::GCMPH_A},
+};
+use core::sync::atomic::{compiler_fence, Ordering::SeqCst};
+
+use pac::dmamux::c0cr::DMAREQ_ID_A::{AES_IN, AES_OUT};
+
+const DMA_IN_ID: u8 = AES_IN as u8;
+const DMA_OUT_ID: u8 = AES_OUT as u8;
+
+/// AES driver with DMA transfers.
+///
+/// This moves the payload through a pair of DMA channels instead of
+/// polling the data registers word-by-word.
+/// The DMA methods operate on complete blocks, the buffers are processed
+/// in-place.
+///
+/// The polling methods of the [`Aes`] driver are available with
+/// [`aes`](Self::aes).
+///
+/// # Example
+///
+/// ```no_run
+/// use stm32wl_hal::{
+///     aes::AesDma,
+///     dma::{AllDma, Dma1Ch1, Dma1Ch2},
+///     pac,
+/// };
+///
+/// let mut dp: pac::Peripherals = pac::Peripherals::take().unwrap();
+/// let dma: AllDma = AllDma::split(dp.DMAMUX, dp.DMA1, dp.DMA2, &mut dp.RCC);
+/// let mut aes: AesDma<Dma1Ch1, Dma1Ch2> = AesDma::new(dp.AES, (dma.d1.c1, dma.d1.c2), &mut dp.RCC);
+///
+/// const KEY: [u32; 4] = [0; 4];
+///
+/// // a 4 KiB log page
+/// let mut page: [[u32; 4]; 256] = [[0; 4]; 256];
+/// aes.encrypt_ecb_inplace(&KEY, &mut page)?;
+/// # Ok::<(), stm32wl_hal::aes::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct AesDma<IN, OUT> {
+    aes: Aes,
+    dma_in: IN,
+    dma_out: OUT,
+}
+
+impl<IN: DmaCh, OUT: DmaCh> AesDma<IN, OUT> {
+    /// Create a new AES driver from an AES peripheral and a pair of DMA
+    /// channels.
+    ///
+    /// The first channel moves data into the AES peripheral, the second
+    /// channel moves data out of the AES peripheral.
+    ///
+    /// This will enable clocks and reset the AES peripheral.
+    ///
+    /// # Example
+    ///
+    /// See [`AesDma`].
+    pub fn new(aes: pac::AES, dmas: (IN, OUT), rcc: &mut pac::RCC) -> Self {
+        Aes::new(aes, rcc).with_dma(dmas)
+    }
+
+    pub(super) fn from_aes(aes: Aes, mut dmas: (IN, OUT)) -> Self {
+        let dinr: u32 = &aes.aes.dinr as *const _ as u32;
+        let doutr: u32 = &aes.aes.doutr as *const _ as u32;
+
+        dmas.0.set_cr(dma::Cr::DISABLE);
+        dmas.0.clear_all_flags();
+        dmas.0.set_periph_addr(dinr);
+        dmas.0.set_mux_cr_reqid(DMA_IN_ID);
+
+        dmas.1.set_cr(dma::Cr::DISABLE);
+        dmas.1.clear_all_flags();
+        dmas.1.set_periph_addr(doutr);
+        dmas.1.set_mux_cr_reqid(DMA_OUT_ID);
+
+        AesDma {
+            aes,
+            dma_in: dmas.0,
+            dma_out: dmas.1,
+        }
+    }
+
+    /// Free the AES driver and the DMA channels.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use stm32wl_hal::{
+    ///     aes::{Aes, AesDma},
+    ///     dma::{AllDma, Dma1Ch1, Dma1Ch2},
+    ///     pac,
+    /// };
+    ///
+    /// let mut dp: pac::Peripherals = pac::Peripherals::take().unwrap();
+    /// let dma: AllDma = AllDma::split(dp.DMAMUX, dp.DMA1, dp.DMA2, &mut dp.RCC);
+    /// let aes: AesDma<Dma1Ch1, Dma1Ch2> = AesDma::new(dp.AES, (dma.d1.c1, dma.d1.c2), &mut dp.RCC);
+    /// // ... use AES
+    /// let (aes, dma_in, dma_out): (Aes, Dma1Ch1, Dma1Ch2) = aes.free();
+    /// ```
+    pub fn free(self) -> (Aes, IN, OUT) {
+        (self.aes, self.dma_in, self.dma_out)
+    }
+
+    /// Get the polling AES driver.
+    pub fn aes(&mut self) -> &mut Aes {
+        &mut self.aes
+    }
+
+    fn set_cr(&mut self, algo: Algorithm, mode: Mode, keysize: KeySize, gcmph: GCMPH_A, en: bool) {
+        let chmod2: bool = algo.chmod2();
+        let chmod10: u8 = algo.chmod10();
+        let mode: u8 = mode.bits();
+
+        #[rustfmt::skip]
+        self.aes.aes.cr.write(|w|
+            w
+                .en().bit(en)
+                .datatype().none()
+                .mode().bits(mode)
+                .chmod2().bit(chmod2)
+                .chmod().bits(chmod10)
+                .ccfc().clear()
+                .errc().clear()
+                .ccfie().disabled()
+                .errie().disabled()
+                .dmainen().disabled()
+                .dmaouten().disabled()
+                .gcmph().variant(gcmph)
+                .keysize().variant(keysize)
+                .npblb().bits(0) // no padding, complete blocks only
+        );
+    }
+
+    fn xfer(&mut self, buf: &mut [[u32; 4]]) -> Result<(), Error> {
+        const IN_CR: dma::Cr = dma::Cr::RESET
+            .set_dir_from_mem()
+            .set_mem_inc(true)
+            .set_mem_size(dma::Size::Bits32)
+            .set_periph_size(dma::Size::Bits32)
+            .set_enable(true);
+        const OUT_CR: dma::Cr = dma::Cr::RESET
+            .set_dir_from_periph()
+            .set_mem_inc(true)
+            .set_mem_size(dma::Size::Bits32)
+            .set_periph_size(dma::Size::Bits32)
+            .set_enable(true);
+
+        // the output block is written after the input block is read,
+        // in-place transfers are safe
+        let ndt: u32 = (buf.len() * 4) as u32;
+        self.dma_in.set_mem_addr(buf.as_ptr() as u32);
+        self.dma_out.set_mem_addr(buf.as_mut_ptr() as u32);
+        self.dma_in.set_num_data_xfer(ndt);
+        self.dma_out.set_num_data_xfer(ndt);
+
+        // tell the compiler the memory in buf must be written before the DMA
+        compiler_fence(SeqCst);
+
+        // OUT must come before IN
+        self.dma_out.set_cr(OUT_CR);
+        self.dma_in.set_cr(IN_CR);
+        self.aes
+            .aes
+            .cr
+            .modify(|_, w| w.dmainen().enabled().dmaouten().enabled().en().enabled());
+
+        let ret: Result<(), Error> = loop {
+            let sr = self.aes.aes.sr.read();
+            if sr.wrerr().bit_is_set() {
+                break Err(Error::Write);
+            }
+            if sr.rderr().bit_is_set() {
+                break Err(Error::Read);
+            }
+            let in_flags: u8 = self.dma_in.flags();
+            let out_flags: u8 = self.dma_out.flags();
+            if in_flags & dma::flags::XFER_ERR != 0 || out_flags & dma::flags::XFER_ERR != 0 {
+                break Err(Error::Dma);
+            }
+            if out_flags & dma::flags::XFER_CPL != 0 {
+                break Ok(());
+            }
+        };
+
+        self.aes
+            .aes
+            .cr
+            .modify(|_, w| w.dmainen().disabled().dmaouten().disabled().ccfc().clear());
+
+        // IN must come before OUT
+        self.dma_in.set_cr(dma::Cr::DISABLE);
+        self.dma_out.set_cr(dma::Cr::DISABLE);
+        self.dma_in.clear_all_flags();
+        self.dma_out.clear_all_flags();
+
+        // tell the compiler the memory in buf may have changed
+        compiler_fence(SeqCst);
+        // tell the cpu the memory in buf may have changed
+        cortex_m::asm::dmb();
+
+        ret
+    }
+
+    fn ecb_cbc(
+        &mut self,
+        algo: Algorithm,
+        mode: Mode,
+        key: &[u32],
+        iv: Option<&[u32; 4]>,
+        buf: &mut [[u32; 4]],
+    ) -> Result<(), Error> {
+        let keysize: KeySize = self.aes.set_key(key);
+        if buf.is_empty() {
+            return Ok(());
+        }
+
+        if matches!(mode, Mode::Decryption) {
+            self.aes.derive_decryption_key(algo, keysize)?;
+        }
+        if let Some(iv) = iv {
+            self.aes.set_iv(iv);
+        }
+        self.set_cr(algo, mode, keysize, GCMPH_A::INIT, false);
+        self.xfer(buf)
+    }
+
+    /// Encrypt using the electronic codebook chaining (ECB) algorithm
+    /// in-place with DMA.
+    ///
+    /// # Panics
+    ///
+    /// * Key is not 128-bits long `[u32; 4]` or 256-bits long `[u32; 8]`.
+    ///
+    /// # Example
+    ///
+    /// See [`AesDma`].
+    pub fn encrypt_ecb_inplace(&mut self, key: &[u32], buf: &mut [[u32; 4]]) -> Result<(), Error> {
+        self.ecb_cbc(Algorithm::Ecb, Mode::Encryption, key, None, buf)
+    }
+
+    /// Decrypt using the electronic codebook chaining (ECB) algorithm
+    /// in-place with DMA.
+    ///
+    /// # Panics
+    ///
+    /// * Key is not 128-bits long `[u32; 4]` or 256-bits long `[u32; 8]`.
+    pub fn decrypt_ecb_inplace(&mut self, key: &[u32], buf: &mut [[u32; 4]]) -> Result<(), Error> {
+        self.ecb_cbc(Algorithm::Ecb, Mode::Decryption, key, None, buf)
+    }
+
+    /// Encrypt using the cipher block chaining (CBC) algorithm in-place
+    /// with DMA.
+    ///
+    /// # Panics
+    ///
+    /// * Key is not 128-bits long `[u32; 4]` or 256-bits long `[u32; 8]`.
+    pub fn encrypt_cbc_inplace(
+        &mut self,
+        key: &[u32],
+        iv: &[u32; 4],
+        buf: &mut [[u32; 4]],
+    ) -> Result<(), Error> {
+        self.ecb_cbc(Algorithm::Cbc, Mode::Encryption, key, Some(iv), buf)
+    }
+
+    /// Decrypt using the cipher block chaining (CBC) algorithm in-place
+    /// with DMA.
+    ///
+    /// # Panics
+    ///
+    /// * Key is not 128-bits long `[u32; 4]` or 256-bits long `[u32; 8]`.
+    pub fn decrypt_cbc_inplace(
+        &mut self,
+        key: &[u32],
+        iv: &[u32; 4],
+        buf: &mut [[u32; 4]],
+    ) -> Result<(), Error> {
+        self.ecb_cbc(Algorithm::Cbc, Mode::Decryption, key, Some(iv), buf)
+    }
+
+    fn ctr_inplace(
+        &mut self,
+        key: &[u32],
+        counter: &mut [u32; 4],
+        buf: &mut [[u32; 4]],
+    ) -> Result<(), Error> {
+        let keysize: KeySize = self.aes.set_key(key);
+        if buf.is_empty() {
+            return Ok(());
+        }
+
+        self.aes.set_iv(counter);
+        // CTR is symmetric, the keystream is always generated with encryption
+        self.set_cr(
+            Algorithm::Ctr,
+            Mode::Encryption,
+            keysize,
+            GCMPH_A::INIT,
+            false,
+        );
+        self.xfer(buf)?;
+        // the hardware increments the 32 least significant bits
+        counter[3] = counter[3].wrapping_add(buf.len() as u32);
+        Ok(())
+    }
+
+    /// Encrypt using the counter (CTR) algorithm in-place with DMA.
+    ///
+    /// The `counter` is updated in the same way as
+    /// [`Aes::encrypt_ctr_inplace`] to allow resuming.
+    ///
+    /// # Panics
+    ///
+    /// * Key is not 128-bits long `[u32; 4]` or 256-bits long `[u32; 8]`.
+    pub fn encrypt_ctr_inplace(
+        &mut self,
+        key: &[u32],
+        counter: &mut [u32; 4],
+        plaintext: &mut [[u32; 4]],
+    ) -> Result<(), Error> {
+        self.ctr_inplace(key, counter, plaintext)
+    }
+
+    /// Decrypt using the counter (CTR) algorithm in-place with DMA.
+    ///
+    /// The `counter` is updated in the same way as
+    /// [`Aes::decrypt_ctr_inplace`] to allow resuming.
+    ///
+    /// # Panics
+    ///
+    /// * Key is not 128-bits long `[u32; 4]` or 256-bits long `[u32; 8]`.
+    pub fn decrypt_ctr_inplace(
+        &mut self,
+        key: &[u32],
+        counter: &mut [u32; 4],
+        ciphertext: &mut [[u32; 4]],
+    ) -> Result<(), Error> {
+        self.ctr_inplace(key, counter, ciphertext)
+    }
+
+    fn gcm_inplace(
+        &mut self,
+        mode: Mode,
+        key: &[u32],
+        iv: &[u32; 3],
+        aad: &[u8],
+        buf: &mut [[u32; 4]],
+        tag: &mut [u32; 4],
+    ) -> Result<(), Error> {
+        const ALGO: Algorithm = Algorithm::Gcm;
+        let encrypt: bool = matches!(mode, Mode::Encryption);
+        let mode = || {
+            if encrypt {
+                Mode::Encryption
+            } else {
+                Mode::Decryption
+            }
+        };
+
+        // init phase
+        let keysize: KeySize = self.aes.set_key(key);
+        self.aes.set_iv(&[iv[0], iv[1], iv[2], 2]);
+        self.set_cr(ALGO, mode(), keysize, GCMPH_A::INIT, true);
+        self.aes.poll_completion()?;
+
+        // header phase, the associated data is usually small
+        for block in aad.chunks(16) {
+            self.set_cr(ALGO, mode(), keysize, GCMPH_A::HEADER, true);
+            self.aes.set_din_block(block);
+            self.aes.poll_completion()?;
+        }
+
+        // payload phase
+        self.set_cr(ALGO, mode(), keysize, GCMPH_A::PAYLOAD, true);
+        if !buf.is_empty() {
+            self.xfer(buf)?;
+        }
+
+        // final phase
+        self.set_cr(ALGO, mode(), keysize, GCMPH_A::FINAL, true);
+
+        // byte lengths to bit lengths
+        let aad_len: u64 = (aad.len() as u64) << 3;
+        let buf_len: u64 = (buf.len() as u64) << 7;
+
+        let dinr = &self.aes.aes.dinr;
+        dinr.write(|w| w.din().bits((aad_len >> 32) as u32));
+        dinr.write(|w| w.din().bits(aad_len as u32));
+        dinr.write(|w| w.din().bits((buf_len >> 32) as u32));
+        dinr.write(|w| w.din().bits(buf_len as u32));
+
+        self.aes.poll_completion()?;
+        self.aes.dout(tag);
+        Ok(())
+    }
+
+    /// Encrypt using the Galois counter mode (GCM) algorithm in-place with
+    /// DMA.
+    ///
+    /// The associated data is written by polling, the payload is moved with
+    /// DMA.
+    ///
+    /// # Panics
+    ///
+    /// * Key is not 128-bits long `[u32; 4]` or 256-bits long `[u32; 8]`.
+    pub fn encrypt_gcm_inplace(
+        &mut self,
+        key: &[u32],
+        iv: &[u32; 3],
+        aad: &[u8],
+        plaintext: &mut [[u32; 4]],
+        tag: &mut [u32; 4],
+    ) -> Result<(), Error> {
+        self.gcm_inplace(Mode::Encryption, key, iv, aad, plaintext, tag)
+    }
+
+    /// Decrypt using the Galois counter mode (GCM) algorithm in-place with
+    /// DMA.
+    ///
+    /// The resulting tag should be compared to the tag sent from the peer
+    /// to verify the authenticity of the message.
+    ///
+    /// # Panics
+    ///
+    /// * Key is not 128-bits long `[u32; 4]` or 256-bits long `[u32; 8]`.
+    pub fn decrypt_gcm_inplace(
+        &mut self,
+        key: &[u32],
+        iv: &[u32; 3],
+        aad: &[u8],
+        ciphertext: &mut [[u32; 4]],
+        tag: &mut [u32; 4],
+    ) -> Result<(), Error> {
+        self.gcm_inplace(Mode::Decryption, key, iv, aad, ciphertext, tag)
+    }
+}
@@ -0,0 +1,670 @@
+//! NIST SP800-90A CTR_DRBG.
+
+use super::{Aes, Error};
+use crate::{
+    rng::{self, Rng},
+    util::zeroize,
+};
+use core::num::NonZeroU32;
+
+// AES-256 key length, block length, and seed length in 32-bit words
+const KEY_DW: usize = 8;
+const BLOCK_DW: usize = 4;
+const SEED_DW: usize = KEY_DW + BLOCK_DW;
+
+// security strength of AES-256 in bytes, this is the length of the entropy
+// input, the nonce is half the security strength
+const ENTROPY_LEN: usize = 32;
+const NONCE_LEN: usize = 16;
+
+// SP800-90A table 3, the maximum number of bytes per request is 2^19 bits
+const MAX_REQUEST_LEN: usize = 1 << 16;
+
+// SP800-90A table 3, the maximum reseed interval is 2^48
+const MAX_RESEED_INTERVAL: u64 = 1 << 48;
+
+/// CTR_DRBG errors.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum DrbgError {
+    /// Entropy source error.
+    Rng(rng::Error),
+    /// Block cipher error.
+    Aes(Error),
+}
+
+impl From<rng::Error> for DrbgError {
+    fn from(e: rng::Error) -> Self {
+        DrbgError::Rng(e)
+    }
+}
+
+impl From<Error> for DrbgError {
+    fn from(e: Error) -> Self {
+        DrbgError::Aes(e)
+    }
+}
+
+impl From<DrbgError> for rand_core::Error {
+    fn from(e: DrbgError) -> Self {
+        match e {
+            DrbgError::Rng(e) => e.into(),
+            // safety: 3 is non-zero
+            DrbgError::Aes(_) => unsafe { NonZeroU32::new_unchecked(3) }.into(),
+        }
+    }
+}
+
+// block encryption with an AES-256 key, implemented by the AES driver and by
+// a software cipher in the host tests
+trait BlockEncrypt {
+    fn encrypt_block(
+        &mut self,
+        key: &[u32; KEY_DW],
+        block: &mut [u32; BLOCK_DW],
+    ) -> Result<(), Error>;
+}
+
+impl BlockEncrypt for Aes {
+    fn encrypt_block(
+        &mut self,
+        key: &[u32; KEY_DW],
+        block: &mut [u32; BLOCK_DW],
+    ) -> Result<(), Error> {
+        self.encrypt_ecb_inplace(key, block)
+    }
+}
+
+// V = (V + 1) mod 2^128
+fn increment(v: &mut [u32; BLOCK_DW]) {
+    for dw in v.iter_mut().rev() {
+        let (sum, carry) = dw.overflowing_add(1);
+        *dw = sum;
+        if !carry {
+            break;
+        }
+    }
+}
+
+// BCC function, SP800-90A section 10.3.3, over the string
+// IV || L || N || input || 0x80 || zero padding
+// where the IV is the block counter `i` padded to the block length
+fn bcc<C: BlockEncrypt>(
+    cipher: &mut C,
+    key: &[u32; KEY_DW],
+    i: u32,
+    input: &[&[u8]],
+) -> Result<[u32; BLOCK_DW], Error> {
+    let len: usize = input.iter().map(|s| s.len()).sum();
+    let len: [u8; 4] = (len as u32).to_be_bytes();
+    let n: [u8; 4] = ((SEED_DW * 4) as u32).to_be_bytes();
+
+    let mut chain: [u32; BLOCK_DW] = [i, 0, 0, 0];
+    cipher.encrypt_block(key, &mut chain)?;
+
+    let mut block: [u8; 16] = [0; 16];
+    let mut block_len: usize = 0;
+    let header: [&[u8]; 2] = [&len, &n];
+    let bytes = header
+        .iter()
+        .chain(input.iter())
+        .flat_map(|s| s.iter())
+        .chain(core::iter::once(&0x80_u8));
+    for &byte in bytes {
+        block[block_len] = byte;
+        block_len += 1;
+        if block_len == block.len() {
+            xor_block(&mut chain, &block);
+            cipher.encrypt_block(key, &mut chain)?;
+            block_len = 0;
+        }
+    }
+    if block_len != 0 {
+        block[block_len..].iter_mut().for_each(|b| *b = 0);
+        xor_block(&mut chain, &block);
+        cipher.encrypt_block(key, &mut chain)?;
+    }
+
+    Ok(chain)
+}
+
+fn xor_block(chain: &mut [u32; BLOCK_DW], block: &[u8; 16]) {
+    chain
+        .iter_mut()
+        .zip(block.chunks_exact(4))
+        .for_each(|(dw, b)| *dw ^= u32::from_be_bytes([b[0], b[1], b[2], b[3]]));
+}
+
+// Block_Cipher_df, SP800-90A section 10.3.2, returning seedlen bits
+fn block_cipher_df<C: BlockEncrypt>(
+    cipher: &mut C,
+    input: &[&[u8]],
+) -> Result<[u32; SEED_DW], Error> {
+    const DF_KEY: [u32; KEY_DW] = [
+        0x00010203, 0x04050607, 0x08090a0b, 0x0c0d0e0f, 0x10111213, 0x14151617, 0x18191a1b,
+        0x1c1d1e1f,
+    ];
+
+    let mut temp: [u32; SEED_DW] = [0; SEED_DW];
+    for (i, chunk) in temp.chunks_exact_mut(BLOCK_DW).enumerate() {
+        chunk.copy_from_slice(&bcc(cipher, &DF_KEY, i as u32, input)?);
+    }
+
+    let mut key: [u32; KEY_DW] = [0; KEY_DW];
+    key.copy_from_slice(&temp[..KEY_DW]);
+    let mut x: [u32; BLOCK_DW] = [0; BLOCK_DW];
+    x.copy_from_slice(&temp[KEY_DW..]);
+
+    for chunk in temp.chunks_exact_mut(BLOCK_DW) {
+        cipher.encrypt_block(&key, &mut x)?;
+        chunk.copy_from_slice(&x);
+    }
+
+    zeroize(&mut key);
+    zeroize(&mut x);
+    Ok(temp)
+}
+
+// CTR_DRBG working state, SP800-90A section 10.2.1.1
+//
+// the reseed counter is kept by the `CtrDrbg`
+struct State {
+    key: [u32; KEY_DW],
+    v: [u32; BLOCK_DW],
+}
+
+impl State {
+    const fn new() -> State {
+        State {
+            key: [0; KEY_DW],
+            v: [0; BLOCK_DW],
+        }
+    }
+
+    // CTR_DRBG_Update, SP800-90A section 10.2.1.2
+    fn update<C: BlockEncrypt>(
+        &mut self,
+        cipher: &mut C,
+        provided_data: &[u32; SEED_DW],
+    ) -> Result<(), Error> {
+        let mut temp: [u32; SEED_DW] = [0; SEED_DW];
+        for chunk in temp.chunks_exact_mut(BLOCK_DW) {
+            increment(&mut self.v);
+            let mut block: [u32; BLOCK_DW] = self.v;
+            cipher.encrypt_block(&self.key, &mut block)?;
+            chunk.copy_from_slice(&block);
+        }
+
+        temp.iter_mut()
+            .zip(provided_data.iter())
+            .for_each(|(t, p)| *t ^= p);
+        self.key.copy_from_slice(&temp[..KEY_DW]);
+        self.v.copy_from_slice(&temp[KEY_DW..]);
+        zeroize(&mut temp);
+        Ok(())
+    }
+
+    // derive a seed from the input and update the state with the seed,
+    // this is the instantiate and reseed algorithm with the derivation function
+    // SP800-90A sections 10.2.1.3.2 and 10.2.1.4.2
+    fn seed<C: BlockEncrypt>(&mut self, cipher: &mut C, input: &[&[u8]]) -> Result<(), Error> {
+        let mut seed: [u32; SEED_DW] = block_cipher_df(cipher, input)?;
+        let ret: Result<(), Error> = self.update(cipher, &seed);
+        zeroize(&mut seed);
+        ret
+    }
+
+    // CTR_DRBG_Generate_algorithm, SP800-90A section 10.2.1.5.2,
+    // without the reseed check
+    fn generate<C: BlockEncrypt>(
+        &mut self,
+        cipher: &mut C,
+        dest: &mut [u8],
+        additional_input: &[u8],
+    ) -> Result<(), Error> {
+        let mut additional: [u32; SEED_DW] = [0; SEED_DW];
+        if !additional_input.is_empty() {
+            additional = block_cipher_df(cipher, &[additional_input])?;
+            self.update(cipher, &additional)?;
+        }
+
+        for chunk in dest.chunks_mut(BLOCK_DW * 4) {
+            increment(&mut self.v);
+            let mut block: [u32; BLOCK_DW] = self.v;
+            cipher.encrypt_block(&self.key, &mut block)?;
+            chunk
+                .iter_mut()
+                .zip(block.iter().flat_map(|dw| dw.to_be_bytes()))
+                .for_each(|(d, b)| *d = b);
+            zeroize(&mut block);
+        }
+
+        let ret: Result<(), Error> = self.update(cipher, &additional);
+        zeroize(&mut additional);
+        ret
+    }
+
+    fn zeroize(&mut self) {
+        zeroize(&mut self.key);
+        zeroize(&mut self.v);
+    }
+}
+
+/// NIST SP800-90A CTR_DRBG.
+///
+/// This is a deterministic random bit generator using AES-256 as the block
+/// cipher with the derivation function, seeded with entropy from the
+/// [`Rng`].
+///
+/// Random bytes are generated by the AES peripheral, this is much faster
+/// than the [`Rng`] and does not wait for the entropy source.
+/// The [`Rng`] is only used to instantiate and reseed.
+///
+/// The DRBG is reseeded with new entropy after
+/// [`reseed_interval`](Self::reseed_interval) generate requests,
+/// or before every request when
+/// [prediction resistance](Self::set_prediction_resistance) is enabled.
+///
+/// The internal state is zeroed by [`free`](Self::free).
+///
+/// # Example
+///
+/// ```no_run
+/// use stm32wl_hal::{
+///     aes::{Aes, CtrDrbg},
+///     pac,
+///     rng::{rand_core::RngCore, Clk, Rng},
+/// };
+///
+/// let mut dp: pac::Peripherals = pac::Peripherals::take().unwrap();
+/// let aes: Aes = Aes::new(dp.AES, &mut dp.RCC);
+/// let rng: Rng = Rng::new(dp.RNG, Clk::MSI, &mut dp.RCC);
+///
+/// let mut drbg: CtrDrbg = CtrDrbg::new(aes, rng, b"my device")?;
+///
+/// let mut key: [u8; 32] = [0; 32];
+/// drbg.try_fill_bytes(&mut key)?;
+/// # Ok::<(), stm32wl_hal::rng::rand_core::Error>(())
+/// ```
+pub struct CtrDrbg {
+    aes: Aes,
+    rng: Rng,
+    state: State,
+    reseed_counter: u64,
+    reseed_interval: u64,
+    prediction_resistance: bool,
+}
+
+impl core::fmt::Debug for CtrDrbg {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("CtrDrbg { ... }")
+    }
+}
+
+impl CtrDrbg {
+    /// Instantiate a new CTR_DRBG.
+    ///
+    /// The entropy input and nonce are read from the [`Rng`].
+    /// The personalization string is optional, it should be unique to the
+    /// device, for example a serial number.
+    ///
+    /// The reseed interval defaults to 2<sup>16</sup> requests, and
+    /// prediction resistance is disabled.
+    ///
+    /// # Example
+    ///
+    /// See [`CtrDrbg`].
+    pub fn new(aes: Aes, mut rng: Rng, personalization: &[u8]) -> Result<CtrDrbg, DrbgError> {
+        let mut entropy: [u8; ENTROPY_LEN + NONCE_LEN] = [0; ENTROPY_LEN + NONCE_LEN];
+        let ret: Result<(), rng::Error> = rng.try_fill_u8(&mut entropy);
+        let drbg: Result<CtrDrbg, DrbgError> = ret.map_err(DrbgError::from).and_then(|()| {
+            let (entropy, nonce) = entropy.split_at(ENTROPY_LEN);
+            CtrDrbg::instantiate(aes, rng, entropy, nonce, personalization).map_err(DrbgError::from)
+        });
+        zeroize(&mut entropy);
+        drbg
+    }
+
+    /// Instantiate a new CTR_DRBG with the provided entropy input and nonce.
+    ///
+    /// This only exists for the on-target known-answer tests, the entropy
+    /// input must come from the [`Rng`] otherwise.
+    /// The [`Rng`] is only used for reseeding.
+    #[cfg(feature = "drbg-kat")]
+    pub fn with_entropy(
+        aes: Aes,
+        rng: Rng,
+        entropy: &[u8],
+        nonce: &[u8],
+        personalization: &[u8],
+    ) -> Result<CtrDrbg, Error> {
+        CtrDrbg::instantiate(aes, rng, entropy, nonce, personalization)
+    }
+
+    // CTR_DRBG_Instantiate_algorithm, SP800-90A section 10.2.1.3.2
+    fn instantiate(
+        mut aes: Aes,
+        rng: Rng,
+        entropy: &[u8],
+        nonce: &[u8],
+        personalization: &[u8],
+    ) -> Result<CtrDrbg, Error> {
+        let mut state: State = State::new();
+        state.seed(&mut aes, &[entropy, nonce, personalization])?;
+        Ok(CtrDrbg {
+            aes,
+            rng,
+            state,
+            reseed_counter: 1,
+            reseed_interval: 1 << 16,
+            prediction_resistance: false,
+        })
+    }
+
+    /// Free the AES and RNG drivers from the DRBG.
+    ///
+    /// The internal state is zeroed.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use stm32wl_hal::{
+    ///     aes::{Aes, CtrDrbg},
+    ///     pac,
+    ///     rng::{Clk, Rng},
+    /// };
+    ///
+    /// let mut dp: pac::Peripherals = pac::Peripherals::take().unwrap();
+    /// let aes: Aes = Aes::new(dp.AES, &mut dp.RCC);
+    /// let rng: Rng = Rng::new(dp.RNG, Clk::MSI, &mut dp.RCC);
+    ///
+    /// let drbg: CtrDrbg = CtrDrbg::new(aes, rng, &[])?;
+    /// // ... use the DRBG
+    /// let (aes, rng): (Aes, Rng) = drbg.free();
+    /// # Ok::<(), stm32wl_hal::aes::DrbgError>(())
+    /// ```
+    pub fn free(mut self) -> (Aes, Rng) {
+        self.state.zeroize();
+        (self.aes, self.rng)
+    }
+
+    /// Set the maximum number of generate requests between reseeds.
+    ///
+    /// # Panics
+    ///
+    /// * `interval` is zero or greater than 2<sup>48</sup>.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let aes = unsafe { stm32wl_hal::aes::Aes::steal() };
+    /// # let rng = unsafe { stm32wl_hal::rng::Rng::steal() };
+    /// # let mut drbg = stm32wl_hal::aes::CtrDrbg::new(aes, rng, &[])?;
+    /// drbg.set_reseed_interval(1024);
+    /// assert_eq!(drbg.reseed_interval(), 1024);
+    /// # Ok::<(), stm32wl_hal::aes::DrbgError>(())
+    /// ```
+    pub fn set_reseed_interval(&mut self, interval: u64) {
+        assert!(interval != 0 && interval <= MAX_RESEED_INTERVAL);
+        self.reseed_interval = interval
+    }
+
+    /// Maximum number of generate requests between reseeds.
+    pub fn reseed_interval(&self) -> u64 {
+        self.reseed_interval
+    }
+
+    /// Enable or disable prediction resistance.
+    ///
+    /// With prediction resistance enabled the DRBG is reseeded with new
+    /// entropy before every generate request.
+    /// This limits the generation speed to the speed of the [`Rng`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let aes = unsafe { stm32wl_hal::aes::Aes::steal() };
+    /// # let rng = unsafe { stm32wl_hal::rng::Rng::steal() };
+    /// # let mut drbg = stm32wl_hal::aes::CtrDrbg::new(aes, rng, &[])?;
+    /// drbg.set_prediction_resistance(true);
+    /// assert!(drbg.prediction_resistance());
+    /// # Ok::<(), stm32wl_hal::aes::DrbgError>(())
+    /// ```
+    pub fn set_prediction_resistance(&mut self, en: bool) {
+        self.prediction_resistance = en
+    }
+
+    /// Returns `true` if prediction resistance is enabled.
+    pub fn prediction_resistance(&self) -> bool {
+        self.prediction_resistance
+    }
+
+    /// Reseed the DRBG with new entropy from the [`Rng`].
+    ///
+    /// The additional input is optional.
+    ///
+    /// Reseeding happens automatically after
+    /// [`reseed_interval`](Self::reseed_interval) requests, this is only
+    /// required to inject new entropy sooner.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let aes = unsafe { stm32wl_hal::aes::Aes::steal() };
+    /// # let rng = unsafe { stm32wl_hal::rng::Rng::steal() };
+    /// # let mut drbg = stm32wl_hal::aes::CtrDrbg::new(aes, rng, &[])?;
+    /// drbg.reseed(&[])?;
+    /// # Ok::<(), stm32wl_hal::aes::DrbgError>(())
+    /// ```
+    pub fn reseed(&mut self, additional_input: &[u8]) -> Result<(), DrbgError> {
+        let mut entropy: [u8; ENTROPY_LEN] = [0; ENTROPY_LEN];
+        let ret: Result<(), DrbgError> = self
+            .rng
+            .try_fill_u8(&mut entropy)
+            .map_err(DrbgError::from)
+            .and_then(|()| {
+                self.reseed_entropy(&entropy, additional_input)
+                    .map_err(DrbgError::from)
+            });
+        zeroize(&mut entropy);
+        ret
+    }
+
+    /// Reseed the DRBG with the provided entropy input.
+    ///
+    /// This only exists for the on-target known-answer tests, the entropy
+    /// input must come from the [`Rng`] otherwise.
+    #[cfg(feature = "drbg-kat")]
+    pub fn reseed_with_entropy(
+        &mut self,
+        entropy: &[u8],
+        additional_input: &[u8],
+    ) -> Result<(), Error> {
+        self.reseed_entropy(entropy, additional_input)
+    }
+
+    // CTR_DRBG_Reseed_algorithm, SP800-90A section 10.2.1.4.2
+    fn reseed_entropy(&mut self, entropy: &[u8], additional_input: &[u8]) -> Result<(), Error> {
+        self.state
+            .seed(&mut self.aes, &[entropy, additional_input])?;
+        self.reseed_counter = 1;
+        Ok(())
+    }
+
+    /// Generate random bytes.
+    ///
+    /// The additional input is optional.
+    ///
+    /// Requests longer than 2<sup>16</sup> bytes are split into multiple
+    /// requests.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let aes = unsafe { stm32wl_hal::aes::Aes::steal() };
+    /// # let rng = unsafe { stm32wl_hal::rng::Rng::steal() };
+    /// # let mut drbg = stm32wl_hal::aes::CtrDrbg::new(aes, rng, &[])?;
+    /// let mut nonce: [u8; 12] = [0; 12];
+    /// drbg.generate(&mut nonce, b"nonce")?;
+    /// # Ok::<(), stm32wl_hal::aes::DrbgError>(())
+    /// ```
+    pub fn generate(&mut self, dest: &mut [u8], additional_input: &[u8]) -> Result<(), DrbgError> {
+        for chunk in dest.chunks_mut(MAX_REQUEST_LEN) {
+            self.generate_request(chunk, additional_input)?;
+        }
+        Ok(())
+    }
+
+    // CTR_DRBG_Generate_algorithm, SP800-90A section 10.2.1.5.2
+    fn generate_request(
+        &mut self,
+        dest: &mut [u8],
+        mut additional_input: &[u8],
+    ) -> Result<(), DrbgError> {
+        if self.prediction_resistance || self.reseed_counter > self.reseed_interval {
+            self.reseed(additional_input)?;
+            additional_input = &[];
+        }
+
+        self.state.generate(&mut self.aes, dest, additional_input)?;
+        self.reseed_counter += 1;
+        Ok(())
+    }
+}
+
+impl rand_core::RngCore for CtrDrbg {
+    /// Not recommended for use, panics upon errors.
+    fn next_u32(&mut self) -> u32 {
+        let mut buf: [u8; 4] = [0; 4];
+        unwrap!(self.generate(&mut buf, &[]));
+        u32::from_le_bytes(buf)
+    }
+
+    /// Not recommended for use, panics upon errors.
+    fn next_u64(&mut self) -> u64 {
+        let mut buf: [u8; 8] = [0; 8];
+        unwrap!(self.generate(&mut buf, &[]));
+        u64::from_le_bytes(buf)
+    }
+
+    /// Not recommended for use, panics upon errors.
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        unwrap!(self.generate(dest, &[]))
+    }
+
+    /// Use this method if using the `RngCore` for `CryptoRng` traits.
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        self.generate(dest, &[])?;
+        Ok(())
+    }
+}
+
+impl rand_core::CryptoRng for CtrDrbg {}
+
+#[cfg(test)]
+mod test {
+    use super::{increment, BlockEncrypt, Error, State, BLOCK_DW, KEY_DW};
+    use aes::{
+        cipher::{BlockEncrypt as _, KeyInit},
+        Aes256,
+    };
+    use hex_literal::hex;
+
+    struct SoftAes;
+
+    impl BlockEncrypt for SoftAes {
+        fn encrypt_block(
+            &mut self,
+            key: &[u32; KEY_DW],
+            block: &mut [u32; BLOCK_DW],
+        ) -> Result<(), Error> {
+            let mut key_bytes: [u8; 32] = [0; 32];
+            key_bytes
+                .chunks_exact_mut(4)
+                .zip(key.iter())
+                .for_each(|(b, dw)| b.copy_from_slice(&dw.to_be_bytes()));
+            let mut block_bytes: [u8; 16] = [0; 16];
+            block_bytes
+                .chunks_exact_mut(4)
+                .zip(block.iter())
+                .for_each(|(b, dw)| b.copy_from_slice(&dw.to_be_bytes()));
+
+            Aes256::new(&key_bytes.into()).encrypt_block((&mut block_bytes).into());
+
+            block
+                .iter_mut()
+                .zip(block_bytes.chunks_exact(4))
+                .for_each(|(dw, b)| *dw = u32::from_be_bytes([b[0], b[1], b[2], b[3]]));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn increment_carry() {
+        let mut v: [u32; 4] = [0, 0, 0xFFFF_FFFF, 0xFFFF_FFFF];
+        increment(&mut v);
+        assert_eq!(v, [0, 1, 0, 0]);
+
+        let mut v: [u32; 4] = [0xFFFF_FFFF; 4];
+        increment(&mut v);
+        assert_eq!(v, [0; 4]);
+    }
+
+    // NIST CAVP CTR_DRBG AES-256 use df, no prediction resistance,
+    // reseed before the first generate, 512-bit returned bits, COUNT = 0
+    #[test]
+    fn cavp_aes256_use_df() {
+        const ENTROPY: [u8; 32] =
+            hex!("2d4c9f46b981c6a0b2b5d8c69391e569ff13851437ebc0fc00d616340252fed5");
+        const NONCE: [u8; 16] = hex!("0bf814b411f65ec4866be1abb59d3c32");
+        const ENTROPY_RESEED: [u8; 32] =
+            hex!("93500fae4fa32b86033b7a7bac9d37e710dcc67ca266bc8607d665937766d207");
+        const RETURNED: [u8; 64] = hex!(
+            "322dd28670e75c0ea638f3cb68d6a9d6e50ddfd052b772a7b1d78263a7b8978b"
+            "6740c2b65a9550c3a76325866fa97e16d74006bc96f26249b9f0a90d076f08e5"
+        );
+
+        let mut state: State = State::new();
+        let mut buf: [u8; 64] = [0; 64];
+        state.seed(&mut SoftAes, &[&ENTROPY, &NONCE, &[]]).unwrap();
+        state.seed(&mut SoftAes, &[&ENTROPY_RESEED, &[]]).unwrap();
+        state.generate(&mut SoftAes, &mut buf, &[]).unwrap();
+        state.generate(&mut SoftAes, &mut buf, &[]).unwrap();
+        assert_eq!(buf, RETURNED);
+    }
+
+    // NIST CAVP CTR_DRBG AES-256 use df, prediction resistance,
+    // 256-bit personalization string and additional input, COUNT = 0
+    #[test]
+    fn cavp_aes256_use_df_pr() {
+        const ENTROPY: [u8; 32] =
+            hex!("6168fc1af0b5956b85099b743f1378493b85ec93133ba94f96ab2ce4c88fdd6a");
+        const NONCE: [u8; 16] = hex!("add2bbbab76589c3216c55332b36ffa4");
+        const PERSONALIZATION: [u8; 32] =
+            hex!("6ecae72072d3845a32d34b2472c4632b9d12240c23268e8316370bd1064f686d");
+        const ENTROPY_PR_1: [u8; 32] =
+            hex!("0b23afdff162d7d34397f87704a84220bdf60fc1172f9f54bb561786680ebaa9");
+        const ADDITIONAL_1: [u8; 32] =
+            hex!("7e084abbe3217cc923d2f8b07398ba847423ab068ae222d37bce9bd24a76b8de");
+        const ENTROPY_PR_2: [u8; 32] =
+            hex!("bf6c592a0d440fae9a5e0373d8a6e1cf25613824869e53e8a4df56f406079c0f");
+        const ADDITIONAL_2: [u8; 32] =
+            hex!("946bc99fab8dc5ec71881d008c8968e4c8077736176d7978c7064e99042829c3");
+        const RETURNED: [u8; 16] = hex!("224ab4b8b6ee7db19ec9f9a0d9e29700");
+
+        let mut state: State = State::new();
+        let mut buf: [u8; 16] = [0; 16];
+        state
+            .seed(&mut SoftAes, &[&ENTROPY, &NONCE, &PERSONALIZATION])
+            .unwrap();
+        state
+            .seed(&mut SoftAes, &[&ENTROPY_PR_1, &ADDITIONAL_1])
+            .unwrap();
+        state.generate(&mut SoftAes, &mut buf, &[]).unwrap();
+        state
+            .seed(&mut SoftAes, &[&ENTROPY_PR_2, &ADDITIONAL_2])
+            .unwrap();
+        state.generate(&mut SoftAes, &mut buf, &[]).unwrap();
+        assert_eq!(buf, RETURNED);
+    }
+}
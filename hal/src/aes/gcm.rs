@@ -0,0 +1,259 @@
+use super::{be_bytes_from_words, ct_eq, Aes, Algorithm, Error, KeySize, Mode};
+use crate::pac::aes::cr::GCMPH_A;
+
+/// Incremental Galois counter mode (GCM) context.
+///
+/// Unlike [`Aes::encrypt_gcm_inplace`] and [`Aes::decrypt_gcm_inplace`] the
+/// associated data and payload do not need to be in one contiguous buffer.
+/// The GCM state is held by the AES peripheral, the context borrows the
+/// driver until the tag is computed.
+///
+/// The associated data may be provided in any number of chunks of any length.
+/// All payload buffers except the last must have a length that is a multiple
+/// of 16 bytes.
+///
+/// # Example
+///
+/// Authenticate and decrypt a firmware image in chunks.
+///
+/// ```no_run
+/// use stm32wl_hal::{
+///     aes::{Aes, Gcm},
+///     pac,
+/// };
+///
+/// let mut dp: pac::Peripherals = pac::Peripherals::take().unwrap();
+/// let mut aes: Aes = Aes::new(dp.AES, &mut dp.RCC);
+///
+/// const KEY: [u32; 4] = [0; 4];
+/// const IV: [u32; 3] = [0; 3];
+/// const TAG: [u32; 4] = [0; 4];
+///
+/// let mut gcm: Gcm = Gcm::init_decrypt(&mut aes, &KEY, &IV)?;
+/// gcm.update_aad(b"firmware v1.2.3")?;
+///
+/// let mut chunk: [u8; 256] = [0; 256];
+/// for _ in 0..16 {
+///     // ... read the next chunk from external flash
+///     gcm.update(&mut chunk)?;
+///     // ... write the decrypted chunk to a staging area
+/// }
+///
+/// gcm.finish_decrypt(&TAG)?;
+/// // the image is authentic and can be installed
+/// # Ok::<(), stm32wl_hal::aes::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct Gcm<'a> {
+    aes: &'a mut Aes,
+    mode: u8,
+    keysize: KeySize,
+    aad_buf: [u8; 16],
+    aad_buf_len: usize,
+    aad_len: u64,
+    payload_len: u64,
+    payload: bool,
+    partial: bool,
+}
+
+impl<'a> Gcm<'a> {
+    fn init(aes: &'a mut Aes, mode: Mode, key: &[u32], iv: &[u32; 3]) -> Result<Self, Error> {
+        let keysize: KeySize = aes.set_key(key);
+        aes.set_iv(&[iv[0], iv[1], iv[2], 2]);
+
+        let mut gcm: Gcm = Gcm {
+            aes,
+            mode: mode.bits(),
+            keysize,
+            aad_buf: [0; 16],
+            aad_buf_len: 0,
+            aad_len: 0,
+            payload_len: 0,
+            payload: false,
+            partial: false,
+        };
+        gcm.set_cr(GCMPH_A::INIT, 0);
+        gcm.aes.poll_completion()?;
+        Ok(gcm)
+    }
+
+    /// Start a GCM encryption.
+    ///
+    /// # Panics
+    ///
+    /// * Key is not 128-bits long `[u32; 4]` or 256-bits long `[u32; 8]`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use stm32wl_hal::{
+    ///     aes::{Aes, Gcm},
+    ///     pac,
+    /// };
+    ///
+    /// let mut dp: pac::Peripherals = pac::Peripherals::take().unwrap();
+    /// let mut aes: Aes = Aes::new(dp.AES, &mut dp.RCC);
+    ///
+    /// const KEY: [u32; 4] = [0; 4];
+    /// const IV: [u32; 3] = [0; 3];
+    ///
+    /// let mut text: [u8; 13] = b"Hello, World!".clone();
+    /// let mut gcm: Gcm = Gcm::init_encrypt(&mut aes, &KEY, &IV)?;
+    /// gcm.update_aad(b"Hello, ")?;
+    /// gcm.update_aad(b"AAD!")?;
+    /// gcm.update(&mut text)?;
+    /// let tag: [u32; 4] = gcm.finish_encrypt()?;
+    /// # Ok::<(), stm32wl_hal::aes::Error>(())
+    /// ```
+    pub fn init_encrypt(aes: &'a mut Aes, key: &[u32], iv: &[u32; 3]) -> Result<Self, Error> {
+        Self::init(aes, Mode::Encryption, key, iv)
+    }
+
+    /// Start a GCM decryption.
+    ///
+    /// # Panics
+    ///
+    /// * Key is not 128-bits long `[u32; 4]` or 256-bits long `[u32; 8]`.
+    ///
+    /// # Example
+    ///
+    /// See [`Gcm`].
+    pub fn init_decrypt(aes: &'a mut Aes, key: &[u32], iv: &[u32; 3]) -> Result<Self, Error> {
+        Self::init(aes, Mode::Decryption, key, iv)
+    }
+
+    fn set_cr(&mut self, gcmph: GCMPH_A, npblb: u8) {
+        const ALGO: Algorithm = Algorithm::Gcm;
+        const CHMOD2: bool = ALGO.chmod2();
+        const CHMOD10: u8 = ALGO.chmod10();
+        let mode: u8 = self.mode;
+        let keysize: KeySize = self.keysize;
+
+        #[rustfmt::skip]
+        self.aes.aes.cr.write(|w|
+            w
+                .en().enabled()
+                .datatype().none()
+                .mode().bits(mode)
+                .chmod2().bit(CHMOD2)
+                .chmod().bits(CHMOD10)
+                .ccfc().clear()
+                .errc().clear()
+                .ccfie().disabled()
+                .errie().disabled()
+                .dmainen().disabled()
+                .dmaouten().disabled()
+                .gcmph().variant(gcmph)
+                .keysize().variant(keysize)
+                .npblb().bits(npblb)
+        );
+    }
+
+    fn flush_aad(&mut self) -> Result<(), Error> {
+        if self.aad_buf_len != 0 {
+            self.set_cr(GCMPH_A::HEADER, 0);
+            self.aes.set_din_block(&self.aad_buf[..self.aad_buf_len]);
+            self.aes.poll_completion()?;
+            self.aad_buf_len = 0;
+        }
+        Ok(())
+    }
+
+    /// Update the associated data.
+    ///
+    /// # Panics
+    ///
+    /// * Called after [`update`](Self::update).
+    pub fn update_aad(&mut self, aad: &[u8]) -> Result<(), Error> {
+        assert!(
+            !self.payload,
+            "Associated data must be provided before the payload"
+        );
+        self.aad_len += aad.len() as u64;
+
+        let mut remain: &[u8] = aad;
+        while !remain.is_empty() {
+            let n: usize = core::cmp::min(16 - self.aad_buf_len, remain.len());
+            self.aad_buf[self.aad_buf_len..self.aad_buf_len + n].copy_from_slice(&remain[..n]);
+            self.aad_buf_len += n;
+            remain = &remain[n..];
+
+            if self.aad_buf_len == 16 {
+                self.flush_aad()?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Encrypt or decrypt a chunk of the payload in-place.
+    ///
+    /// # Panics
+    ///
+    /// * Called after a previous call with a buffer length that is not a
+    ///   multiple of 16 bytes.
+    pub fn update(&mut self, buf: &mut [u8]) -> Result<(), Error> {
+        assert!(
+            !self.partial,
+            "Only the last payload buffer may have a length that is not a multiple of 16 bytes"
+        );
+        if !self.payload {
+            self.flush_aad()?;
+            self.payload = true;
+        }
+        self.payload_len += buf.len() as u64;
+
+        for block in buf.chunks_mut(16) {
+            self.set_cr(GCMPH_A::PAYLOAD, 16 - (block.len() as u8));
+            self.aes.set_din_block(block);
+            self.aes.poll_completion()?;
+            self.aes.dout_block(block);
+            self.partial = block.len() != 16;
+        }
+
+        Ok(())
+    }
+
+    fn finish(mut self) -> Result<[u32; 4], Error> {
+        if !self.payload {
+            self.flush_aad()?;
+        }
+
+        self.set_cr(GCMPH_A::FINAL, 0);
+
+        // byte lengths to bit lengths
+        let aad_len: u64 = self.aad_len << 3;
+        let payload_len: u64 = self.payload_len << 3;
+
+        let dinr = &self.aes.aes.dinr;
+        dinr.write(|w| w.din().bits((aad_len >> 32) as u32));
+        dinr.write(|w| w.din().bits(aad_len as u32));
+        dinr.write(|w| w.din().bits((payload_len >> 32) as u32));
+        dinr.write(|w| w.din().bits(payload_len as u32));
+
+        self.aes.poll_completion()?;
+        let mut tag: [u32; 4] = [0; 4];
+        self.aes.dout(&mut tag);
+        Ok(tag)
+    }
+
+    /// Finish the encryption and return the authentication tag.
+    pub fn finish_encrypt(self) -> Result<[u32; 4], Error> {
+        self.finish()
+    }
+
+    /// Finish the decryption and verify the authentication tag.
+    ///
+    /// The tag is compared in constant time, [`Error::Tag`] is returned if the
+    /// tags do not match.
+    ///
+    /// The decrypted payload must not be trusted until this returns `Ok`.
+    pub fn finish_decrypt(self, tag: &[u32; 4]) -> Result<(), Error> {
+        let computed: [u32; 4] = self.finish()?;
+        if ct_eq(&be_bytes_from_words(&computed), &be_bytes_from_words(tag)) {
+            Ok(())
+        } else {
+            Err(Error::Tag)
+        }
+    }
+}
@@ -0,0 +1,181 @@
+use super::{Aes, Algorithm, Error, KeySize, Mode};
+
+/// Decryption key schedule loaded in the AES peripheral.
+///
+/// ECB and CBC decryption require a key derived from the encryption key.
+/// [`Aes::decrypt_ecb`] and [`Aes::decrypt_cbc`] derive the key on every
+/// call, this context derives the key once in
+/// [`Aes::decryption_key`] and re-uses it for every decryption until it is
+/// dropped.
+///
+/// The context borrows the AES driver to prevent the derived key from being
+/// overwritten.
+///
+/// # Example
+///
+/// ```no_run
+/// use stm32wl_hal::{
+///     aes::{Aes, DecryptionKey},
+///     pac,
+/// };
+///
+/// let mut dp: pac::Peripherals = pac::Peripherals::take().unwrap();
+/// let mut aes: Aes = Aes::new(dp.AES, &mut dp.RCC);
+///
+/// const KEY: [u32; 4] = [0; 4];
+///
+/// let mut dk: DecryptionKey = aes.decryption_key(&KEY)?;
+/// let mut packet: [u32; 4] = [0; 4];
+/// loop {
+///     // ... receive the next packet
+///     dk.decrypt_ecb_inplace(&mut packet)?;
+///     // ... process the packet
+/// #   break;
+/// }
+/// # Ok::<(), stm32wl_hal::aes::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct DecryptionKey<'a> {
+    aes: &'a mut Aes,
+    keysize: KeySize,
+}
+
+impl<'a> DecryptionKey<'a> {
+    pub(super) fn new(aes: &'a mut Aes, key: &[u32]) -> Result<Self, Error> {
+        let keysize: KeySize = aes.set_key(key);
+        aes.derive_decryption_key(Algorithm::Ecb, keysize)?;
+        Ok(DecryptionKey { aes, keysize })
+    }
+
+    fn init(&mut self, algo: Algorithm, iv: Option<&[u32; 4]>) {
+        // the configuration and IV must be written while the peripheral is
+        // disabled, disabling does not clear the derived key
+        self.aes.aes.cr.modify(|_, w| w.en().disabled());
+        if let Some(iv) = iv {
+            self.aes.set_iv(iv);
+        }
+
+        let chmod2: bool = algo.chmod2();
+        let chmod10: u8 = algo.chmod10();
+        const MODE: u8 = Mode::Decryption.bits();
+        let keysize: KeySize = self.keysize;
+
+        #[rustfmt::skip]
+        self.aes.aes.cr.write(|w|
+            w
+                .en().enabled()
+                .datatype().none()
+                .mode().bits(MODE)
+                .chmod2().bit(chmod2)
+                .chmod().bits(chmod10)
+                .ccfc().clear()
+                .errc().clear()
+                .ccfie().disabled()
+                .errie().disabled()
+                .dmainen().disabled()
+                .dmaouten().disabled()
+                .gcmph().bits(0) // do not care for ECB/CBC
+                .keysize().variant(keysize)
+                .npblb().bits(0) // no padding
+        );
+    }
+
+    /// Decrypt using the electronic codebook chaining (ECB) algorithm.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use stm32wl_hal::{
+    ///     aes::{Aes, DecryptionKey},
+    ///     pac,
+    /// };
+    ///
+    /// let mut dp: pac::Peripherals = pac::Peripherals::take().unwrap();
+    /// let mut aes: Aes = Aes::new(dp.AES, &mut dp.RCC);
+    ///
+    /// const KEY: [u32; 4] = [0; 4];
+    ///
+    /// let ciphertext: [u32; 4] = [0x0336763e, 0x966d9259, 0x5a567cc9, 0xce537f5e];
+    /// let mut plaintext: [u32; 4] = [0; 4];
+    /// let mut dk: DecryptionKey = aes.decryption_key(&KEY)?;
+    /// dk.decrypt_ecb(&ciphertext, &mut plaintext)?;
+    /// # Ok::<(), stm32wl_hal::aes::Error>(())
+    /// ```
+    pub fn decrypt_ecb(
+        &mut self,
+        ciphertext: &[u32; 4],
+        plaintext: &mut [u32; 4],
+    ) -> Result<(), Error> {
+        self.init(Algorithm::Ecb, None);
+        self.aes.process_block(ciphertext, plaintext)
+    }
+
+    /// Decrypt using the electronic codebook chaining (ECB) algorithm
+    /// in-place.
+    ///
+    /// # Example
+    ///
+    /// See [`DecryptionKey`].
+    pub fn decrypt_ecb_inplace(&mut self, ciphertext: &mut [u32; 4]) -> Result<(), Error> {
+        let din: [u32; 4] = *ciphertext;
+        self.decrypt_ecb(&din, ciphertext)
+    }
+
+    /// Decrypt using the cipher block chaining (CBC) algorithm.
+    ///
+    /// # Panics
+    ///
+    /// * Ciphertext and plaintext buffers do not have the same length.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use stm32wl_hal::{
+    ///     aes::{Aes, DecryptionKey},
+    ///     pac,
+    /// };
+    ///
+    /// let mut dp: pac::Peripherals = pac::Peripherals::take().unwrap();
+    /// let mut aes: Aes = Aes::new(dp.AES, &mut dp.RCC);
+    ///
+    /// const KEY: [u32; 4] = [0; 4];
+    /// const IV: [u32; 4] = [0; 4];
+    ///
+    /// let ciphertext: [[u32; 4]; 2] = [[0; 4]; 2];
+    /// let mut plaintext: [[u32; 4]; 2] = [[0; 4]; 2];
+    /// let mut dk: DecryptionKey = aes.decryption_key(&KEY)?;
+    /// dk.decrypt_cbc(&IV, &ciphertext, &mut plaintext)?;
+    /// # Ok::<(), stm32wl_hal::aes::Error>(())
+    /// ```
+    pub fn decrypt_cbc(
+        &mut self,
+        iv: &[u32; 4],
+        ciphertext: &[[u32; 4]],
+        plaintext: &mut [[u32; 4]],
+    ) -> Result<(), Error> {
+        assert_eq!(
+            ciphertext.len(),
+            plaintext.len(),
+            "Input and output buffers must have the same length"
+        );
+        self.init(Algorithm::Cbc, Some(iv));
+        ciphertext
+            .iter()
+            .zip(plaintext.iter_mut())
+            .try_for_each(|(din, dout)| self.aes.process_block(din, dout))
+    }
+
+    /// Decrypt using the cipher block chaining (CBC) algorithm in-place.
+    pub fn decrypt_cbc_inplace(
+        &mut self,
+        iv: &[u32; 4],
+        ciphertext: &mut [[u32; 4]],
+    ) -> Result<(), Error> {
+        self.init(Algorithm::Cbc, Some(iv));
+        for block in ciphertext.iter_mut() {
+            let din: [u32; 4] = *block;
+            self.aes.process_block(&din, block)?;
+        }
+        Ok(())
+    }
+}
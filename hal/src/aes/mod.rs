@@ -0,0 +1,1492 @@
+//! Advanced encryption standard
+
+mod cmac;
+mod context;
+mod dma;
+mod drbg;
+mod gcm;
+mod key;
+#[cfg(feature = "rustcrypto")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rustcrypto")))]
+pub mod rustcrypto;
+
+pub use cmac::Cmac;
+pub use context::Context;
+pub use dma::AesDma;
+pub use drbg::{CtrDrbg, DrbgError};
+pub use gcm::Gcm;
+pub use key::DecryptionKey;
+
+use crate::{dma::DmaCh, pac};
+use pac::aes::cr::KEYSIZE_A as KeySize;
+
+/// Algorithm modes.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Algorithm {
+    /// Electronic codebook chaining algorithm
+    Ecb,
+    /// Cipher block chaining algorithm
+    Cbc,
+    /// Counter mode chaining algorithm
+    Ctr,
+    /// Galois counter mode - Galois message authentication code
+    Gcm,
+    /// Counter with Cipher Mode
+    Ccm,
+}
+
+impl Algorithm {
+    /// Bit 16
+    pub(crate) const fn chmod2(&self) -> bool {
+        matches!(self, Algorithm::Ccm)
+    }
+
+    /// Bits 6:5
+    pub(crate) const fn chmod10(&self) -> u8 {
+        match self {
+            Algorithm::Ecb => 0b00,
+            Algorithm::Cbc => 0b01,
+            Algorithm::Ctr => 0b10,
+            Algorithm::Gcm => 0b11,
+            Algorithm::Ccm => 0b00,
+        }
+    }
+}
+
+#[repr(u8)]
+enum Mode {
+    Encryption = 0b00,
+    KeyDerivation = 0b01,
+    Decryption = 0b10,
+    /// ST does not document this!
+    /// ST uses this in their HAL implementation and it passes NIST tests...
+    KeyDerivationDecryption = 0b11,
+}
+
+impl Mode {
+    pub const fn bits(self) -> u8 {
+        self as u8
+    }
+}
+
+impl From<Mode> for u8 {
+    fn from(m: Mode) -> Self {
+        m as u8
+    }
+}
+
+/// AES errors.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Error {
+    /// Unexpected read operation from the AES_DOUTR register
+    /// during computation or data input phase.
+    Read,
+    /// Unexpected write operation to the AES_DINR register
+    /// during computation or data output phase.
+    Write,
+    /// The computed authentication tag does not match the expected tag.
+    Tag,
+    /// DMA transfer error.
+    Dma,
+}
+
+fn words_from_be_bytes(bytes: &[u8; 16]) -> [u32; 4] {
+    let mut words: [u32; 4] = [0; 4];
+    words
+        .iter_mut()
+        .zip(bytes.chunks_exact(4))
+        .for_each(|(word, chunk)| {
+            *word = u32::from_be_bytes([chunk[0], chunk[1], chunk[2], chunk[3]])
+        });
+    words
+}
+
+fn be_bytes_from_words(words: &[u32; 4]) -> [u8; 16] {
+    let mut bytes: [u8; 16] = [0; 16];
+    bytes
+        .chunks_exact_mut(4)
+        .zip(words.iter())
+        .for_each(|(chunk, word)| chunk.copy_from_slice(&word.to_be_bytes()));
+    bytes
+}
+
+// compare without early return to avoid leaking the position of a mismatch
+fn ct_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b.iter()).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+fn assert_ccm_params(nonce_len: usize, tag_len: usize) {
+    assert!(
+        (7..=13).contains(&nonce_len),
+        "Nonce must be 7 to 13 bytes long not {} bytes",
+        nonce_len
+    );
+    assert!(
+        matches!(tag_len, 0 | 4 | 6 | 8 | 10 | 12 | 14 | 16),
+        "Tag must be 0, 4, 6, 8, 10, 12, 14, or 16 bytes long not {} bytes",
+        tag_len
+    );
+}
+
+/// AES driver.
+#[derive(Debug)]
+pub struct Aes {
+    aes: pac::AES,
+}
+
+impl Aes {
+    /// Create a new AES driver from an AES peripheral.
+    ///
+    /// This will enable clocks and reset the AES peripheral.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use stm32wl_hal::{aes::Aes, pac};
+    ///
+    /// let mut dp: pac::Peripherals = pac::Peripherals::take().unwrap();
+    /// let mut aes: Aes = Aes::new(dp.AES, &mut dp.RCC);
+    /// ```
+    #[inline]
+    pub fn new(aes: pac::AES, rcc: &mut pac::RCC) -> Aes {
+        Self::enable_clock(rcc);
+        unsafe { Self::pulse_reset(rcc) };
+
+        Aes { aes }
+    }
+
+    /// Free the AES peripheral from the driver.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use stm32wl_hal::{aes::Aes, pac};
+    ///
+    /// let mut dp: pac::Peripherals = pac::Peripherals::take().unwrap();
+    /// let mut aes: Aes = Aes::new(dp.AES, &mut dp.RCC);
+    /// // ... use AES
+    /// let aes: pac::AES = aes.free();
+    /// ```
+    #[inline]
+    pub fn free(self) -> pac::AES {
+        self.aes
+    }
+
+    /// Convert the AES driver into a driver that uses DMA for data transfers.
+    ///
+    /// The first channel moves data into the AES peripheral, the second
+    /// channel moves data out of the AES peripheral.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use stm32wl_hal::{
+    ///     aes::{Aes, AesDma},
+    ///     dma::{AllDma, Dma1Ch1, Dma1Ch2},
+    ///     pac,
+    /// };
+    ///
+    /// let mut dp: pac::Peripherals = pac::Peripherals::take().unwrap();
+    /// let dma: AllDma = AllDma::split(dp.DMAMUX, dp.DMA1, dp.DMA2, &mut dp.RCC);
+    /// let aes: Aes = Aes::new(dp.AES, &mut dp.RCC);
+    /// let aes: AesDma<Dma1Ch1, Dma1Ch2> = aes.with_dma((dma.d1.c1, dma.d1.c2));
+    /// ```
+    #[inline]
+    pub fn with_dma<IN: DmaCh, OUT: DmaCh>(self, dmas: (IN, OUT)) -> AesDma<IN, OUT> {
+        AesDma::from_aes(self, dmas)
+    }
+
+    /// Reset the AES peripheral.
+    ///
+    /// [`new`](Self::new) will pulse reset for you.
+    ///
+    /// # Safety
+    ///
+    /// 1. Ensure nothing is using the AES peripheral before calling this function.
+    ///
+    /// # Example
+    ///
+    /// See [`steal`](Self::steal).
+    #[inline]
+    pub unsafe fn pulse_reset(rcc: &mut pac::RCC) {
+        rcc.ahb3rstr.modify(|_, w| w.aesrst().set_bit());
+        rcc.ahb3rstr.modify(|_, w| w.aesrst().clear_bit());
+    }
+
+    /// Disable the AES peripheral clock.
+    ///
+    /// # Safety
+    ///
+    /// 1. Ensure nothing is using the AES peripheral before disabling the clock.
+    /// 2. You are responsible for re-enabling the clock before using the AES peripheral.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use stm32wl_hal::{aes::Aes, pac};
+    ///
+    /// let mut dp: pac::Peripherals = pac::Peripherals::take().unwrap();
+    /// let mut aes: Aes = Aes::new(dp.AES, &mut dp.RCC);
+    /// // ... use AES
+    ///
+    /// // safety: AES is not in use
+    /// unsafe { Aes::disable_clock(&mut dp.RCC) };
+    ///
+    /// // have a low power nap or something
+    ///
+    /// Aes::enable_clock(&mut dp.RCC);
+    /// // ... use AES
+    /// ```
+    #[inline]
+    pub unsafe fn disable_clock(rcc: &mut pac::RCC) {
+        rcc.ahb3enr.modify(|_, w| w.aesen().disabled());
+    }
+
+    /// Enable the AES peripheral clock.
+    ///
+    /// [`new`](Self::new) will enable clocks for you.
+    ///
+    /// # Example
+    ///
+    /// See [`steal`](Self::steal).
+    #[inline]
+    pub fn enable_clock(rcc: &mut pac::RCC) {
+        rcc.ahb3enr.modify(|_, w| w.aesen().enabled());
+        rcc.ahb3enr.read(); // delay after an RCC peripheral clock enabling
+    }
+
+    /// Steal the AES peripheral from whatever is currently using it.
+    ///
+    /// This will **not** initialize the AES peripheral (unlike [`new`]).
+    ///
+    /// # Safety
+    ///
+    /// 1. Ensure that the code stealing the AES peripheral has exclusive access.
+    ///    Singleton checks are bypassed with this method.
+    /// 2. You are responsible for resetting the AES peripheral and enabling
+    ///    the AES peripheral clock before use.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use stm32wl_hal::{aes::Aes, pac};
+    ///
+    /// let mut dp: pac::Peripherals = pac::Peripherals::take().unwrap();
+    /// // AES cannot be used via registers now
+    /// let _: pac::AES = dp.AES;
+    ///
+    /// // safety: nothing is using the peripheral
+    /// unsafe { Aes::pulse_reset(&mut dp.RCC) };
+    ///
+    /// Aes::enable_clock(&mut dp.RCC);
+    ///
+    /// // safety
+    /// // 1. We have exclusive access
+    /// // 2. peripheral has been setup
+    /// let aes: Aes = unsafe { Aes::steal() };
+    /// ```
+    ///
+    /// [`new`]: Aes::new
+    #[inline]
+    pub unsafe fn steal() -> Aes {
+        let dp: pac::Peripherals = pac::Peripherals::steal();
+        Aes { aes: dp.AES }
+    }
+
+    /// Unmask the AES IRQ in the NVIC.
+    ///
+    /// # Safety
+    ///
+    /// This can break mask-based critical sections.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # #[cfg(all(not(feature = "stm32wl5x_cm0p"), feature = "rt"))]
+    /// unsafe { stm32wl_hal::aes::Aes::unmask_irq() };
+    /// ```
+    #[cfg(all(not(feature = "stm32wl5x_cm0p"), feature = "rt"))]
+    #[cfg_attr(docsrs, doc(cfg(all(not(feature = "stm32wl5x_cm0p"), feature = "rt"))))]
+    #[inline]
+    pub unsafe fn unmask_irq() {
+        pac::NVIC::unmask(pac::Interrupt::AES)
+    }
+
+    fn set_key(&mut self, key: &[u32]) -> KeySize {
+        match key.len() {
+            4 => {
+                self.aes.cr.write(|w| w.en().disabled().keysize().bits128());
+                self.aes.keyr3.write(|w| w.key().bits(key[0]));
+                self.aes.keyr2.write(|w| w.key().bits(key[1]));
+                self.aes.keyr1.write(|w| w.key().bits(key[2]));
+                self.aes.keyr0.write(|w| w.key().bits(key[3]));
+                KeySize::BITS128
+            }
+            8 => {
+                self.aes.cr.write(|w| w.en().disabled().keysize().bits256());
+                self.aes.keyr7.write(|w| w.key().bits(key[0]));
+                self.aes.keyr6.write(|w| w.key().bits(key[1]));
+                self.aes.keyr5.write(|w| w.key().bits(key[2]));
+                self.aes.keyr4.write(|w| w.key().bits(key[3]));
+                self.aes.keyr3.write(|w| w.key().bits(key[4]));
+                self.aes.keyr2.write(|w| w.key().bits(key[5]));
+                self.aes.keyr1.write(|w| w.key().bits(key[6]));
+                self.aes.keyr0.write(|w| w.key().bits(key[7]));
+                KeySize::BITS256
+            }
+            _ => panic!("Key must be 128-bit or 256-bit not {}-bit", key.len() * 32),
+        }
+    }
+
+    fn poll_completion(&self) -> Result<(), Error> {
+        loop {
+            let sr = self.aes.sr.read();
+            if sr.wrerr().bit_is_set() {
+                return Err(Error::Write);
+            }
+            if sr.rderr().bit_is_set() {
+                return Err(Error::Read);
+            }
+            if sr.ccf().bit_is_set() {
+                return Ok(());
+            }
+        }
+    }
+
+    fn set_din(&mut self, din: &[u32; 4]) {
+        din.iter()
+            .for_each(|dw| self.aes.dinr.write(|w| w.din().bits(*dw)))
+    }
+
+    fn dout(&mut self, buf: &mut [u32; 4]) {
+        buf.iter_mut()
+            .for_each(|dw| *dw = self.aes.doutr.read().bits());
+    }
+
+    // expensive copy for the sake of allowing unaligned u8 data
+    fn set_din_block(&mut self, block: &[u8]) {
+        for chunk in block.chunks(4) {
+            let din: u32 = (chunk.get(0).copied().unwrap_or(0) as u32) << 24
+                | (chunk.get(1).copied().unwrap_or(0) as u32) << 16
+                | (chunk.get(2).copied().unwrap_or(0) as u32) << 8
+                | (chunk.get(3).copied().unwrap_or(0) as u32);
+
+            self.aes.dinr.write(|w| w.din().bits(din));
+        }
+
+        let remain_dw: usize = 4 - ((block.len() + 3) / 4);
+        for _ in 0..remain_dw {
+            self.aes.dinr.write(|w| w.din().bits(0));
+        }
+    }
+
+    // expensive copy for the sake of allowing unaligned u8 data
+    fn dout_block(&mut self, block: &mut [u8]) {
+        for chunk in block.chunks_mut(4) {
+            let dout: u32 = self.aes.doutr.read().bits();
+            if let Some(byte) = chunk.get_mut(0) {
+                *byte = (dout >> 24) as u8
+            }
+            if let Some(byte) = chunk.get_mut(1) {
+                *byte = (dout >> 16) as u8
+            }
+            if let Some(byte) = chunk.get_mut(2) {
+                *byte = (dout >> 8) as u8
+            }
+            if let Some(byte) = chunk.get_mut(3) {
+                *byte = dout as u8
+            }
+        }
+
+        let remain_dw: usize = 4 - ((block.len() + 3) / 4);
+        for _ in 0..remain_dw {
+            let _: u32 = self.aes.doutr.read().bits();
+        }
+    }
+
+    fn gcm_inplace(
+        &mut self,
+        mode: Mode,
+        key: &[u32],
+        iv: &[u32; 3],
+        aad: &[u8],
+        buf: &mut [u8],
+        tag: &mut [u32; 4],
+    ) -> Result<(), Error> {
+        const ALGO: Algorithm = Algorithm::Gcm;
+        const CHMOD2: bool = ALGO.chmod2();
+        const CHMOD10: u8 = ALGO.chmod10();
+        let mode: u8 = mode.bits();
+
+        // init phase
+        let keysize: KeySize = self.set_key(key);
+        self.aes.ivr0.write(|w| w.ivi().bits(2));
+        self.aes.ivr1.write(|w| w.ivi().bits(iv[2]));
+        self.aes.ivr2.write(|w| w.ivi().bits(iv[1]));
+        self.aes.ivr3.write(|w| w.ivi().bits(iv[0]));
+        #[rustfmt::skip]
+        self.aes.cr.write(|w|
+            w
+                .en().enabled()
+                .datatype().none()
+                .mode().bits(mode)
+                .chmod2().bit(CHMOD2)
+                .chmod().bits(CHMOD10)
+                .ccfc().clear()
+                .errc().clear()
+                .ccfie().disabled()
+                .errie().disabled()
+                .dmainen().disabled()
+                .dmaouten().disabled()
+                .gcmph().init()
+                .keysize().variant(keysize)
+                .npblb().bits(0)
+        );
+        self.poll_completion()?;
+
+        // header phase
+        for block in aad.chunks(16) {
+            #[rustfmt::skip]
+            self.aes.cr.write(|w|
+                w
+                    .en().enabled()
+                    .datatype().none()
+                    .mode().bits(mode)
+                    .chmod2().bit(CHMOD2)
+                    .chmod().bits(CHMOD10)
+                    .ccfc().clear()
+                    .errc().clear()
+                    .ccfie().disabled()
+                    .errie().disabled()
+                    .dmainen().disabled()
+                    .dmaouten().disabled()
+                    .gcmph().header()
+                    .keysize().variant(keysize)
+                    .npblb().bits(0) // not used in header phase
+            );
+            self.set_din_block(block);
+            self.poll_completion()?;
+        }
+
+        // payload phase
+        for block in buf.chunks_mut(16) {
+            #[rustfmt::skip]
+            self.aes.cr.write(|w|
+                w
+                    .en().enabled()
+                    .datatype().none()
+                    .mode().bits(mode)
+                    .chmod2().bit(CHMOD2)
+                    .chmod().bits(CHMOD10)
+                    .ccfc().clear()
+                    .errc().clear()
+                    .ccfie().disabled()
+                    .errie().disabled()
+                    .dmainen().disabled()
+                    .dmaouten().disabled()
+                    .gcmph().payload()
+                    .keysize().variant(keysize)
+                    .npblb().bits(16 - (block.len() as u8))
+            );
+            self.set_din_block(block);
+            self.poll_completion()?;
+            self.dout_block(block);
+        }
+
+        // final phase
+        #[rustfmt::skip]
+        self.aes.cr.write(|w|
+            w
+                .en().enabled()
+                .datatype().none()
+                .mode().bits(mode)
+                .chmod2().bit(CHMOD2)
+                .chmod().bits(CHMOD10)
+                .ccfc().clear()
+                .errc().clear()
+                .ccfie().disabled()
+                .errie().disabled()
+                .dmainen().disabled()
+                .dmaouten().disabled()
+                .gcmph().final_()
+                .keysize().variant(keysize)
+                .npblb().bits(0)
+        );
+
+        // byte length to bit lengths
+        // impossible to overflow, not enough RAM for [u8; (u32::MAX >> 3) + 1]
+        let aad_len: u32 = (aad.len() as u32) << 3;
+        let buf_len: u32 = (buf.len() as u32) << 3;
+
+        self.aes.dinr.write(|w| w.din().bits(0));
+        self.aes.dinr.write(|w| w.din().bits(aad_len));
+        self.aes.dinr.write(|w| w.din().bits(0));
+        self.aes.dinr.write(|w| w.din().bits(buf_len));
+
+        self.poll_completion()?;
+        self.dout(tag);
+        Ok(())
+    }
+
+    fn ccm_inplace(
+        &mut self,
+        mode: Mode,
+        key: &[u32],
+        nonce: &[u8],
+        aad: &[u8],
+        buf: &mut [u8],
+        tag: &mut [u8],
+    ) -> Result<(), Error> {
+        const ALGO: Algorithm = Algorithm::Ccm;
+        const CHMOD2: bool = ALGO.chmod2();
+        const CHMOD10: u8 = ALGO.chmod10();
+        let mode: u8 = mode.bits();
+
+        assert_ccm_params(nonce.len(), tag.len());
+
+        // size of the message length field in bytes
+        let l: usize = 15 - nonce.len();
+        assert!(
+            l >= 8 || (buf.len() as u64) < (1 << (8 * l)),
+            "Message of {} bytes is too long for a {}-byte nonce",
+            buf.len(),
+            nonce.len()
+        );
+
+        // format the B0 block, NIST SP 800-38C A.2.1
+        let mut b0: [u8; 16] = [0; 16];
+        let m: u8 = if tag.is_empty() {
+            0
+        } else {
+            (tag.len() as u8 - 2) / 2
+        };
+        b0[0] = (u8::from(!aad.is_empty()) << 6) | (m << 3) | (l as u8 - 1);
+        b0[1..=nonce.len()].copy_from_slice(nonce);
+        b0[16 - l..].copy_from_slice(&(buf.len() as u64).to_be_bytes()[8 - l..]);
+
+        // init phase
+        let keysize: KeySize = self.set_key(key);
+        self.set_iv(&words_from_be_bytes(&b0));
+        #[rustfmt::skip]
+        self.aes.cr.write(|w|
+            w
+                .en().enabled()
+                .datatype().none()
+                .mode().bits(mode)
+                .chmod2().bit(CHMOD2)
+                .chmod().bits(CHMOD10)
+                .ccfc().clear()
+                .errc().clear()
+                .ccfie().disabled()
+                .errie().disabled()
+                .dmainen().disabled()
+                .dmaouten().disabled()
+                .gcmph().init()
+                .keysize().variant(keysize)
+                .npblb().bits(0)
+        );
+        self.poll_completion()?;
+
+        // header phase
+        if !aad.is_empty() {
+            // format the associated data blocks, NIST SP 800-38C A.2.2
+            let mut block: [u8; 16] = [0; 16];
+            let mut pos: usize = if aad.len() < 0xFF00 {
+                block[..2].copy_from_slice(&(aad.len() as u16).to_be_bytes());
+                2
+            } else {
+                block[..2].copy_from_slice(&[0xFF, 0xFE]);
+                block[2..6].copy_from_slice(&(aad.len() as u32).to_be_bytes());
+                6
+            };
+
+            let mut remain: &[u8] = aad;
+            while !remain.is_empty() {
+                let n: usize = core::cmp::min(16 - pos, remain.len());
+                block[pos..pos + n].copy_from_slice(&remain[..n]);
+                remain = &remain[n..];
+
+                #[rustfmt::skip]
+                self.aes.cr.write(|w|
+                    w
+                        .en().enabled()
+                        .datatype().none()
+                        .mode().bits(mode)
+                        .chmod2().bit(CHMOD2)
+                        .chmod().bits(CHMOD10)
+                        .ccfc().clear()
+                        .errc().clear()
+                        .ccfie().disabled()
+                        .errie().disabled()
+                        .dmainen().disabled()
+                        .dmaouten().disabled()
+                        .gcmph().header()
+                        .keysize().variant(keysize)
+                        .npblb().bits(0) // not used in header phase
+                );
+                self.set_din_block(&block[..pos + n]);
+                self.poll_completion()?;
+                pos = 0;
+            }
+        }
+
+        // payload phase
+        for block in buf.chunks_mut(16) {
+            #[rustfmt::skip]
+            self.aes.cr.write(|w|
+                w
+                    .en().enabled()
+                    .datatype().none()
+                    .mode().bits(mode)
+                    .chmod2().bit(CHMOD2)
+                    .chmod().bits(CHMOD10)
+                    .ccfc().clear()
+                    .errc().clear()
+                    .ccfie().disabled()
+                    .errie().disabled()
+                    .dmainen().disabled()
+                    .dmaouten().disabled()
+                    .gcmph().payload()
+                    .keysize().variant(keysize)
+                    .npblb().bits(16 - (block.len() as u8))
+            );
+            self.set_din_block(block);
+            self.poll_completion()?;
+            self.dout_block(block);
+        }
+
+        // final phase, the length is encoded in B0 so there is no input
+        #[rustfmt::skip]
+        self.aes.cr.write(|w|
+            w
+                .en().enabled()
+                .datatype().none()
+                .mode().bits(mode)
+                .chmod2().bit(CHMOD2)
+                .chmod().bits(CHMOD10)
+                .ccfc().clear()
+                .errc().clear()
+                .ccfie().disabled()
+                .errie().disabled()
+                .dmainen().disabled()
+                .dmaouten().disabled()
+                .gcmph().final_()
+                .keysize().variant(keysize)
+                .npblb().bits(0)
+        );
+        self.poll_completion()?;
+
+        let mut full_tag: [u32; 4] = [0; 4];
+        self.dout(&mut full_tag);
+        tag.copy_from_slice(&be_bytes_from_words(&full_tag)[..tag.len()]);
+        Ok(())
+    }
+
+    // mode 2 in the reference manual, prepares the decryption key in-place
+    fn derive_decryption_key(&mut self, algo: Algorithm, keysize: KeySize) -> Result<(), Error> {
+        let chmod2: bool = algo.chmod2();
+        let chmod10: u8 = algo.chmod10();
+        const MODE: u8 = Mode::KeyDerivation.bits();
+
+        #[rustfmt::skip]
+        self.aes.cr.write(|w|
+            w
+                .en().enabled()
+                .datatype().none()
+                .mode().bits(MODE)
+                .chmod2().bit(chmod2)
+                .chmod().bits(chmod10)
+                .ccfc().clear()
+                .errc().clear()
+                .ccfie().disabled()
+                .errie().disabled()
+                .dmainen().disabled()
+                .dmaouten().disabled()
+                .gcmph().bits(0) // do not care for key derivation
+                .keysize().variant(keysize)
+                .npblb().bits(0) // no padding
+        );
+        self.poll_completion()?;
+        self.clear_ccf();
+        Ok(())
+    }
+
+    fn set_iv(&mut self, iv: &[u32; 4]) {
+        self.aes.ivr3.write(|w| w.ivi().bits(iv[0]));
+        self.aes.ivr2.write(|w| w.ivi().bits(iv[1]));
+        self.aes.ivr1.write(|w| w.ivi().bits(iv[2]));
+        self.aes.ivr0.write(|w| w.ivi().bits(iv[3]));
+    }
+
+    fn clear_ccf(&mut self) {
+        self.aes.cr.modify(|_, w| w.ccfc().clear())
+    }
+
+    // process a single block, clearing the completion flag for the next block
+    fn process_block(&mut self, din: &[u32; 4], dout: &mut [u32; 4]) -> Result<(), Error> {
+        self.set_din(din);
+        self.poll_completion()?;
+        self.dout(dout);
+        self.clear_ccf();
+        Ok(())
+    }
+
+    fn cbc_init(&mut self, mode: Mode, key: &[u32], iv: &[u32; 4]) {
+        const ALGO: Algorithm = Algorithm::Cbc;
+        const CHMOD2: bool = ALGO.chmod2();
+        const CHMOD10: u8 = ALGO.chmod10();
+        let mode: u8 = mode.bits();
+
+        let keysize: KeySize = self.set_key(key);
+        self.set_iv(iv);
+
+        #[rustfmt::skip]
+        self.aes.cr.write(|w|
+            w
+                .en().enabled()
+                .datatype().none()
+                .mode().bits(mode)
+                .chmod2().bit(CHMOD2)
+                .chmod().bits(CHMOD10)
+                .ccfc().clear()
+                .errc().clear()
+                .ccfie().disabled()
+                .errie().disabled()
+                .dmainen().disabled()
+                .dmaouten().disabled()
+                .gcmph().bits(0) // do not care for CBC
+                .keysize().variant(keysize)
+                .npblb().bits(0) // no padding
+        );
+    }
+
+    fn ctr_inplace(
+        &mut self,
+        key: &[u32],
+        counter: &mut [u32; 4],
+        buf: &mut [u8],
+    ) -> Result<(), Error> {
+        const ALGO: Algorithm = Algorithm::Ctr;
+        const CHMOD2: bool = ALGO.chmod2();
+        const CHMOD10: u8 = ALGO.chmod10();
+        // CTR is symmetric, the keystream is always generated with encryption
+        const MODE: u8 = Mode::Encryption.bits();
+
+        let keysize: KeySize = self.set_key(key);
+        self.set_iv(counter);
+
+        #[rustfmt::skip]
+        self.aes.cr.write(|w|
+            w
+                .en().enabled()
+                .datatype().none()
+                .mode().bits(MODE)
+                .chmod2().bit(CHMOD2)
+                .chmod().bits(CHMOD10)
+                .ccfc().clear()
+                .errc().clear()
+                .ccfie().disabled()
+                .errie().disabled()
+                .dmainen().disabled()
+                .dmaouten().disabled()
+                .gcmph().bits(0) // do not care for CTR
+                .keysize().variant(keysize)
+                .npblb().bits(0) // not used in CTR
+        );
+
+        for block in buf.chunks_mut(16) {
+            self.set_din_block(block);
+            self.poll_completion()?;
+            self.dout_block(block);
+            self.clear_ccf();
+            // the hardware increments the 32 least significant bits
+            counter[3] = counter[3].wrapping_add(1);
+        }
+
+        Ok(())
+    }
+
+    fn cbc(
+        &mut self,
+        mode: Mode,
+        key: &[u32],
+        iv: &[u32; 4],
+        input: &[[u32; 4]],
+        output: &mut [[u32; 4]],
+    ) -> Result<(), Error> {
+        assert_eq!(
+            input.len(),
+            output.len(),
+            "Input and output buffers must have the same length"
+        );
+        self.cbc_init(mode, key, iv);
+        input
+            .iter()
+            .zip(output.iter_mut())
+            .try_for_each(|(din, dout)| self.process_block(din, dout))
+    }
+
+    fn cbc_inplace(
+        &mut self,
+        mode: Mode,
+        key: &[u32],
+        iv: &[u32; 4],
+        buf: &mut [[u32; 4]],
+    ) -> Result<(), Error> {
+        self.cbc_init(mode, key, iv);
+        for block in buf.iter_mut() {
+            let din: [u32; 4] = *block;
+            self.process_block(&din, block)?;
+        }
+        Ok(())
+    }
+
+    /// Encrypt using the electronic codebook chaining (ECB) algorithm.
+    ///
+    /// # Panics
+    ///
+    /// * Key is not 128-bits long `[u32; 4]` or 256-bits long `[u32; 8]`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use stm32wl_hal::{aes::Aes, pac};
+    ///
+    /// let mut dp: pac::Peripherals = pac::Peripherals::take().unwrap();
+    /// let mut aes: Aes = Aes::new(dp.AES, &mut dp.RCC);
+    ///
+    /// const KEY: [u32; 4] = [0; 4];
+    ///
+    /// let plaintext: [u32; 4] = [0xf34481ec, 0x3cc627ba, 0xcd5dc3fb, 0x08f273e6];
+    /// let mut ciphertext: [u32; 4] = [0; 4];
+    /// aes.encrypt_ecb(&KEY, &plaintext, &mut ciphertext)?;
+    /// # Ok::<(), stm32wl_hal::aes::Error>(())
+    /// ```
+    pub fn encrypt_ecb(
+        &mut self,
+        key: &[u32],
+        plaintext: &[u32; 4],
+        ciphertext: &mut [u32; 4],
+    ) -> Result<(), Error> {
+        const ALGO: Algorithm = Algorithm::Ecb;
+        const CHMOD2: bool = ALGO.chmod2();
+        const CHMOD10: u8 = ALGO.chmod10();
+        const MODE: u8 = Mode::Encryption.bits();
+
+        let keysize: KeySize = self.set_key(key);
+
+        #[rustfmt::skip]
+        self.aes.cr.write(|w|
+            w
+                .en().enabled()
+                .datatype().none()
+                .mode().bits(MODE)
+                .chmod2().bit(CHMOD2)
+                .chmod().bits(CHMOD10)
+                .ccfc().clear()
+                .errc().clear()
+                .ccfie().disabled()
+                .errie().disabled()
+                .dmainen().disabled()
+                .dmaouten().disabled()
+                .gcmph().bits(0) // do not care for ECB
+                .keysize().variant(keysize)
+                .npblb().bits(0) // no padding
+        );
+
+        self.set_din(plaintext);
+        self.poll_completion()?;
+        self.dout(ciphertext);
+        Ok(())
+    }
+
+    /// Encrypt using the electronic codebook chaining (ECB) algorithm in-place.
+    ///
+    /// # Panics
+    ///
+    /// * Key is not 128-bits long `[u32; 4]` or 256-bits long `[u32; 8]`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use stm32wl_hal::{aes::Aes, pac};
+    ///
+    /// let mut dp: pac::Peripherals = pac::Peripherals::take().unwrap();
+    /// let mut aes: Aes = Aes::new(dp.AES, &mut dp.RCC);
+    ///
+    /// const KEY: [u32; 4] = [0; 4];
+    ///
+    /// let mut text: [u32; 4] = [0xf34481ec, 0x3cc627ba, 0xcd5dc3fb, 0x08f273e6];
+    /// aes.encrypt_ecb_inplace(&KEY, &mut text)?;
+    /// # Ok::<(), stm32wl_hal::aes::Error>(())
+    /// ```
+    pub fn encrypt_ecb_inplace(
+        &mut self,
+        key: &[u32],
+        plaintext: &mut [u32; 4],
+    ) -> Result<(), Error> {
+        const ALGO: Algorithm = Algorithm::Ecb;
+        const CHMOD2: bool = ALGO.chmod2();
+        const CHMOD10: u8 = ALGO.chmod10();
+        const MODE: u8 = Mode::Encryption.bits();
+
+        let keysize: KeySize = self.set_key(key);
+
+        #[rustfmt::skip]
+        self.aes.cr.write(|w|
+            w
+                .en().enabled()
+                .datatype().none()
+                .mode().bits(MODE)
+                .chmod2().bit(CHMOD2)
+                .chmod().bits(CHMOD10)
+                .ccfc().clear()
+                .errc().clear()
+                .ccfie().disabled()
+                .errie().disabled()
+                .dmainen().disabled()
+                .dmaouten().disabled()
+                .gcmph().bits(0) // do not care for ECB
+                .keysize().variant(keysize)
+                .npblb().bits(0) // no padding
+        );
+
+        self.set_din(plaintext);
+        self.poll_completion()?;
+        self.dout(plaintext);
+        Ok(())
+    }
+
+    /// Encrypt using the cipher block chaining (CBC) algorithm.
+    ///
+    /// The IV is chained between blocks by the hardware, the `plaintext`
+    /// and `ciphertext` may contain any number of blocks.
+    ///
+    /// # Panics
+    ///
+    /// * Key is not 128-bits long `[u32; 4]` or 256-bits long `[u32; 8]`.
+    /// * `plaintext` and `ciphertext` have different lengths.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use stm32wl_hal::{aes::Aes, pac};
+    ///
+    /// let mut dp: pac::Peripherals = pac::Peripherals::take().unwrap();
+    /// let mut aes: Aes = Aes::new(dp.AES, &mut dp.RCC);
+    ///
+    /// const KEY: [u32; 4] = [0; 4];
+    /// const IV: [u32; 4] = [0x00010203, 0x04050607, 0x08090a0b, 0x0c0d0e0f];
+    ///
+    /// let plaintext: [[u32; 4]; 2] = [
+    ///     [0x6bc1bee2, 0x2e409f96, 0xe93d7e11, 0x7393172a],
+    ///     [0xae2d8a57, 0x1e03ac9c, 0x9eb76fac, 0x45af8e51],
+    /// ];
+    /// let mut ciphertext: [[u32; 4]; 2] = [[0; 4]; 2];
+    /// aes.encrypt_cbc(&KEY, &IV, &plaintext, &mut ciphertext)?;
+    /// # Ok::<(), stm32wl_hal::aes::Error>(())
+    /// ```
+    pub fn encrypt_cbc(
+        &mut self,
+        key: &[u32],
+        iv: &[u32; 4],
+        plaintext: &[[u32; 4]],
+        ciphertext: &mut [[u32; 4]],
+    ) -> Result<(), Error> {
+        self.cbc(Mode::Encryption, key, iv, plaintext, ciphertext)
+    }
+
+    /// Encrypt using the cipher block chaining (CBC) algorithm in-place.
+    ///
+    /// # Panics
+    ///
+    /// * Key is not 128-bits long `[u32; 4]` or 256-bits long `[u32; 8]`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use stm32wl_hal::{aes::Aes, pac};
+    ///
+    /// let mut dp: pac::Peripherals = pac::Peripherals::take().unwrap();
+    /// let mut aes: Aes = Aes::new(dp.AES, &mut dp.RCC);
+    ///
+    /// const KEY: [u32; 4] = [0; 4];
+    /// const IV: [u32; 4] = [0x00010203, 0x04050607, 0x08090a0b, 0x0c0d0e0f];
+    ///
+    /// let mut text: [[u32; 4]; 2] = [
+    ///     [0x6bc1bee2, 0x2e409f96, 0xe93d7e11, 0x7393172a],
+    ///     [0xae2d8a57, 0x1e03ac9c, 0x9eb76fac, 0x45af8e51],
+    /// ];
+    /// aes.encrypt_cbc_inplace(&KEY, &IV, &mut text)?;
+    /// # Ok::<(), stm32wl_hal::aes::Error>(())
+    /// ```
+    pub fn encrypt_cbc_inplace(
+        &mut self,
+        key: &[u32],
+        iv: &[u32; 4],
+        plaintext: &mut [[u32; 4]],
+    ) -> Result<(), Error> {
+        self.cbc_inplace(Mode::Encryption, key, iv, plaintext)
+    }
+
+    /// Encrypt using the counter with CBC-MAC (CCM) algorithm in-place.
+    ///
+    /// The length of the authentication tag is set by the length of `tag`.
+    /// A zero-length tag provides encryption without authentication (CCM*).
+    ///
+    /// # Panics
+    ///
+    /// * Key is not 128-bits long `[u32; 4]` or 256-bits long `[u32; 8]`.
+    /// * Nonce is not 7 to 13 bytes long.
+    /// * Tag is not 0, 4, 6, 8, 10, 12, 14, or 16 bytes long.
+    /// * Plaintext length does not fit in the `15 - nonce.len()` byte
+    ///   length field.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use stm32wl_hal::{aes::Aes, pac};
+    ///
+    /// let mut dp: pac::Peripherals = pac::Peripherals::take().unwrap();
+    /// let mut aes: Aes = Aes::new(dp.AES, &mut dp.RCC);
+    ///
+    /// const KEY: [u32; 4] = [0; 4];
+    /// const NONCE: [u8; 13] = [0; 13];
+    ///
+    /// let associated_data: [u8; 2] = [0x12, 0x34];
+    /// let mut plaintext: [u8; 13] = b"Hello, World!".clone();
+    /// let mut tag: [u8; 8] = [0; 8];
+    /// aes.encrypt_ccm_inplace(&KEY, &NONCE, &associated_data, &mut plaintext, &mut tag)?;
+    /// # Ok::<(), stm32wl_hal::aes::Error>(())
+    /// ```
+    pub fn encrypt_ccm_inplace(
+        &mut self,
+        key: &[u32],
+        nonce: &[u8],
+        aad: &[u8],
+        plaintext: &mut [u8],
+        tag: &mut [u8],
+    ) -> Result<(), Error> {
+        self.ccm_inplace(Mode::Encryption, key, nonce, aad, plaintext, tag)
+    }
+
+    /// Encrypt using the counter (CTR) algorithm in-place.
+    ///
+    /// The buffer may be any length, a partial final block is supported.
+    ///
+    /// The `counter` is the initial counter block.
+    /// On return it contains the counter block for the next 16-byte block,
+    /// which allows a long message to be processed in multiple calls.
+    /// Resuming is only valid if all previous calls were on buffers with a
+    /// length that is a multiple of 16 bytes, the keystream of a partial
+    /// block is discarded.
+    ///
+    /// # Panics
+    ///
+    /// * Key is not 128-bits long `[u32; 4]` or 256-bits long `[u32; 8]`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use stm32wl_hal::{aes::Aes, pac};
+    ///
+    /// let mut dp: pac::Peripherals = pac::Peripherals::take().unwrap();
+    /// let mut aes: Aes = Aes::new(dp.AES, &mut dp.RCC);
+    ///
+    /// const KEY: [u32; 4] = [0; 4];
+    ///
+    /// let mut counter: [u32; 4] = [0xf0f1f2f3, 0xf4f5f6f7, 0xf8f9fafb, 0xfcfdfeff];
+    /// let mut text: [u8; 13] = b"Hello, World!".clone();
+    /// aes.encrypt_ctr_inplace(&KEY, &mut counter, &mut text)?;
+    /// # Ok::<(), stm32wl_hal::aes::Error>(())
+    /// ```
+    pub fn encrypt_ctr_inplace(
+        &mut self,
+        key: &[u32],
+        counter: &mut [u32; 4],
+        plaintext: &mut [u8],
+    ) -> Result<(), Error> {
+        self.ctr_inplace(key, counter, plaintext)
+    }
+
+    /// Encrypt using the Galois counter mode (GCM) algorithm in-place.
+    ///
+    /// # Panics
+    ///
+    /// * Key is not 128-bits long `[u32; 4]` or 256-bits long `[u32; 8]`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use stm32wl_hal::{
+    ///     aes::Aes,
+    ///     pac,
+    ///     rng::{self, Rng},
+    /// };
+    ///
+    /// let mut dp: pac::Peripherals = pac::Peripherals::take().unwrap();
+    /// let mut aes: Aes = Aes::new(dp.AES, &mut dp.RCC);
+    /// let mut rng = Rng::new(dp.RNG, rng::Clk::MSI, &mut dp.RCC);
+    ///
+    /// const KEY: [u32; 4] = [0; 4];
+    ///
+    /// let mut iv: [u32; 3] = [0; 3];
+    /// rng.try_fill_u32(&mut iv)
+    ///     .expect("failed to generate entropy");
+    ///
+    /// let mut associated_data: [u8; 0] = [];
+    /// let mut plaintext: [u8; 13] = b"Hello, World!".clone();
+    /// let mut tag: [u32; 4] = [0; 4];
+    /// aes.encrypt_gcm_inplace(&KEY, &iv, &associated_data, &mut plaintext, &mut tag)?;
+    /// # Ok::<(), stm32wl_hal::aes::Error>(())
+    /// ```
+    pub fn encrypt_gcm_inplace(
+        &mut self,
+        key: &[u32],
+        iv: &[u32; 3],
+        aad: &[u8],
+        plaintext: &mut [u8],
+        tag: &mut [u32; 4],
+    ) -> Result<(), Error> {
+        self.gcm_inplace(Mode::Encryption, key, iv, aad, plaintext, tag)
+    }
+
+    /// Derive the ECB/CBC decryption key once for multiple decryptions.
+    ///
+    /// Every call to [`decrypt_ecb`](Self::decrypt_ecb) and
+    /// [`decrypt_cbc`](Self::decrypt_cbc) prepares the decryption key from
+    /// the encryption key.
+    /// The returned context keeps the prepared key loaded in the AES
+    /// peripheral to skip this step.
+    ///
+    /// # Panics
+    ///
+    /// * Key is not 128-bits long `[u32; 4]` or 256-bits long `[u32; 8]`.
+    ///
+    /// # Example
+    ///
+    /// See [`DecryptionKey`].
+    pub fn decryption_key(&mut self, key: &[u32]) -> Result<DecryptionKey<'_>, Error> {
+        DecryptionKey::new(self, key)
+    }
+
+    /// Decrypt using the electronic codebook chaining (ECB) algorithm.
+    ///
+    /// # Panics
+    ///
+    /// * Key is not 128-bits long `[u32; 4]` or 256-bits long `[u32; 8]`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use stm32wl_hal::{aes::Aes, pac};
+    ///
+    /// let mut dp: pac::Peripherals = pac::Peripherals::take().unwrap();
+    /// let mut aes: Aes = Aes::new(dp.AES, &mut dp.RCC);
+    ///
+    /// const KEY: [u32; 4] = [0; 4];
+    ///
+    /// let ciphertext: [u32; 4] = [0x0336763e, 0x966d9259, 0x5a567cc9, 0xce537f5e];
+    /// let mut plaintext: [u32; 4] = [0; 4];
+    /// aes.decrypt_ecb(&KEY, &ciphertext, &mut plaintext)?;
+    /// # Ok::<(), stm32wl_hal::aes::Error>(())
+    /// ```
+    pub fn decrypt_ecb(
+        &mut self,
+        key: &[u32],
+        ciphertext: &[u32; 4],
+        plaintext: &mut [u32; 4],
+    ) -> Result<(), Error> {
+        const ALGO: Algorithm = Algorithm::Ecb;
+        const CHMOD2: bool = ALGO.chmod2();
+        const CHMOD10: u8 = ALGO.chmod10();
+        const MODE: u8 = Mode::KeyDerivationDecryption.bits();
+
+        let keysize: KeySize = self.set_key(key);
+
+        #[rustfmt::skip]
+        self.aes.cr.write(|w|
+            w
+                .en().enabled()
+                .datatype().none()
+                .mode().bits(MODE)
+                .chmod2().bit(CHMOD2)
+                .chmod().bits(CHMOD10)
+                .ccfc().clear()
+                .errc().clear()
+                .ccfie().disabled()
+                .errie().disabled()
+                .dmainen().disabled()
+                .dmaouten().disabled()
+                .gcmph().bits(0) // do not care for ECB
+                .keysize().variant(keysize)
+                .npblb().bits(0) // no padding
+        );
+
+        self.set_din(ciphertext);
+        self.poll_completion()?;
+        self.dout(plaintext);
+        Ok(())
+    }
+
+    /// Decrypt using the electronic codebook chaining (ECB) algorithm in-place.
+    ///
+    /// # Panics
+    ///
+    /// * Key is not 128-bits long `[u32; 4]` or 256-bits long `[u32; 8]`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use stm32wl_hal::{aes::Aes, pac};
+    ///
+    /// let mut dp: pac::Peripherals = pac::Peripherals::take().unwrap();
+    /// let mut aes: Aes = Aes::new(dp.AES, &mut dp.RCC);
+    ///
+    /// const KEY: [u32; 4] = [0; 4];
+    ///
+    /// let mut text: [u32; 4] = [0x0336763e, 0x966d9259, 0x5a567cc9, 0xce537f5e];
+    /// aes.decrypt_ecb_inplace(&KEY, &mut text)?;
+    /// # Ok::<(), stm32wl_hal::aes::Error>(())
+    /// ```
+    pub fn decrypt_ecb_inplace(
+        &mut self,
+        key: &[u32],
+        ciphertext: &mut [u32; 4],
+    ) -> Result<(), Error> {
+        const ALGO: Algorithm = Algorithm::Ecb;
+        const CHMOD2: bool = ALGO.chmod2();
+        const CHMOD10: u8 = ALGO.chmod10();
+        const MODE: u8 = Mode::KeyDerivationDecryption.bits();
+        let keysize: KeySize = self.set_key(key);
+
+        #[rustfmt::skip]
+        self.aes.cr.write(|w|
+            w
+                .en().enabled()
+                .datatype().none()
+                .mode().bits(MODE)
+                .chmod2().bit(CHMOD2)
+                .chmod().bits(CHMOD10)
+                .ccfc().clear()
+                .errc().clear()
+                .ccfie().disabled()
+                .errie().disabled()
+                .dmainen().disabled()
+                .dmaouten().disabled()
+                .gcmph().bits(0) // do not care for ECB
+                .keysize().variant(keysize)
+                .npblb().bits(0) // no padding
+        );
+
+        self.set_din(ciphertext);
+        self.poll_completion()?;
+        self.dout(ciphertext);
+        Ok(())
+    }
+
+    /// Decrypt using the cipher block chaining (CBC) algorithm.
+    ///
+    /// # Panics
+    ///
+    /// * Key is not 128-bits long `[u32; 4]` or 256-bits long `[u32; 8]`.
+    /// * `ciphertext` and `plaintext` have different lengths.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use stm32wl_hal::{aes::Aes, pac};
+    ///
+    /// let mut dp: pac::Peripherals = pac::Peripherals::take().unwrap();
+    /// let mut aes: Aes = Aes::new(dp.AES, &mut dp.RCC);
+    ///
+    /// const KEY: [u32; 4] = [0; 4];
+    /// const IV: [u32; 4] = [0x00010203, 0x04050607, 0x08090a0b, 0x0c0d0e0f];
+    ///
+    /// let ciphertext: [[u32; 4]; 2] = [
+    ///     [0x7649abac, 0x8119b246, 0xcee98e9b, 0x12e9197d],
+    ///     [0x5086cb9b, 0x507219ee, 0x95db113a, 0x917678b2],
+    /// ];
+    /// let mut plaintext: [[u32; 4]; 2] = [[0; 4]; 2];
+    /// aes.decrypt_cbc(&KEY, &IV, &ciphertext, &mut plaintext)?;
+    /// # Ok::<(), stm32wl_hal::aes::Error>(())
+    /// ```
+    pub fn decrypt_cbc(
+        &mut self,
+        key: &[u32],
+        iv: &[u32; 4],
+        ciphertext: &[[u32; 4]],
+        plaintext: &mut [[u32; 4]],
+    ) -> Result<(), Error> {
+        self.cbc(
+            Mode::KeyDerivationDecryption,
+            key,
+            iv,
+            ciphertext,
+            plaintext,
+        )
+    }
+
+    /// Decrypt using the cipher block chaining (CBC) algorithm in-place.
+    ///
+    /// # Panics
+    ///
+    /// * Key is not 128-bits long `[u32; 4]` or 256-bits long `[u32; 8]`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use stm32wl_hal::{aes::Aes, pac};
+    ///
+    /// let mut dp: pac::Peripherals = pac::Peripherals::take().unwrap();
+    /// let mut aes: Aes = Aes::new(dp.AES, &mut dp.RCC);
+    ///
+    /// const KEY: [u32; 4] = [0; 4];
+    /// const IV: [u32; 4] = [0x00010203, 0x04050607, 0x08090a0b, 0x0c0d0e0f];
+    ///
+    /// let mut text: [[u32; 4]; 2] = [
+    ///     [0x7649abac, 0x8119b246, 0xcee98e9b, 0x12e9197d],
+    ///     [0x5086cb9b, 0x507219ee, 0x95db113a, 0x917678b2],
+    /// ];
+    /// aes.decrypt_cbc_inplace(&KEY, &IV, &mut text)?;
+    /// # Ok::<(), stm32wl_hal::aes::Error>(())
+    /// ```
+    pub fn decrypt_cbc_inplace(
+        &mut self,
+        key: &[u32],
+        iv: &[u32; 4],
+        ciphertext: &mut [[u32; 4]],
+    ) -> Result<(), Error> {
+        self.cbc_inplace(Mode::KeyDerivationDecryption, key, iv, ciphertext)
+    }
+
+    /// Decrypt using the counter with CBC-MAC (CCM) algorithm in-place.
+    ///
+    /// The computed authentication tag is compared to `tag` in constant
+    /// time.
+    /// If the tags do not match [`Error::Tag`] is returned, and the buffer is
+    /// zeroed to avoid releasing unauthenticated plaintext.
+    ///
+    /// # Panics
+    ///
+    /// * Key is not 128-bits long `[u32; 4]` or 256-bits long `[u32; 8]`.
+    /// * Nonce is not 7 to 13 bytes long.
+    /// * Tag is not 0, 4, 6, 8, 10, 12, 14, or 16 bytes long.
+    /// * Ciphertext length does not fit in the `15 - nonce.len()` byte
+    ///   length field.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use stm32wl_hal::{aes::Aes, pac};
+    ///
+    /// let mut dp: pac::Peripherals = pac::Peripherals::take().unwrap();
+    /// let mut aes: Aes = Aes::new(dp.AES, &mut dp.RCC);
+    ///
+    /// const KEY: [u32; 4] = [0; 4];
+    /// const NONCE: [u8; 13] = [0; 13];
+    ///
+    /// let associated_data: [u8; 2] = [0x12, 0x34];
+    /// let mut ciphertext: [u8; 5] = [0xf3, 0x44, 0x81, 0xec, 0x3c];
+    /// let tag: [u8; 8] = [0x2e, 0x26, 0xe4, 0x72, 0x5b, 0x69, 0x9d, 0x40];
+    /// match aes.decrypt_ccm_inplace(&KEY, &NONCE, &associated_data, &mut ciphertext, &tag) {
+    ///     Ok(()) => (), // ciphertext now contains authentic plaintext
+    ///     Err(stm32wl_hal::aes::Error::Tag) => (), // message is not authentic
+    ///     Err(e) => return Err(e),
+    /// }
+    /// # Ok::<(), stm32wl_hal::aes::Error>(())
+    /// ```
+    pub fn decrypt_ccm_inplace(
+        &mut self,
+        key: &[u32],
+        nonce: &[u8],
+        aad: &[u8],
+        ciphertext: &mut [u8],
+        tag: &[u8],
+    ) -> Result<(), Error> {
+        assert_ccm_params(nonce.len(), tag.len());
+        let mut computed_tag: [u8; 16] = [0; 16];
+        let computed_tag: &mut [u8] = &mut computed_tag[..tag.len()];
+        self.ccm_inplace(Mode::Decryption, key, nonce, aad, ciphertext, computed_tag)?;
+
+        if ct_eq(computed_tag, tag) {
+            Ok(())
+        } else {
+            ciphertext.iter_mut().for_each(|byte| *byte = 0);
+            Err(Error::Tag)
+        }
+    }
+
+    /// Decrypt using the counter (CTR) algorithm in-place.
+    ///
+    /// The buffer may be any length, a partial final block is supported.
+    ///
+    /// See [`encrypt_ctr_inplace`](Self::encrypt_ctr_inplace) for details
+    /// about resuming with the updated `counter`.
+    ///
+    /// # Panics
+    ///
+    /// * Key is not 128-bits long `[u32; 4]` or 256-bits long `[u32; 8]`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use stm32wl_hal::{aes::Aes, pac};
+    ///
+    /// let mut dp: pac::Peripherals = pac::Peripherals::take().unwrap();
+    /// let mut aes: Aes = Aes::new(dp.AES, &mut dp.RCC);
+    ///
+    /// const KEY: [u32; 4] = [0; 4];
+    ///
+    /// let mut counter: [u32; 4] = [0xf0f1f2f3, 0xf4f5f6f7, 0xf8f9fafb, 0xfcfdfeff];
+    /// let mut text: [u8; 5] = [0xf3, 0x44, 0x81, 0xec, 0x3c];
+    /// aes.decrypt_ctr_inplace(&KEY, &mut counter, &mut text)?;
+    /// # Ok::<(), stm32wl_hal::aes::Error>(())
+    /// ```
+    pub fn decrypt_ctr_inplace(
+        &mut self,
+        key: &[u32],
+        counter: &mut [u32; 4],
+        ciphertext: &mut [u8],
+    ) -> Result<(), Error> {
+        self.ctr_inplace(key, counter, ciphertext)
+    }
+
+    /// Decrypt using the Galois counter mode (GCM) algorithm in-place.
+    ///
+    /// The resulting tag should be compared to the tag sent from the peer
+    /// to verify the authenticity of the message.
+    ///
+    /// # Panics
+    ///
+    /// * Key is not 128-bits long `[u32; 4]` or 256-bits long `[u32; 8]`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use stm32wl_hal::{aes::Aes, pac};
+    ///
+    /// let mut dp: pac::Peripherals = pac::Peripherals::take().unwrap();
+    /// let mut aes: Aes = Aes::new(dp.AES, &mut dp.RCC);
+    ///
+    /// const KEY: [u32; 4] = [0; 4];
+    /// const IV: [u32; 3] = [0; 3];
+    ///
+    /// let mut associated_data: [u8; 0] = [];
+    /// let mut ciphertext: [u8; 5] = [0xf3, 0x44, 0x81, 0xec, 0x3c];
+    /// let mut tag: [u32; 4] = [0; 4];
+    /// aes.decrypt_gcm_inplace(&KEY, &IV, &associated_data, &mut ciphertext, &mut tag)?;
+    /// # Ok::<(), stm32wl_hal::aes::Error>(())
+    /// ```
+    pub fn decrypt_gcm_inplace(
+        &mut self,
+        key: &[u32],
+        iv: &[u32; 3],
+        aad: &[u8],
+        ciphertext: &mut [u8],
+        tag: &mut [u32; 4],
+    ) -> Result<(), Error> {
+        self.gcm_inplace(Mode::Decryption, key, iv, aad, ciphertext, tag)
+    }
+}
@@ -0,0 +1,379 @@
+//! RustCrypto trait implementations.
+//!
+//! The wrapper types in this module implement the [`cipher`] and [`aead`]
+//! traits with the AES peripheral.
+//!
+//! Each wrapper mutably borrows the [`Aes`] driver for its lifetime.
+//! The driver is available again after the wrapper is dropped.
+//!
+//! The wrappers are constructed with an inherent `new` method that takes the
+//! driver and the key, the [`KeyInit`](cipher::KeyInit) trait is not
+//! implemented because it constructs ciphers from a key alone.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use stm32wl_hal::{
+//!     aes::{
+//!         rustcrypto::{aead::AeadInPlace, Aes128Gcm},
+//!         Aes,
+//!     },
+//!     pac,
+//! };
+//!
+//! let mut dp: pac::Peripherals = pac::Peripherals::take().unwrap();
+//! let mut aes: Aes = Aes::new(dp.AES, &mut dp.RCC);
+//!
+//! let cipher: Aes128Gcm = Aes128Gcm::new(&mut aes, &[0; 16].into());
+//! let mut buf: [u8; 13] = *b"Hello, World!";
+//! let tag = cipher.encrypt_in_place_detached(&[0; 12].into(), b"", &mut buf)?;
+//! # Ok::<(), stm32wl_hal::aes::rustcrypto::aead::Error>(())
+//! ```
+
+pub use aead;
+pub use cipher;
+
+use super::{be_bytes_from_words, ct_eq, words_from_be_bytes, Aes};
+use aead::{AeadCore, AeadInPlace, Nonce, Tag};
+use cipher::{
+    consts::{U0, U1, U10, U11, U12, U13, U14, U16, U32, U4, U6, U7, U8, U9},
+    generic_array::{ArrayLength, GenericArray},
+    inout::InOut,
+    Block, BlockBackend, BlockCipher, BlockClosure, BlockDecrypt, BlockEncrypt, BlockSizeUser, Key,
+    KeySizeUser, ParBlocksSizeUser,
+};
+use core::{cell::RefCell, marker::PhantomData};
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// CCM tag sizes supported by the AES peripheral.
+///
+/// This is implemented for 4, 6, 8, 10, 12, 14, and 16 bytes.
+pub trait CcmTagSize: ArrayLength<u8> + sealed::Sealed {}
+
+/// CCM nonce sizes supported by the AES peripheral.
+///
+/// This is implemented for 7 to 13 bytes.
+pub trait CcmNonceSize: ArrayLength<u8> + sealed::Sealed {}
+
+macro_rules! ccm_sizes {
+    ($tr:ident: $($size:ty),+) => {
+        $(
+            impl $tr for $size {}
+        )+
+    };
+}
+
+impl sealed::Sealed for U4 {}
+impl sealed::Sealed for U6 {}
+impl sealed::Sealed for U7 {}
+impl sealed::Sealed for U8 {}
+impl sealed::Sealed for U9 {}
+impl sealed::Sealed for U10 {}
+impl sealed::Sealed for U11 {}
+impl sealed::Sealed for U12 {}
+impl sealed::Sealed for U13 {}
+impl sealed::Sealed for U14 {}
+impl sealed::Sealed for U16 {}
+
+ccm_sizes!(CcmTagSize: U4, U6, U8, U10, U12, U14, U16);
+ccm_sizes!(CcmNonceSize: U7, U8, U9, U10, U11, U12, U13);
+
+fn be_words<const N: usize>(bytes: &[u8]) -> [u32; N] {
+    let mut words: [u32; N] = [0; N];
+    words
+        .iter_mut()
+        .zip(bytes.chunks_exact(4))
+        .for_each(|(w, b)| *w = u32::from_be_bytes([b[0], b[1], b[2], b[3]]));
+    words
+}
+
+fn block_words(block: &Block<Aes128>) -> [u32; 4] {
+    let mut bytes: [u8; 16] = [0; 16];
+    bytes.copy_from_slice(block);
+    words_from_be_bytes(&bytes)
+}
+
+macro_rules! block_cipher {
+    ($name:ident, $backend:ident, $keysize:ty, $words:literal, $bytes:literal, $bits:literal) => {
+        #[doc = concat!("AES-", $bits, " block cipher.")]
+        pub struct $name<'a> {
+            aes: RefCell<&'a mut Aes>,
+            key: [u32; $words],
+        }
+
+        impl<'a> $name<'a> {
+            #[doc = concat!("Create a new AES-", $bits, " block cipher.")]
+            ///
+            /// # Example
+            ///
+            /// ```no_run
+            /// use stm32wl_hal::{
+            #[doc = concat!("     aes::{rustcrypto::", stringify!($name), ", Aes},")]
+            ///     pac,
+            /// };
+            ///
+            /// let mut dp: pac::Peripherals = pac::Peripherals::take().unwrap();
+            /// let mut aes: Aes = Aes::new(dp.AES, &mut dp.RCC);
+            ///
+            #[doc = concat!(" let cipher = ", stringify!($name), "::new(&mut aes, &[0; ", $bytes, "].into());")]
+            /// ```
+            pub fn new(aes: &'a mut Aes, key: &Key<Self>) -> Self {
+                $name {
+                    aes: RefCell::new(aes),
+                    key: be_words(key),
+                }
+            }
+        }
+
+        impl<'a> core::fmt::Debug for $name<'a> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.write_str(concat!(stringify!($name), " { ... }"))
+            }
+        }
+
+        impl<'a> KeySizeUser for $name<'a> {
+            type KeySize = $keysize;
+        }
+
+        impl<'a> BlockSizeUser for $name<'a> {
+            type BlockSize = U16;
+        }
+
+        impl<'a> BlockCipher for $name<'a> {}
+
+        impl<'a> BlockEncrypt for $name<'a> {
+            fn encrypt_with_backend(&self, f: impl BlockClosure<BlockSize = U16>) {
+                f.call(&mut $backend {
+                    aes: &mut self.aes.borrow_mut(),
+                    key: &self.key,
+                    decrypt: false,
+                })
+            }
+        }
+
+        impl<'a> BlockDecrypt for $name<'a> {
+            fn decrypt_with_backend(&self, f: impl BlockClosure<BlockSize = U16>) {
+                f.call(&mut $backend {
+                    aes: &mut self.aes.borrow_mut(),
+                    key: &self.key,
+                    decrypt: true,
+                })
+            }
+        }
+
+        struct $backend<'b> {
+            aes: &'b mut Aes,
+            key: &'b [u32; $words],
+            decrypt: bool,
+        }
+
+        impl<'b> BlockSizeUser for $backend<'b> {
+            type BlockSize = U16;
+        }
+
+        impl<'b> ParBlocksSizeUser for $backend<'b> {
+            type ParBlocksSize = U1;
+        }
+
+        impl<'b> BlockBackend for $backend<'b> {
+            fn proc_block(&mut self, mut block: InOut<'_, '_, Block<Self>>) {
+                let mut words: [u32; 4] = block_words(block.get_in());
+                let result = if self.decrypt {
+                    self.aes.decrypt_ecb_inplace(self.key, &mut words)
+                } else {
+                    self.aes.encrypt_ecb_inplace(self.key, &mut words)
+                };
+                // read and write errors only occur when the data registers
+                // are accessed during a computation, the driver is borrowed
+                // exclusively so this is a bug in the driver
+                if result.is_err() {
+                    unreachable!("AES block operation failed");
+                }
+                block
+                    .get_out()
+                    .copy_from_slice(&be_bytes_from_words(&words));
+            }
+        }
+    };
+}
+
+block_cipher!(Aes128, Aes128Backend, U16, 4, 16, "128");
+block_cipher!(Aes256, Aes256Backend, U32, 8, 32, "256");
+
+macro_rules! gcm {
+    ($name:ident, $keysize:ty, $words:literal, $bits:literal) => {
+        #[doc = concat!("AES-", $bits, "-GCM authenticated encryption with a 96-bit nonce.")]
+        pub struct $name<'a> {
+            aes: RefCell<&'a mut Aes>,
+            key: [u32; $words],
+        }
+
+        impl<'a> $name<'a> {
+            #[doc = concat!("Create a new AES-", $bits, "-GCM cipher.")]
+            ///
+            /// # Example
+            ///
+            /// See the [module level documentation](self).
+            pub fn new(aes: &'a mut Aes, key: &Key<Self>) -> Self {
+                $name {
+                    aes: RefCell::new(aes),
+                    key: be_words(key),
+                }
+            }
+        }
+
+        impl<'a> core::fmt::Debug for $name<'a> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.write_str(concat!(stringify!($name), " { ... }"))
+            }
+        }
+
+        impl<'a> KeySizeUser for $name<'a> {
+            type KeySize = $keysize;
+        }
+
+        impl<'a> AeadCore for $name<'a> {
+            type NonceSize = U12;
+            type TagSize = U16;
+            type CiphertextOverhead = U0;
+        }
+
+        impl<'a> AeadInPlace for $name<'a> {
+            fn encrypt_in_place_detached(
+                &self,
+                nonce: &Nonce<Self>,
+                associated_data: &[u8],
+                buffer: &mut [u8],
+            ) -> aead::Result<Tag<Self>> {
+                let iv: [u32; 3] = be_words(nonce);
+                let mut tag: [u32; 4] = [0; 4];
+                self.aes
+                    .borrow_mut()
+                    .encrypt_gcm_inplace(&self.key, &iv, associated_data, buffer, &mut tag)
+                    .map_err(|_| aead::Error)?;
+                Ok(GenericArray::clone_from_slice(&be_bytes_from_words(&tag)))
+            }
+
+            fn decrypt_in_place_detached(
+                &self,
+                nonce: &Nonce<Self>,
+                associated_data: &[u8],
+                buffer: &mut [u8],
+                tag: &Tag<Self>,
+            ) -> aead::Result<()> {
+                let iv: [u32; 3] = be_words(nonce);
+                let mut computed: [u32; 4] = [0; 4];
+                self.aes
+                    .borrow_mut()
+                    .decrypt_gcm_inplace(&self.key, &iv, associated_data, buffer, &mut computed)
+                    .map_err(|_| aead::Error)?;
+
+                if ct_eq(&be_bytes_from_words(&computed), tag) {
+                    Ok(())
+                } else {
+                    // do not release unauthenticated plaintext
+                    buffer.iter_mut().for_each(|b| *b = 0);
+                    Err(aead::Error)
+                }
+            }
+        }
+    };
+}
+
+gcm!(Aes128Gcm, U16, 4, "128");
+gcm!(Aes256Gcm, U32, 8, "256");
+
+macro_rules! ccm {
+    ($name:ident, $keysize:ty, $words:literal, $bytes:literal, $bits:literal) => {
+        #[doc = concat!("AES-", $bits, "-CCM authenticated encryption.")]
+        ///
+        /// `T` is the tag size in bytes, `N` is the nonce size in bytes.
+        /// Only the sizes supported by the AES peripheral implement
+        /// [`CcmTagSize`] and [`CcmNonceSize`].
+        pub struct $name<'a, T: CcmTagSize, N: CcmNonceSize> {
+            aes: RefCell<&'a mut Aes>,
+            key: [u32; $words],
+            sizes: PhantomData<(T, N)>,
+        }
+
+        impl<'a, T: CcmTagSize, N: CcmNonceSize> $name<'a, T, N> {
+            #[doc = concat!("Create a new AES-", $bits, "-CCM cipher.")]
+            ///
+            /// # Example
+            ///
+            /// ```no_run
+            /// use stm32wl_hal::{
+            ///     aes::{
+            #[doc = concat!("         rustcrypto::{cipher::consts::{U13, U16}, ", stringify!($name), "},")]
+            ///         Aes,
+            ///     },
+            ///     pac,
+            /// };
+            ///
+            /// let mut dp: pac::Peripherals = pac::Peripherals::take().unwrap();
+            /// let mut aes: Aes = Aes::new(dp.AES, &mut dp.RCC);
+            ///
+            #[doc = concat!(" let cipher: ", stringify!($name), "<U16, U13> =")]
+            #[doc = concat!("     ", stringify!($name), "::new(&mut aes, &[0; ", $bytes, "].into());")]
+            /// ```
+            pub fn new(aes: &'a mut Aes, key: &Key<Self>) -> Self {
+                $name {
+                    aes: RefCell::new(aes),
+                    key: be_words(key),
+                    sizes: PhantomData,
+                }
+            }
+        }
+
+        impl<'a, T: CcmTagSize, N: CcmNonceSize> core::fmt::Debug for $name<'a, T, N> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.write_str(concat!(stringify!($name), " { ... }"))
+            }
+        }
+
+        impl<'a, T: CcmTagSize, N: CcmNonceSize> KeySizeUser for $name<'a, T, N> {
+            type KeySize = $keysize;
+        }
+
+        impl<'a, T: CcmTagSize, N: CcmNonceSize> AeadCore for $name<'a, T, N> {
+            type NonceSize = N;
+            type TagSize = T;
+            type CiphertextOverhead = U0;
+        }
+
+        impl<'a, T: CcmTagSize, N: CcmNonceSize> AeadInPlace for $name<'a, T, N> {
+            fn encrypt_in_place_detached(
+                &self,
+                nonce: &Nonce<Self>,
+                associated_data: &[u8],
+                buffer: &mut [u8],
+            ) -> aead::Result<Tag<Self>> {
+                let mut tag: Tag<Self> = GenericArray::default();
+                self.aes
+                    .borrow_mut()
+                    .encrypt_ccm_inplace(&self.key, nonce, associated_data, buffer, &mut tag)
+                    .map_err(|_| aead::Error)?;
+                Ok(tag)
+            }
+
+            fn decrypt_in_place_detached(
+                &self,
+                nonce: &Nonce<Self>,
+                associated_data: &[u8],
+                buffer: &mut [u8],
+                tag: &Tag<Self>,
+            ) -> aead::Result<()> {
+                self.aes
+                    .borrow_mut()
+                    .decrypt_ccm_inplace(&self.key, nonce, associated_data, buffer, tag)
+                    .map_err(|_| aead::Error)
+            }
+        }
+    };
+}
+
+ccm!(Aes128Ccm, U16, 4, 16, "128");
+ccm!(Aes256Ccm, U32, 8, 32, "256");
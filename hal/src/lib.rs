@@ -54,6 +54,7 @@ pub mod rng;
 pub mod rtc;
 pub mod spi;
 pub mod subghz;
+pub mod tamp;
 pub mod uart;
 pub mod util;
 
@@ -0,0 +1,2960 @@
+//! Public key accelerator
+//!
+//! Quickstart:
+//!
+//! * [ECDSA signing](Pka::ecdsa_sign)
+//! * [Deterministic ECDSA signing](Pka::ecdsa_sign_deterministic)
+//! * [ECDSA verify](Pka::ecdsa_verify)
+//! * [ECDH](Pka::ecdh_shared_secret)
+//! * [Public key validation](Pka::point_on_curve)
+//! * [RSA verify](Pka::rsa_pkcs1v15_sha256_verify)
+//! * [RSA private key operation](Pka::rsa_crt)
+//! * [Modular exponentiation](Pka::modular_exp)
+//! * [Big integer arithmetic](Pka::arith_add)
+//!
+//! # Interrupts
+//!
+//! The `_start` methods enable the PKA interrupt sources, with the PKA IRQ
+//! unmasked in the NVIC the core can sleep while the PKA is busy.
+//! The PKA interrupt handler must call [`Pka::on_irq`].
+//!
+//! ```no_run
+//! # #[cfg(all(not(feature = "stm32wl5x_cm0p"), feature = "rt"))]
+//! # fn main() -> Result<(), stm32wl_hal::pka::EcdsaSignError> {
+//! # let mut pka = unsafe { stm32wl_hal::pka::Pka::steal() };
+//! # let nonce: [u32; 8] = [0; 8];
+//! # let priv_key: [u32; 8] = [0; 8];
+//! # let hash: [u32; 8] = [0; 8];
+//! use stm32wl_hal::{
+//!     pac::interrupt,
+//!     pka::{curve::NIST_P256, Pka},
+//! };
+//!
+//! #[interrupt]
+//! fn PKA() {
+//!     Pka::on_irq();
+//! }
+//!
+//! unsafe { Pka::unmask_irq() };
+//!
+//! let mut r_sign: [u32; 8] = [0; 8];
+//! let mut s_sign: [u32; 8] = [0; 8];
+//! pka.ecdsa_sign_start(&NIST_P256, &nonce, &priv_key, &hash)?;
+//! loop {
+//!     match pka.ecdsa_sign_result(&mut r_sign, &mut s_sign) {
+//!         Err(nb::Error::WouldBlock) => pka.wfi(),
+//!         Err(nb::Error::Other(e)) => return Err(e),
+//!         Ok(()) => break,
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! # #[cfg(not(all(not(feature = "stm32wl5x_cm0p"), feature = "rt")))]
+//! # fn main() {}
+//! ```
+//!
+//! With the `async` feature the `_async` methods, such as
+//! [`ecdsa_sign_async`](Pka::ecdsa_sign_async), wait for the interrupt with
+//! an executor instead.
+//!
+//! # Key material
+//!
+//! The PKA RAM is zeroed after every ECDSA sign, ECC scalar multiplication,
+//! ECDH, modular exponentiation, and RSA operation, including operations
+//! that fail, and when the driver is [freed](Pka::free).
+//!
+//! Private values computed by the driver are returned in a [`Secret`],
+//! which is zeroed when it is dropped.
+//!
+//! # Alternatives
+//!
+//! The [p256-cortex-m4] crate offers an assembly implementation of P256 that is
+//! about 10x faster then the hardware.
+//!
+//! # ECDSA key pair generation
+//!
+//! Generate a private key for [`NIST_P256`](curve::NIST_P256):
+//!
+//! ```console
+//! $ openssl ecparam -genkey -name prime256v1 -out key.pem
+//! $ openssl ec -in key.pem -noout -text
+//! read EC key
+//! Private-Key: (256 bit)
+//! priv:
+//!     49:ac:87:27:ce:e8:74:84:fe:6d:fd:a5:10:23:8a:
+//!     d4:11:ac:e8:fe:59:3a:8c:b7:04:92:d6:59:db:81:
+//!     80:2a
+//! pub:
+//!     04:fa:65:57:59:de:c3:90:28:96:46:0a:43:2b:ae:
+//!     1d:00:91:26:e1:b4:88:78:9f:f4:ef:6b:9a:9b:de:
+//!     1b:c3:63:8f:a0:2a:c4:c4:21:ca:88:4f:06:51:f4:
+//!     e9:85:e3:cf:d0:af:40:69:cc:87:f3:a8:8a:8e:95:
+//!     e7:55:6c:ed:97
+//! ASN1 OID: prime256v1
+//! NIST CURVE: P-256
+//! ```
+//!
+//! The first `04` on the public key is encoding information, expressed in rust
+//! that keypair becomes this:
+//!
+//! ```
+//! use stm32wl_hal::pka::EcdsaPublicKey;
+//!
+//! const PRIV_KEY: [u32; 8] = [
+//!     0x49ac8727, 0xcee87484, 0xfe6dfda5, 0x10238ad4, 0x11ace8fe, 0x593a8cb7, 0x0492d659,
+//!     0xdb81802a,
+//! ];
+//!
+//! const CURVE_PT_X: [u32; 8] = [
+//!     0xfa655759, 0xdec39028, 0x96460a43, 0x2bae1d00, 0x9126e1b4, 0x88789ff4, 0xef6b9a9b,
+//!     0xde1bc363,
+//! ];
+//! const CURVE_PT_Y: [u32; 8] = [
+//!     0x8fa02ac4, 0xc421ca88, 0x4f0651f4, 0xe985e3cf, 0xd0af4069, 0xcc87f3a8, 0x8a8e95e7,
+//!     0x556ced97,
+//! ];
+//! let pub_key: EcdsaPublicKey<8> = EcdsaPublicKey {
+//!     curve_pt_x: &CURVE_PT_X,
+//!     curve_pt_y: &CURVE_PT_Y,
+//! };
+//! ```
+//!
+//! Use this command to find the name of other curves:
+//!
+//! ```bash
+//! openssl ecparam -list_curves
+//! ```
+//!
+//! [p256-cortex-m4]: https://crates.io/crates/p256-cortex-m4
+
+mod rfc6979;
+mod secret;
+
+pub use rfc6979::rfc6979_nonce;
+pub use secret::Secret;
+
+use crate::pac;
+use core::{
+    cmp::Ordering,
+    mem::size_of,
+    ptr::{read_volatile, write_volatile},
+};
+use nb;
+
+/// Errors from an ECDSA signing operation.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum EcdsaSignError {
+    /// Address access is out of range (unmapped address).
+    Address,
+    /// An AHB access to the PKA RAM occurred while the PKA core was computing
+    /// and using its internal RAM.
+    /// (AHB PKA_RAM access are not allowed while PKA operation is in progress).
+    Ram,
+    /// Signature part R is equal to 0.
+    Rzero,
+    /// Signature part S is equal to 0.
+    Szero,
+    /// PKA mode does not match the expected mode.
+    Mode {
+        /// Actual mode bits
+        mode: u8,
+    },
+    /// Unknown result code.
+    Unknown {
+        /// Unknown result code bits.
+        bits: u32,
+    },
+}
+
+impl EcdsaSignError {
+    const fn from_raw(raw: u32) -> nb::Result<(), EcdsaSignError> {
+        match raw {
+            0 => Ok(()),
+            1 => Err(nb::Error::Other(EcdsaSignError::Rzero)),
+            2 => Err(nb::Error::Other(EcdsaSignError::Szero)),
+            _ => Err(nb::Error::Other(EcdsaSignError::Unknown { bits: raw })),
+        }
+    }
+
+    const fn mode(mode: u8) -> nb::Result<(), EcdsaSignError> {
+        Err(nb::Error::Other(EcdsaSignError::Mode { mode }))
+    }
+}
+
+/// Errors from an ECDSA verify operation.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum EcdsaVerifyError {
+    /// Address access is out of range (unmapped address).
+    Address,
+    /// An AHB access to the PKA RAM occurred while the PKA core was computing
+    /// and using its internal RAM.
+    /// (AHB PKA_RAM access is not allowed while a PKA operation is in progress).
+    Ram,
+    /// Invalid signature.
+    Invalid,
+    /// The public key is not a point on the curve.
+    ///
+    /// Only returned by [`Pka::ecdsa_verify_checked`].
+    PublicKey,
+    /// PKA mode does not match the expected mode.
+    Mode {
+        /// Actual mode bits
+        mode: u8,
+    },
+}
+
+impl From<PointCheckError> for EcdsaVerifyError {
+    fn from(e: PointCheckError) -> Self {
+        match e {
+            PointCheckError::Address => EcdsaVerifyError::Address,
+            PointCheckError::Ram => EcdsaVerifyError::Ram,
+            PointCheckError::NotOnCurve => EcdsaVerifyError::PublicKey,
+            PointCheckError::Mode { mode } => EcdsaVerifyError::Mode { mode },
+        }
+    }
+}
+
+impl EcdsaVerifyError {
+    const fn from_raw(raw: u32) -> nb::Result<(), EcdsaVerifyError> {
+        match raw {
+            0 => Ok(()),
+            _ => Err(nb::Error::Other(EcdsaVerifyError::Invalid)),
+        }
+    }
+
+    const fn mode(mode: u8) -> nb::Result<(), EcdsaVerifyError> {
+        Err(nb::Error::Other(EcdsaVerifyError::Mode { mode }))
+    }
+}
+
+/// Errors from an ECC scalar multiplication operation.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum EccError {
+    /// Address access is out of range (unmapped address).
+    Address,
+    /// An AHB access to the PKA RAM occurred while the PKA core was computing
+    /// and using its internal RAM.
+    /// (AHB PKA_RAM access is not allowed while a PKA operation is in progress).
+    Ram,
+    /// The input point is not on the curve.
+    ///
+    /// Only returned by [`Pka::ecdh_shared_secret`].
+    NotOnCurve,
+    /// PKA mode does not match the expected mode.
+    Mode {
+        /// Actual mode bits
+        mode: u8,
+    },
+}
+
+impl From<PointCheckError> for EccError {
+    fn from(e: PointCheckError) -> Self {
+        match e {
+            PointCheckError::Address => EccError::Address,
+            PointCheckError::Ram => EccError::Ram,
+            PointCheckError::NotOnCurve => EccError::NotOnCurve,
+            PointCheckError::Mode { mode } => EccError::Mode { mode },
+        }
+    }
+}
+
+impl EccError {
+    const fn mode(mode: u8) -> nb::Result<(), EccError> {
+        Err(nb::Error::Other(EccError::Mode { mode }))
+    }
+}
+
+/// Errors from a point check operation.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum PointCheckError {
+    /// Address access is out of range (unmapped address).
+    Address,
+    /// An AHB access to the PKA RAM occurred while the PKA core was computing
+    /// and using its internal RAM.
+    /// (AHB PKA_RAM access is not allowed while a PKA operation is in progress).
+    Ram,
+    /// The point is not on the curve.
+    NotOnCurve,
+    /// PKA mode does not match the expected mode.
+    Mode {
+        /// Actual mode bits
+        mode: u8,
+    },
+}
+
+impl PointCheckError {
+    const fn from_raw(raw: u32) -> nb::Result<(), PointCheckError> {
+        match raw {
+            0 => Ok(()),
+            _ => Err(nb::Error::Other(PointCheckError::NotOnCurve)),
+        }
+    }
+
+    const fn mode(mode: u8) -> nb::Result<(), PointCheckError> {
+        Err(nb::Error::Other(PointCheckError::Mode { mode }))
+    }
+}
+
+/// Errors from a modular exponentiation or RSA operation.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum RsaError {
+    /// Address access is out of range (unmapped address).
+    Address,
+    /// An AHB access to the PKA RAM occurred while the PKA core was computing
+    /// and using its internal RAM.
+    /// (AHB PKA_RAM access is not allowed while a PKA operation is in progress).
+    Ram,
+    /// Invalid signature.
+    ///
+    /// Only returned by [`Pka::rsa_pkcs1v15_sha256_verify`].
+    Invalid,
+    /// PKA mode does not match the expected mode.
+    Mode {
+        /// Actual mode bits
+        mode: u8,
+    },
+}
+
+impl RsaError {
+    const fn mode(mode: u8) -> nb::Result<(), RsaError> {
+        Err(nb::Error::Other(RsaError::Mode { mode }))
+    }
+}
+
+/// Errors from an arithmetic or modular arithmetic operation.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ArithmeticError {
+    /// Address access is out of range (unmapped address).
+    Address,
+    /// An AHB access to the PKA RAM occurred while the PKA core was computing
+    /// and using its internal RAM.
+    /// (AHB PKA_RAM access is not allowed while a PKA operation is in progress).
+    Ram,
+}
+
+impl From<ArithmeticError> for PointCheckError {
+    fn from(e: ArithmeticError) -> Self {
+        match e {
+            ArithmeticError::Address => PointCheckError::Address,
+            ArithmeticError::Ram => PointCheckError::Ram,
+        }
+    }
+}
+
+/// PKA operation codes.
+#[derive(Debug, Clone, Copy)]
+#[repr(u8)]
+#[allow(dead_code)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+enum PkaOpcode {
+    /// Montgomery parameter computation then modular exponentiation.
+    MontgomeryParameterExponentiation = 0b000000,
+    /// Montgomery parameter computation only.
+    MontgomeryParameter = 0b000001,
+    /// Modular exponentiation only (Montgomery parameter must be loaded first).
+    ModularExponentiation = 0b000010,
+    /// Montgomery parameter computation then ECC scalar multiplication.
+    MontgomeryParameterEcc = 0b100000,
+    /// ECC scalar multiplication only (Montgomery parameter must be loaded first).
+    EccScalar = 0b100010,
+    /// ECDSA signing.
+    EcdsaSign = 0b100100,
+    /// ECDSA verification.
+    EcdsaVerify = 0b100110,
+    /// Point on elliptic curve Fp check.
+    Point = 0b101000,
+    /// RSA CRT exponentiation.
+    RsaCrt = 0b000111,
+    /// Modular inversion.
+    ModularInversion = 0b001000,
+    /// Arithmetic addition.
+    ArithmeticAdd = 0b001001,
+    /// Arithmetic subtraction.
+    ArithmeticSub = 0b001010,
+    /// Arithmetic multiplication.
+    ArithmeticMul = 0b001011,
+    /// Arithmetic comparison.
+    ArithmeticCmp = 0b001100,
+    /// Modular reduction.
+    ModularRed = 0b001101,
+    /// Modular addition.
+    ModularAdd = 0b001110,
+    /// Modular subtraction.
+    ModularSub = 0b001111,
+    /// Montgomery multiplication.
+    MontgomeryMul = 0b010000,
+}
+
+impl From<PkaOpcode> for u8 {
+    fn from(x: PkaOpcode) -> Self {
+        x as u8
+    }
+}
+
+const BASE: usize = 0x5800_2000;
+const RAM_BASE: usize = BASE + 0x400;
+const RAM_NUM_DW: usize = 894;
+
+// ECDSA sign input addresses
+const ECDSA_SIGN_N_LEN: usize = BASE + 0x400;
+const ECDSA_SIGN_P_LEN: usize = BASE + 0x404;
+const ECDSA_SIGN_A_SIGN: usize = BASE + 0x408;
+const ECDSA_SIGN_A: usize = BASE + 0x40C;
+const ECDSA_SIGN_P: usize = BASE + 0x460;
+const ECDSA_SIGN_K: usize = BASE + 0x508;
+const ECDSA_SIGN_X: usize = BASE + 0x55C;
+const ECDSA_SIGN_Y: usize = BASE + 0x5B0;
+const ECDSA_SIGN_Z: usize = BASE + 0xDE8;
+const ECDSA_SIGN_D: usize = BASE + 0xE3C;
+const ECDSA_SIGN_N: usize = BASE + 0xE94;
+
+// ECDSA sign output addresses
+const ECDSA_SIGN_OUT_R: usize = BASE + 0x700;
+const ECDSA_SIGN_OUT_S: usize = BASE + 0x754;
+const ECDSA_SIGN_OUT_RESULT: usize = BASE + 0xEE8;
+
+// ECDSA verify input addresses
+const ECDSA_VERIFY_N_LEN: usize = BASE + 0x404;
+const ECDSA_VERIFY_P_LEN: usize = BASE + 0x4B4;
+const ECDSA_VERIFY_A_SIGN: usize = BASE + 0x45C;
+const ECDSA_VERIFY_A: usize = BASE + 0x460;
+const ECDSA_VERIFY_P: usize = BASE + 0x4B8;
+const ECDSA_VERIFY_X: usize = BASE + 0x5E8;
+const ECDSA_VERIFY_Y: usize = BASE + 0x63C;
+const ECDSA_VERIFY_XQ: usize = BASE + 0xF40;
+const ECDSA_VERIFY_YQ: usize = BASE + 0xF94;
+const ECDSA_VERIFY_R: usize = BASE + 0x1098;
+const ECDSA_VERIFY_S: usize = BASE + 0xA44;
+const ECDSA_VERIFY_Z: usize = BASE + 0xFE8;
+const ECDSA_VERIFY_N: usize = BASE + 0xD5C;
+
+// ECDSA verify output addresses
+const ECDSA_VERIFY_OUT: usize = BASE + 0x5B0;
+
+// ECC scalar multiplication input addresses
+const ECC_SCALAR_MUL_K_LEN: usize = BASE + 0x400;
+const ECC_SCALAR_MUL_P_LEN: usize = BASE + 0x404;
+const ECC_SCALAR_MUL_A_SIGN: usize = BASE + 0x408;
+const ECC_SCALAR_MUL_A: usize = BASE + 0x40C;
+const ECC_SCALAR_MUL_P: usize = BASE + 0x460;
+const ECC_SCALAR_MUL_K: usize = BASE + 0x508;
+const ECC_SCALAR_MUL_X: usize = BASE + 0x55C;
+const ECC_SCALAR_MUL_Y: usize = BASE + 0x5B0;
+
+// Montgomery parameter input addresses
+const MONTGOMERY_PARAM_N_LEN: usize = BASE + 0x404;
+const MONTGOMERY_PARAM_N: usize = BASE + 0xD5C;
+
+// Montgomery parameter output addresses
+const MONTGOMERY_PARAM_OUT: usize = BASE + 0x594;
+
+// Point check input addresses
+const POINT_CHECK_P_LEN: usize = BASE + 0x404;
+const POINT_CHECK_A_SIGN: usize = BASE + 0x408;
+const POINT_CHECK_A: usize = BASE + 0x40C;
+const POINT_CHECK_B: usize = BASE + 0x7FC;
+const POINT_CHECK_P: usize = BASE + 0x460;
+const POINT_CHECK_X: usize = BASE + 0x55C;
+const POINT_CHECK_Y: usize = BASE + 0x5B0;
+const POINT_CHECK_R2: usize = BASE + 0x4B4;
+
+// Point check output addresses
+const POINT_CHECK_OUT: usize = BASE + 0x400;
+
+// ECC scalar multiplication output addresses
+const ECC_SCALAR_MUL_OUT_X: usize = BASE + 0x55C;
+const ECC_SCALAR_MUL_OUT_Y: usize = BASE + 0x5B0;
+
+// Modular exponentiation input addresses
+const MODULAR_EXP_E_LEN: usize = BASE + 0x400;
+const MODULAR_EXP_N_LEN: usize = BASE + 0x404;
+const MODULAR_EXP_A: usize = BASE + 0x724;
+const MODULAR_EXP_E: usize = BASE + 0xBD0;
+const MODULAR_EXP_N: usize = BASE + 0xD5C;
+
+// Modular exponentiation output addresses
+const MODULAR_EXP_OUT: usize = BASE + 0x724;
+
+// RSA CRT exponentiation input addresses
+const RSA_CRT_N_LEN: usize = BASE + 0x404;
+const RSA_CRT_DP: usize = BASE + 0x65C;
+const RSA_CRT_DQ: usize = BASE + 0xBD0;
+const RSA_CRT_QINV: usize = BASE + 0x7EC;
+const RSA_CRT_P: usize = BASE + 0x97C;
+const RSA_CRT_Q: usize = BASE + 0xD5C;
+const RSA_CRT_A: usize = BASE + 0xEEC;
+
+// RSA CRT exponentiation output addresses
+const RSA_CRT_OUT: usize = BASE + 0x724;
+
+// Arithmetic and modular arithmetic input addresses
+const ARITH_OP_LEN: usize = BASE + 0x404;
+const ARITH_OP1: usize = BASE + 0x8B4;
+const ARITH_OP2: usize = BASE + 0xA44;
+const ARITH_OP3: usize = BASE + 0xD5C;
+
+// Arithmetic and modular arithmetic output addresses
+const ARITH_OUT: usize = BASE + 0xBD0;
+
+// Modular reduction input addresses
+const MODULAR_RED_A_LEN: usize = BASE + 0x400;
+const MODULAR_RED_N_LEN: usize = BASE + 0x404;
+const MODULAR_RED_A: usize = BASE + 0x8B4;
+const MODULAR_RED_N: usize = BASE + 0xA44;
+
+// Modular reduction output addresses
+const MODULAR_RED_OUT: usize = BASE + 0xBD0;
+
+// length of an operand in bits, without leading zeros
+fn bit_len(buf: &[u32]) -> u32 {
+    buf.iter().position(|&dw| dw != 0).map_or(0, |idx| {
+        ((buf.len() - idx) * 32) as u32 - buf[idx].leading_zeros()
+    })
+}
+
+/// Maximum operand size in 32-bit words for modular exponentiation, RSA,
+/// and arithmetic operations.
+///
+/// This is the 3136-bit PKA operand limit.
+pub const MAX_OPERAND_SIZE: usize = 98;
+
+// DER encoded DigestInfo prefix for SHA-256, RFC 8017 section 9.2
+const SHA256_DIGEST_INFO: [u8; 19] = [
+    0x30, 0x31, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01, 0x05,
+    0x00, 0x04, 0x20,
+];
+
+// byte `idx` of the EMSA-PKCS1-v1_5 encoding of a SHA-256 hash, `len` bytes long
+//
+// 0x00 || 0x01 || 0xFF... || 0x00 || DigestInfo || hash
+fn pkcs1v15_sha256_byte(idx: usize, len: usize, hash: &[u32; 8]) -> u8 {
+    let t_start: usize = len - SHA256_DIGEST_INFO.len() - 32;
+    let h_start: usize = t_start + SHA256_DIGEST_INFO.len();
+    if idx == 1 {
+        0x01
+    } else if idx == 0 || idx == t_start - 1 {
+        0x00
+    } else if idx < t_start {
+        0xFF
+    } else if idx < h_start {
+        SHA256_DIGEST_INFO[idx - t_start]
+    } else {
+        hash[(idx - h_start) / 4].to_be_bytes()[(idx - h_start) % 4]
+    }
+}
+
+/// PKA driver.
+#[derive(Debug)]
+pub struct Pka {
+    pka: pac::PKA,
+}
+
+impl Pka {
+    /// Create a new PKA driver from a PKA peripheral.
+    ///
+    /// This will enable clocks and reset the PKA peripheral.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use stm32wl_hal::{pac, pka::Pka};
+    ///
+    /// let mut dp: pac::Peripherals = pac::Peripherals::take().unwrap();
+    /// let mut pka = Pka::new(dp.PKA, &mut dp.RCC);
+    /// ```
+    pub fn new(pka: pac::PKA, rcc: &mut pac::RCC) -> Pka {
+        Self::enable_clock(rcc);
+        unsafe { Self::pulse_reset(rcc) };
+
+        // When the PKA peripheral reset signal is released PKA RAM is cleared
+        // automatically, taking 894 clock cycles.
+        // During this time the setting of EN bit in PKA_CR is ignored
+        pka.cr.write(|w| w.en().set_bit());
+        while pka.cr.read().en().bit_is_clear() {
+            pka.cr.write(|w| w.en().set_bit());
+        }
+
+        Pka { pka }
+    }
+
+    /// Returns `true` if the PKA is enabled.
+    #[inline]
+    pub fn is_enabled(&mut self) -> bool {
+        self.pka.cr.read().en().is_enabled()
+    }
+
+    /// Free the PKA peripheral from the driver.
+    ///
+    /// Any operation in progress is aborted and the PKA RAM is zeroed, the
+    /// peripheral is left enabled.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use stm32wl_hal::{pac, pka::Pka};
+    ///
+    /// let mut dp: pac::Peripherals = pac::Peripherals::take().unwrap();
+    /// let pka: pac::PKA = dp.PKA;
+    /// let pka: Pka = Pka::new(pka, &mut dp.RCC);
+    /// // ... use PKA
+    /// let pka: pac::PKA = pka.free();
+    /// ```
+    pub fn free(mut self) -> pac::PKA {
+        // clearing EN aborts an operation in progress,
+        // the RAM cannot be written while an operation is in progress
+        self.pka.cr.write(|w| w.en().clear_bit());
+        while self.pka.cr.read().en().bit_is_set() {}
+        self.pka.cr.write(|w| w.en().set_bit());
+        while self.pka.cr.read().en().bit_is_clear() {
+            self.pka.cr.write(|w| w.en().set_bit());
+        }
+        self.zero_ram();
+        self.pka
+    }
+
+    /// Steal the PKA peripheral from whatever is currently using it.
+    ///
+    /// This will **not** initialize the PKA (unlike [`new`]).
+    ///
+    /// # Safety
+    ///
+    /// This will create a new PKA peripheral, bypassing the singleton checks
+    /// that normally occur.
+    /// You are responsible for ensuring that the driver has exclusive access to
+    /// the PKA peripheral.
+    /// You are also responsible for ensuring the PKA has been setup correctly.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stm32wl_hal::pka::Pka;
+    ///
+    /// // ... setup happens here
+    ///
+    /// let pka = unsafe { Pka::steal() };
+    /// ```
+    ///
+    /// [`new`]: Pka::new
+    pub unsafe fn steal() -> Pka {
+        Pka {
+            pka: pac::Peripherals::steal().PKA,
+        }
+    }
+
+    /// Disable the PKA clock.
+    ///
+    /// # Safety
+    ///
+    /// 1. You are responsible for ensuring the PKA bus is in a state where the
+    ///    clock can be disabled without entering an error state.
+    /// 2. You cannot use the PKA bus while the clock is disabled.
+    /// 3. You are responsible for re-enabling the clock before resuming use
+    ///    of the PKA bus.
+    /// 4. You are reponsible for setting up anything that may have lost state
+    ///    while the clock was disabled.
+    #[inline]
+    pub unsafe fn disable_clock(rcc: &mut pac::RCC) {
+        rcc.ahb3enr.modify(|_, w| w.pkaen().disabled());
+    }
+
+    /// Enable the PKA clock.
+    #[inline]
+    pub fn enable_clock(rcc: &mut pac::RCC) {
+        rcc.ahb3enr.modify(|_, w| w.pkaen().enabled());
+        rcc.ahb3enr.read(); // delay after an RCC peripheral clock enabling
+    }
+
+    /// Reset the PKA.
+    ///
+    /// # Safety
+    ///
+    /// 1. The PKA must not be in-use.
+    /// 2. You are reponsible for setting up the PKA after a reset.
+    #[inline]
+    pub unsafe fn pulse_reset(rcc: &mut pac::RCC) {
+        rcc.ahb3rstr.modify(|_, w| w.pkarst().set_bit());
+        rcc.ahb3rstr.modify(|_, w| w.pkarst().clear_bit());
+    }
+
+    /// Unmask the PKA IRQ in the NVIC.
+    ///
+    /// # Safety
+    ///
+    /// This can break mask-based critical sections.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # #[cfg(not(feature = "stm32wl5x_cm0p"))]
+    /// unsafe { stm32wl_hal::pka::Pka::unmask_irq() };
+    /// ```
+    #[cfg(all(not(feature = "stm32wl5x_cm0p"), feature = "rt"))]
+    #[cfg_attr(docsrs, doc(cfg(all(not(feature = "stm32wl5x_cm0p"), feature = "rt"))))]
+    #[inline]
+    pub unsafe fn unmask_irq() {
+        pac::NVIC::unmask(pac::Interrupt::PKA)
+    }
+
+    /// Mask the PKA IRQ in the NVIC.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # #[cfg(not(feature = "stm32wl5x_cm0p"))]
+    /// stm32wl_hal::pka::Pka::mask_irq();
+    /// ```
+    #[cfg(all(not(feature = "stm32wl5x_cm0p"), feature = "rt"))]
+    #[cfg_attr(docsrs, doc(cfg(all(not(feature = "stm32wl5x_cm0p"), feature = "rt"))))]
+    #[inline]
+    pub fn mask_irq() {
+        pac::NVIC::mask(pac::Interrupt::PKA)
+    }
+
+    /// PKA interrupt handler.
+    ///
+    /// Call this from the PKA interrupt handler when using interrupts to
+    /// complete operations.
+    ///
+    /// This disables the PKA interrupt sources, leaving the flags set for the
+    /// `_result` methods, and wakes the task waiting on an `_async` method.
+    ///
+    /// The interrupt sources are enabled again by the next `_start` method.
+    ///
+    /// # Example
+    ///
+    /// See the [module level documentation](self#interrupts).
+    pub fn on_irq() {
+        // safety: only the interrupt enables are modified, the PKA is not
+        // processing when the completion or error interrupts are raised
+        let pka: &pac::pka::RegisterBlock = unsafe { &*pac::PKA::ptr() };
+        #[rustfmt::skip]
+        pka.cr.modify(|_, w| {
+            w
+                .addrerrie().disabled()
+                .ramerrie().disabled()
+                .procendie().disabled()
+        });
+
+        #[cfg(feature = "async")]
+        cortex_m::interrupt::free(|cs| {
+            if let Some(waker) = WAKER.borrow(cs).borrow_mut().take() {
+                waker.wake()
+            }
+        });
+    }
+
+    /// Sleep until the PKA operation in progress completes.
+    ///
+    /// This executes `WFI` in a critical section if the operation is in
+    /// progress, the PKA interrupt wakes the core even though interrupts are
+    /// disabled, the handler runs after this method returns.
+    ///
+    /// The PKA IRQ must be unmasked, and the handler must call
+    /// [`on_irq`](Self::on_irq).
+    /// Other interrupts also wake the core, use this in a loop with a
+    /// `_result` method.
+    ///
+    /// # Example
+    ///
+    /// See the [module level documentation](self#interrupts).
+    pub fn wfi(&mut self) {
+        cortex_m::interrupt::free(|_| {
+            let sr = self.pka.sr.read();
+            if sr.procendf().is_in_progress()
+                && sr.addrerrf().bit_is_clear()
+                && sr.ramerrf().bit_is_clear()
+            {
+                cortex_m::asm::wfi()
+            }
+        })
+    }
+
+    #[inline]
+    fn clear_all_flags(&mut self) {
+        #[rustfmt::skip]
+        self.pka.clrfr.write(|w| {
+            w
+                .addrerrfc().set_bit()
+                .ramerrfc().set_bit()
+                .procendfc().set_bit()
+        });
+    }
+
+    // also used to scrub the private key material from the RAM after an
+    // operation, the writes are volatile and cannot be optimized out
+    fn zero_ram(&mut self) {
+        (0..RAM_NUM_DW)
+            .into_iter()
+            .for_each(|dw| unsafe { write_volatile((dw * 4 + RAM_BASE) as *mut u32, 0) });
+    }
+
+    unsafe fn write_ram(&mut self, offset: usize, buf: &[u32]) {
+        // asserts are for internal correctness, should not be accessible by users
+        debug_assert_eq!(offset % 4, 0);
+        debug_assert!(offset + buf.len() * size_of::<u32>() < 0x5800_33FF);
+        buf.iter().rev().enumerate().for_each(|(idx, &dw)| {
+            write_volatile((offset + idx * size_of::<u32>()) as *mut u32, dw)
+        });
+    }
+
+    unsafe fn read_ram(&mut self, offset: usize, buf: &mut [u32]) {
+        // asserts are for internal correctness, should not be accessible by users
+        debug_assert_eq!(offset % 4, 0);
+        debug_assert!(offset + buf.len() * size_of::<u32>() < 0x5800_33FF);
+        buf.iter_mut().rev().enumerate().for_each(|(idx, dw)| {
+            *dw = read_volatile((offset + idx * size_of::<u32>()) as *const u32);
+        });
+    }
+
+    #[inline]
+    fn start_process(&mut self, opcode: PkaOpcode) {
+        #[rustfmt::skip]
+        self.pka.cr.write(|w| unsafe {
+            w
+                .addrerrie().enabled()
+                .ramerrie().enabled()
+                .procendie().enabled()
+                .mode().bits(opcode.into())
+                .start().set_bit()
+                .en().set_bit()
+        });
+    }
+
+    /// ECDSA (Ellipctic Curve Digital Signature Algorithm) signing.
+    ///
+    /// This is the blocking ECDSA sign method, equivalent to calling
+    /// [`ecdsa_sign_start`](Self::ecdsa_sign_start) then polling
+    /// [`ecdsa_sign_result`](Self::ecdsa_sign_result).
+    ///
+    /// ```no_run
+    /// # let mut pka = unsafe { stm32wl_hal::pka::Pka::steal() };
+    /// # let curve = stm32wl_hal::pka::curve::NIST_P256;
+    /// # let nonce: [u32; 8] = [0; 8];
+    /// # let priv_key: [u32; 8] = [0; 8];
+    /// # let hash: [u32; 8] = [0; 8];
+    /// # let mut r_sign: [u32; 8] = [0; 8];
+    /// # let mut s_sign: [u32; 8] = [0; 8];
+    /// // blocking
+    /// pka.ecdsa_sign(&curve, &nonce, &priv_key, &hash, &mut r_sign, &mut s_sign);
+    ///
+    /// // non-blocking
+    /// pka.ecdsa_sign_start(&curve, &nonce, &priv_key, &hash)?;
+    /// nb::block!(pka.ecdsa_sign_result(&mut r_sign, &mut s_sign))?;
+    /// # Ok::<(), stm32wl_hal::pka::EcdsaSignError>(())
+    /// ```
+    ///
+    /// # Computation Times
+    ///
+    /// | Modulus Length (bits) | Cycles   | Seconds at 48MHz |
+    /// |-----------------------|----------|------------------|
+    /// | 160                   | 1760000  | 0.037            |
+    /// | 192                   | 2664000  | 0.056            |
+    /// | 256                   | 5249000  | 0.109            |
+    /// | 320                   | 9016000  | 0.188            |
+    /// | 384                   | 14596000 | 0.304            |
+    /// | 512                   | 30618000 | 0.638            |
+    /// | 521                   | 35540000 | 0.740            |
+    pub fn ecdsa_sign<const MODULUS_SIZE: usize, const PRIME_ORDER_SIZE: usize>(
+        &mut self,
+        curve: &EllipticCurve<MODULUS_SIZE, PRIME_ORDER_SIZE>,
+        nonce: &[u32; PRIME_ORDER_SIZE],
+        priv_key: &[u32; PRIME_ORDER_SIZE],
+        hash: &[u32; PRIME_ORDER_SIZE],
+        r_sign: &mut [u32; MODULUS_SIZE],
+        s_sign: &mut [u32; MODULUS_SIZE],
+    ) -> Result<(), EcdsaSignError> {
+        self.ecdsa_sign_start(curve, nonce, priv_key, hash)?;
+        nb::block!(self.ecdsa_sign_result(r_sign, s_sign))
+    }
+
+    /// ECDSA signing with a deterministic nonce.
+    ///
+    /// The nonce is generated from the private key and the hash with
+    /// [`rfc6979_nonce`] as specified in RFC 6979.
+    /// This removes the need for a random nonce, a biased or repeated nonce
+    /// leaks the private key.
+    ///
+    /// The hash must be the SHA-256 digest (truncated to the prime order) for
+    /// the signature to match other RFC 6979 implementations.
+    ///
+    /// ```no_run
+    /// # let mut pka = unsafe { stm32wl_hal::pka::Pka::steal() };
+    /// use stm32wl_hal::pka::curve::NIST_P256;
+    ///
+    /// let priv_key: [u32; 8] = [
+    ///     0x519b423d, 0x715f8b58, 0x1f4fa8ee, 0x59f4771a, 0x5b44c813, 0x0b4e3eac, 0xca54a56d,
+    ///     0xda72b464,
+    /// ];
+    /// let hash: [u32; 8] = [
+    ///     0x44acf6b7, 0xe36c1342, 0xc2c58972, 0x04fe0950, 0x4e1e2efb, 0x1a900377, 0xdbc4e7a6,
+    ///     0xa133ec56,
+    /// ];
+    /// let mut r_sign: [u32; 8] = [0; 8];
+    /// let mut s_sign: [u32; 8] = [0; 8];
+    /// pka.ecdsa_sign_deterministic(&NIST_P256, &priv_key, &hash, &mut r_sign, &mut s_sign)?;
+    /// # Ok::<(), stm32wl_hal::pka::EcdsaSignError>(())
+    /// ```
+    pub fn ecdsa_sign_deterministic<const MODULUS_SIZE: usize, const PRIME_ORDER_SIZE: usize>(
+        &mut self,
+        curve: &EllipticCurve<MODULUS_SIZE, PRIME_ORDER_SIZE>,
+        priv_key: &[u32; PRIME_ORDER_SIZE],
+        hash: &[u32; PRIME_ORDER_SIZE],
+        r_sign: &mut [u32; MODULUS_SIZE],
+        s_sign: &mut [u32; MODULUS_SIZE],
+    ) -> Result<(), EcdsaSignError> {
+        let nonce: Secret<PRIME_ORDER_SIZE> = rfc6979_nonce(curve, priv_key, hash);
+        self.ecdsa_sign(curve, nonce.expose(), priv_key, hash, r_sign, s_sign)
+    }
+
+    /// Start an ECDSA signing operation.
+    ///
+    /// This will enable all the PKA IRQs.
+    ///
+    /// Use the [`ecdsa_sign_result`](Self::ecdsa_sign_result) method to poll
+    /// for completion, or to get the result in an interrupt handler.
+    pub fn ecdsa_sign_start<const MODULUS_SIZE: usize, const PRIME_ORDER_SIZE: usize>(
+        &mut self,
+        curve: &EllipticCurve<MODULUS_SIZE, PRIME_ORDER_SIZE>,
+        nonce: &[u32; PRIME_ORDER_SIZE],
+        priv_key: &[u32; PRIME_ORDER_SIZE],
+        hash: &[u32; PRIME_ORDER_SIZE],
+    ) -> Result<(), EcdsaSignError> {
+        self.zero_ram();
+        let n_length: u32 = bit_len(&curve.prime_order);
+        let p_length: u32 = bit_len(&curve.modulus);
+
+        unsafe {
+            write_volatile(ECDSA_SIGN_N_LEN as *mut u32, n_length);
+            write_volatile(ECDSA_SIGN_P_LEN as *mut u32, p_length);
+            write_volatile(ECDSA_SIGN_A_SIGN as *mut u32, curve.coef_sign.into());
+            self.write_ram(ECDSA_SIGN_A, &curve.coef);
+            self.write_ram(ECDSA_SIGN_P, &curve.modulus);
+            self.write_ram(ECDSA_SIGN_K, nonce);
+            self.write_ram(ECDSA_SIGN_X, &curve.base_point_x);
+            self.write_ram(ECDSA_SIGN_Y, &curve.base_point_y);
+            self.write_ram(ECDSA_SIGN_Z, hash);
+            self.write_ram(ECDSA_SIGN_D, priv_key);
+            self.write_ram(ECDSA_SIGN_N, &curve.prime_order);
+        }
+        let sr = self.pka.sr.read();
+        if sr.addrerrf().bit_is_set() {
+            self.clear_all_flags();
+            self.zero_ram();
+            Err(EcdsaSignError::Address)
+        } else if sr.ramerrf().bit_is_set() {
+            self.clear_all_flags();
+            self.zero_ram();
+            Err(EcdsaSignError::Ram)
+        } else {
+            self.start_process(PkaOpcode::EcdsaSign);
+            Ok(())
+        }
+    }
+
+    /// Get the result of an ECDSA sign operation.
+    ///
+    /// Use this after starting an ECDSA sign operation with
+    /// [`ecdsa_sign_start`](Self::ecdsa_sign_start).
+    pub fn ecdsa_sign_result<const MODULUS_SIZE: usize>(
+        &mut self,
+        r_sign: &mut [u32; MODULUS_SIZE],
+        s_sign: &mut [u32; MODULUS_SIZE],
+    ) -> nb::Result<(), EcdsaSignError> {
+        let mode = self.pka.cr.read().mode();
+        if !mode.is_ecdsasign() {
+            return EcdsaSignError::mode(mode.bits());
+        }
+        let sr = self.pka.sr.read();
+        if sr.addrerrf().bit_is_set() {
+            self.clear_all_flags();
+            self.zero_ram();
+            Err(nb::Error::Other(EcdsaSignError::Address))
+        } else if sr.ramerrf().bit_is_set() {
+            self.clear_all_flags();
+            self.zero_ram();
+            Err(nb::Error::Other(EcdsaSignError::Ram))
+        } else if sr.procendf().is_in_progress() {
+            Err(nb::Error::WouldBlock)
+        } else {
+            self.clear_all_flags();
+
+            unsafe {
+                self.read_ram(ECDSA_SIGN_OUT_R, r_sign);
+                self.read_ram(ECDSA_SIGN_OUT_S, s_sign);
+            }
+
+            let result: u32 = unsafe { read_volatile(ECDSA_SIGN_OUT_RESULT as *const u32) };
+            // Reference manual table 163 "ECDSA sign - Outputs":
+            // If error output is different from zero the content of the PKA
+            // memory should be cleared to avoid leaking information about
+            // the private key.
+            // The RAM is cleared on success as well, it still contains the
+            // private key and the nonce.
+            self.zero_ram();
+            EcdsaSignError::from_raw(result)
+        }
+    }
+
+    /// ECDSA (Ellipctic Curve Digital Signature Algorithm) verification.
+    ///
+    /// This is the blocking ECDSA verify method, equivalent to calling
+    /// [`ecdsa_verify_start`](Self::ecdsa_verify_start) then polling
+    /// [`ecdsa_verify_result`](Self::ecdsa_verify_result).
+    ///
+    /// ```no_run
+    /// # let mut pka = unsafe { stm32wl_hal::pka::Pka::steal() };
+    /// # let curve = stm32wl_hal::pka::curve::NIST_P256;
+    /// # let r_sign: [u32; 8] = [0; 8];
+    /// # let s_sign: [u32; 8] = [0; 8];
+    /// # let curve_pt_x: [u32; 8] = [0; 8];
+    /// # let curve_pt_y: [u32; 8] = [0; 8];
+    /// # let sig = stm32wl_hal::pka::EcdsaSignature { r_sign: &r_sign, s_sign: &s_sign };
+    /// # let pub_key = stm32wl_hal::pka::EcdsaPublicKey { curve_pt_x: &curve_pt_x, curve_pt_y: &curve_pt_y };
+    /// # let hash: [u32; 8] = [0; 8];
+    /// // blocking
+    /// pka.ecdsa_verify(&curve, &sig, &pub_key, &hash)?;
+    ///
+    /// // non-blocking
+    /// pka.ecdsa_verify_start(&curve, &sig, &pub_key, &hash)?;
+    /// nb::block!(pka.ecdsa_verify_result())?;
+    /// # Ok::<(), stm32wl_hal::pka::EcdsaVerifyError>(())
+    /// ```
+    ///
+    /// # Computation Times
+    ///
+    /// | Modulus Length (bits) | Cycles   | Seconds at 48MHz |
+    /// |-----------------------|----------|------------------|
+    /// | 160                   | 3500000  | 0.073            |
+    /// | 192                   | 5350000  | 0.112            |
+    /// | 256                   | 10498000 | 0.219            |
+    /// | 320                   | 18126000 | 0.378            |
+    /// | 384                   | 29118000 | 0.607            |
+    /// | 512                   | 61346000 | 1.278            |
+    /// | 521                   | 71588000 | 1.491            |
+    pub fn ecdsa_verify<const MODULUS_SIZE: usize, const PRIME_ORDER_SIZE: usize>(
+        &mut self,
+        curve: &EllipticCurve<MODULUS_SIZE, PRIME_ORDER_SIZE>,
+        sig: &EcdsaSignature<MODULUS_SIZE>,
+        pub_key: &EcdsaPublicKey<MODULUS_SIZE>,
+        hash: &[u32; PRIME_ORDER_SIZE],
+    ) -> Result<(), EcdsaVerifyError> {
+        self.ecdsa_verify_start(curve, sig, pub_key, hash)?;
+        nb::block!(self.ecdsa_verify_result())
+    }
+
+    /// ECDSA verification with a public key check.
+    ///
+    /// This runs [`point_on_curve`](Self::point_on_curve) on the public key
+    /// before [`ecdsa_verify`](Self::ecdsa_verify), returning
+    /// [`EcdsaVerifyError::PublicKey`] if the public key is not on the curve.
+    ///
+    /// Use this when the public key comes from an untrusted source.
+    /// For the non-blocking equivalent call
+    /// [`point_on_curve_start`](Self::point_on_curve_start) before
+    /// [`ecdsa_verify_start`](Self::ecdsa_verify_start).
+    ///
+    /// ```no_run
+    /// # let mut pka = unsafe { stm32wl_hal::pka::Pka::steal() };
+    /// # let curve = stm32wl_hal::pka::curve::NIST_P256;
+    /// # let r_sign: [u32; 8] = [0; 8];
+    /// # let s_sign: [u32; 8] = [0; 8];
+    /// # let curve_pt_x: [u32; 8] = [0; 8];
+    /// # let curve_pt_y: [u32; 8] = [0; 8];
+    /// # let sig = stm32wl_hal::pka::EcdsaSignature { r_sign: &r_sign, s_sign: &s_sign };
+    /// # let pub_key = stm32wl_hal::pka::EcdsaPublicKey { curve_pt_x: &curve_pt_x, curve_pt_y: &curve_pt_y };
+    /// # let hash: [u32; 8] = [0; 8];
+    /// pka.ecdsa_verify_checked(&curve, &sig, &pub_key, &hash)?;
+    /// # Ok::<(), stm32wl_hal::pka::EcdsaVerifyError>(())
+    /// ```
+    pub fn ecdsa_verify_checked<const MODULUS_SIZE: usize, const PRIME_ORDER_SIZE: usize>(
+        &mut self,
+        curve: &EllipticCurve<MODULUS_SIZE, PRIME_ORDER_SIZE>,
+        sig: &EcdsaSignature<MODULUS_SIZE>,
+        pub_key: &EcdsaPublicKey<MODULUS_SIZE>,
+        hash: &[u32; PRIME_ORDER_SIZE],
+    ) -> Result<(), EcdsaVerifyError> {
+        self.point_on_curve(curve, pub_key)?;
+        self.ecdsa_verify(curve, sig, pub_key, hash)
+    }
+
+    /// Start an ECDSA verify operation.
+    ///
+    /// This will enable all the PKA IRQs.
+    ///
+    /// Use the [`ecdsa_verify_result`](Self::ecdsa_verify_result) method to
+    /// poll for completion, or to get the result in an interrupt handler.
+    pub fn ecdsa_verify_start<const MODULUS_SIZE: usize, const PRIME_ORDER_SIZE: usize>(
+        &mut self,
+        curve: &EllipticCurve<MODULUS_SIZE, PRIME_ORDER_SIZE>,
+        sig: &EcdsaSignature<MODULUS_SIZE>,
+        pub_key: &EcdsaPublicKey<MODULUS_SIZE>,
+        hash: &[u32; PRIME_ORDER_SIZE],
+    ) -> Result<(), EcdsaVerifyError> {
+        self.zero_ram();
+        let n_length: u32 = bit_len(&curve.prime_order);
+        let p_length: u32 = bit_len(&curve.modulus);
+
+        unsafe {
+            write_volatile(ECDSA_VERIFY_N_LEN as *mut u32, n_length);
+            write_volatile(ECDSA_VERIFY_P_LEN as *mut u32, p_length);
+            write_volatile(ECDSA_VERIFY_A_SIGN as *mut u32, curve.coef_sign.into());
+            self.write_ram(ECDSA_VERIFY_A, &curve.coef);
+            self.write_ram(ECDSA_VERIFY_P, &curve.modulus);
+            self.write_ram(ECDSA_VERIFY_X, &curve.base_point_x);
+            self.write_ram(ECDSA_VERIFY_Y, &curve.base_point_y);
+            self.write_ram(ECDSA_VERIFY_XQ, pub_key.curve_pt_x);
+            self.write_ram(ECDSA_VERIFY_YQ, pub_key.curve_pt_y);
+            self.write_ram(ECDSA_VERIFY_R, sig.r_sign);
+            self.write_ram(ECDSA_VERIFY_S, sig.s_sign);
+            self.write_ram(ECDSA_VERIFY_Z, hash);
+            self.write_ram(ECDSA_VERIFY_N, &curve.prime_order);
+        }
+        let sr = self.pka.sr.read();
+        if sr.addrerrf().bit_is_set() {
+            self.clear_all_flags();
+            Err(EcdsaVerifyError::Address)
+        } else if sr.ramerrf().bit_is_set() {
+            self.clear_all_flags();
+            Err(EcdsaVerifyError::Ram)
+        } else {
+            self.start_process(PkaOpcode::EcdsaVerify);
+            Ok(())
+        }
+    }
+
+    /// Get the result of an ECDSA verify operation.
+    ///
+    /// Use this after starting an ECDSA verify operation with
+    /// [`ecdsa_verify_start`](Self::ecdsa_verify_start).
+    pub fn ecdsa_verify_result(&mut self) -> nb::Result<(), EcdsaVerifyError> {
+        let mode = self.pka.cr.read().mode();
+        if !mode.is_ecdsaverif() {
+            return EcdsaVerifyError::mode(mode.bits());
+        }
+        let sr = self.pka.sr.read();
+        if sr.addrerrf().bit_is_set() {
+            self.clear_all_flags();
+            Err(nb::Error::Other(EcdsaVerifyError::Address))
+        } else if sr.ramerrf().bit_is_set() {
+            self.clear_all_flags();
+            Err(nb::Error::Other(EcdsaVerifyError::Ram))
+        } else if sr.procendf().is_in_progress() {
+            Err(nb::Error::WouldBlock)
+        } else {
+            self.clear_all_flags();
+
+            let result: u32 = unsafe { read_volatile(ECDSA_VERIFY_OUT as *const u32) };
+            EcdsaVerifyError::from_raw(result)
+        }
+    }
+
+    // start an operation and poll for completion
+    fn process(&mut self, opcode: PkaOpcode) -> Result<(), ArithmeticError> {
+        self.start_process(opcode);
+        loop {
+            let sr = self.pka.sr.read();
+            if sr.addrerrf().bit_is_set() {
+                self.clear_all_flags();
+                return Err(ArithmeticError::Address);
+            } else if sr.ramerrf().bit_is_set() {
+                self.clear_all_flags();
+                return Err(ArithmeticError::Ram);
+            } else if !sr.procendf().is_in_progress() {
+                self.clear_all_flags();
+                return Ok(());
+            }
+        }
+    }
+
+    // operation with two operands and an optional modulus
+    fn arith<const SIZE: usize>(
+        &mut self,
+        opcode: PkaOpcode,
+        a: &[u32; SIZE],
+        b: &[u32; SIZE],
+        modulus: Option<&[u32; SIZE]>,
+    ) -> Result<(), ArithmeticError> {
+        let op_length: u32 = (SIZE * size_of::<u32>() * 8) as u32;
+        self.arith_len(opcode, op_length, a, b, modulus)
+    }
+
+    fn arith_len<const SIZE: usize>(
+        &mut self,
+        opcode: PkaOpcode,
+        op_length: u32,
+        a: &[u32; SIZE],
+        b: &[u32; SIZE],
+        modulus: Option<&[u32; SIZE]>,
+    ) -> Result<(), ArithmeticError> {
+        assert!(SIZE <= MAX_OPERAND_SIZE);
+
+        self.zero_ram();
+
+        unsafe {
+            write_volatile(ARITH_OP_LEN as *mut u32, op_length);
+            self.write_ram(ARITH_OP1, a);
+            self.write_ram(ARITH_OP2, b);
+            if let Some(modulus) = modulus {
+                self.write_ram(ARITH_OP3, modulus);
+            }
+        }
+        self.process(opcode)
+    }
+
+    /// Check if a point is on an elliptic curve.
+    ///
+    /// Public keys received from a peer should be checked before use,
+    /// using a point that is not on the curve can leak the private key
+    /// (invalid curve attack).
+    ///
+    /// This is the blocking point check method, equivalent to calling
+    /// [`point_on_curve_start`](Self::point_on_curve_start) then polling
+    /// [`point_on_curve_result`](Self::point_on_curve_result).
+    ///
+    /// ```no_run
+    /// # let mut pka = unsafe { stm32wl_hal::pka::Pka::steal() };
+    /// # let curve = stm32wl_hal::pka::curve::NIST_P256;
+    /// # let curve_pt_x: [u32; 8] = [0; 8];
+    /// # let curve_pt_y: [u32; 8] = [0; 8];
+    /// # let pub_key = stm32wl_hal::pka::EcdsaPublicKey { curve_pt_x: &curve_pt_x, curve_pt_y: &curve_pt_y };
+    /// // blocking
+    /// pka.point_on_curve(&curve, &pub_key)?;
+    ///
+    /// // non-blocking
+    /// pka.point_on_curve_start(&curve, &pub_key)?;
+    /// nb::block!(pka.point_on_curve_result())?;
+    /// # Ok::<(), stm32wl_hal::pka::PointCheckError>(())
+    /// ```
+    pub fn point_on_curve<const MODULUS_SIZE: usize, const PRIME_ORDER_SIZE: usize>(
+        &mut self,
+        curve: &EllipticCurve<MODULUS_SIZE, PRIME_ORDER_SIZE>,
+        pub_key: &EcdsaPublicKey<MODULUS_SIZE>,
+    ) -> Result<(), PointCheckError> {
+        self.point_on_curve_start(curve, pub_key)?;
+        nb::block!(self.point_on_curve_result())
+    }
+
+    /// Start a point check operation.
+    ///
+    /// This will enable all the PKA IRQs.
+    ///
+    /// The point check requires the Montgomery parameter of the curve
+    /// modulus, this method blocks while the Montgomery parameter is
+    /// computed before starting the point check.
+    ///
+    /// Use the [`point_on_curve_result`](Self::point_on_curve_result) method
+    /// to poll for completion, or to get the result in an interrupt handler.
+    pub fn point_on_curve_start<const MODULUS_SIZE: usize, const PRIME_ORDER_SIZE: usize>(
+        &mut self,
+        curve: &EllipticCurve<MODULUS_SIZE, PRIME_ORDER_SIZE>,
+        pub_key: &EcdsaPublicKey<MODULUS_SIZE>,
+    ) -> Result<(), PointCheckError> {
+        let mut r2: [u32; MODULUS_SIZE] = [0; MODULUS_SIZE];
+        self.montgomery_param(&curve.modulus, &mut r2)?;
+
+        self.zero_ram();
+        let p_length: u32 = bit_len(&curve.modulus);
+
+        unsafe {
+            write_volatile(POINT_CHECK_P_LEN as *mut u32, p_length);
+            write_volatile(POINT_CHECK_A_SIGN as *mut u32, curve.coef_sign.into());
+            self.write_ram(POINT_CHECK_A, &curve.coef);
+            self.write_ram(POINT_CHECK_B, &curve.coef_b);
+            self.write_ram(POINT_CHECK_P, &curve.modulus);
+            self.write_ram(POINT_CHECK_X, pub_key.curve_pt_x);
+            self.write_ram(POINT_CHECK_Y, pub_key.curve_pt_y);
+            self.write_ram(POINT_CHECK_R2, &r2);
+        }
+        let sr = self.pka.sr.read();
+        if sr.addrerrf().bit_is_set() {
+            self.clear_all_flags();
+            Err(PointCheckError::Address)
+        } else if sr.ramerrf().bit_is_set() {
+            self.clear_all_flags();
+            Err(PointCheckError::Ram)
+        } else {
+            self.start_process(PkaOpcode::Point);
+            Ok(())
+        }
+    }
+
+    /// Get the result of a point check operation.
+    ///
+    /// Use this after starting a point check operation with
+    /// [`point_on_curve_start`](Self::point_on_curve_start).
+    pub fn point_on_curve_result(&mut self) -> nb::Result<(), PointCheckError> {
+        let mode: u8 = self.pka.cr.read().mode().bits();
+        if mode != PkaOpcode::Point.into() {
+            return PointCheckError::mode(mode);
+        }
+        let sr = self.pka.sr.read();
+        if sr.addrerrf().bit_is_set() {
+            self.clear_all_flags();
+            Err(nb::Error::Other(PointCheckError::Address))
+        } else if sr.ramerrf().bit_is_set() {
+            self.clear_all_flags();
+            Err(nb::Error::Other(PointCheckError::Ram))
+        } else if sr.procendf().is_in_progress() {
+            Err(nb::Error::WouldBlock)
+        } else {
+            self.clear_all_flags();
+
+            let result: u32 = unsafe { read_volatile(POINT_CHECK_OUT as *const u32) };
+            PointCheckError::from_raw(result)
+        }
+    }
+
+    /// ECC (Elliptic Curve Cryptography) scalar multiplication.
+    ///
+    /// Computes the curve point k × P.
+    ///
+    /// This is the blocking ECC scalar multiplication method, equivalent to
+    /// calling [`ecc_scalar_mul_start`](Self::ecc_scalar_mul_start) then
+    /// polling [`ecc_scalar_mul_result`](Self::ecc_scalar_mul_result).
+    ///
+    /// ```no_run
+    /// # let mut pka = unsafe { stm32wl_hal::pka::Pka::steal() };
+    /// use stm32wl_hal::pka::{curve::NIST_P256, EcdsaPublicKey};
+    ///
+    /// // derive the public key from a private key
+    /// let priv_key: [u32; 8] = [
+    ///     0x49ac8727, 0xcee87484, 0xfe6dfda5, 0x10238ad4, 0x11ace8fe, 0x593a8cb7, 0x0492d659,
+    ///     0xdb81802a,
+    /// ];
+    /// let base_point: EcdsaPublicKey<8> = EcdsaPublicKey {
+    ///     curve_pt_x: &NIST_P256.base_point_x,
+    ///     curve_pt_y: &NIST_P256.base_point_y,
+    /// };
+    /// let mut pub_x: [u32; 8] = [0; 8];
+    /// let mut pub_y: [u32; 8] = [0; 8];
+    ///
+    /// // blocking
+    /// pka.ecc_scalar_mul(&NIST_P256, &priv_key, &base_point, &mut pub_x, &mut pub_y)?;
+    ///
+    /// // non-blocking
+    /// pka.ecc_scalar_mul_start(&NIST_P256, &priv_key, &base_point)?;
+    /// nb::block!(pka.ecc_scalar_mul_result(&mut pub_x, &mut pub_y))?;
+    /// # Ok::<(), stm32wl_hal::pka::EccError>(())
+    /// ```
+    pub fn ecc_scalar_mul<const MODULUS_SIZE: usize, const PRIME_ORDER_SIZE: usize>(
+        &mut self,
+        curve: &EllipticCurve<MODULUS_SIZE, PRIME_ORDER_SIZE>,
+        k: &[u32; PRIME_ORDER_SIZE],
+        point: &EcdsaPublicKey<MODULUS_SIZE>,
+        out_x: &mut [u32; MODULUS_SIZE],
+        out_y: &mut [u32; MODULUS_SIZE],
+    ) -> Result<(), EccError> {
+        self.ecc_scalar_mul_start(curve, k, point)?;
+        nb::block!(self.ecc_scalar_mul_result(out_x, out_y))
+    }
+
+    /// Start an ECC scalar multiplication operation.
+    ///
+    /// This will enable all the PKA IRQs.
+    ///
+    /// Use the [`ecc_scalar_mul_result`](Self::ecc_scalar_mul_result) method
+    /// to poll for completion, or to get the result in an interrupt handler.
+    pub fn ecc_scalar_mul_start<const MODULUS_SIZE: usize, const PRIME_ORDER_SIZE: usize>(
+        &mut self,
+        curve: &EllipticCurve<MODULUS_SIZE, PRIME_ORDER_SIZE>,
+        k: &[u32; PRIME_ORDER_SIZE],
+        point: &EcdsaPublicKey<MODULUS_SIZE>,
+    ) -> Result<(), EccError> {
+        self.zero_ram();
+        let k_length: u32 = bit_len(&curve.prime_order);
+        let p_length: u32 = bit_len(&curve.modulus);
+
+        unsafe {
+            write_volatile(ECC_SCALAR_MUL_K_LEN as *mut u32, k_length);
+            write_volatile(ECC_SCALAR_MUL_P_LEN as *mut u32, p_length);
+            write_volatile(ECC_SCALAR_MUL_A_SIGN as *mut u32, curve.coef_sign.into());
+            self.write_ram(ECC_SCALAR_MUL_A, &curve.coef);
+            self.write_ram(ECC_SCALAR_MUL_P, &curve.modulus);
+            self.write_ram(ECC_SCALAR_MUL_K, k);
+            self.write_ram(ECC_SCALAR_MUL_X, point.curve_pt_x);
+            self.write_ram(ECC_SCALAR_MUL_Y, point.curve_pt_y);
+        }
+        let sr = self.pka.sr.read();
+        if sr.addrerrf().bit_is_set() {
+            self.clear_all_flags();
+            self.zero_ram();
+            Err(EccError::Address)
+        } else if sr.ramerrf().bit_is_set() {
+            self.clear_all_flags();
+            self.zero_ram();
+            Err(EccError::Ram)
+        } else {
+            self.start_process(PkaOpcode::MontgomeryParameterEcc);
+            Ok(())
+        }
+    }
+
+    /// Get the result of an ECC scalar multiplication operation.
+    ///
+    /// Use this after starting an ECC scalar multiplication operation with
+    /// [`ecc_scalar_mul_start`](Self::ecc_scalar_mul_start).
+    pub fn ecc_scalar_mul_result<const MODULUS_SIZE: usize>(
+        &mut self,
+        out_x: &mut [u32; MODULUS_SIZE],
+        out_y: &mut [u32; MODULUS_SIZE],
+    ) -> nb::Result<(), EccError> {
+        let mode: u8 = self.pka.cr.read().mode().bits();
+        if mode != PkaOpcode::MontgomeryParameterEcc.into() {
+            return EccError::mode(mode);
+        }
+        let sr = self.pka.sr.read();
+        if sr.addrerrf().bit_is_set() {
+            self.clear_all_flags();
+            self.zero_ram();
+            Err(nb::Error::Other(EccError::Address))
+        } else if sr.ramerrf().bit_is_set() {
+            self.clear_all_flags();
+            self.zero_ram();
+            Err(nb::Error::Other(EccError::Ram))
+        } else if sr.procendf().is_in_progress() {
+            Err(nb::Error::WouldBlock)
+        } else {
+            self.clear_all_flags();
+
+            unsafe {
+                self.read_ram(ECC_SCALAR_MUL_OUT_X, out_x);
+                self.read_ram(ECC_SCALAR_MUL_OUT_Y, out_y);
+            }
+            self.zero_ram();
+            Ok(())
+        }
+    }
+
+    /// ECDH (Elliptic Curve Diffie-Hellman) shared secret computation.
+    ///
+    /// Computes the x coordinate of d × Q, where d is the local private key
+    /// and Q is the public key of the peer.
+    ///
+    /// The peer public key is checked with
+    /// [`point_on_curve`](Self::point_on_curve) before it is used,
+    /// [`EccError::NotOnCurve`] is returned for invalid keys.
+    ///
+    /// The shared secret should be passed through a key derivation function
+    /// before use as a symmetric key.
+    ///
+    /// The PKA RAM is zeroed after the computation to avoid leaking the
+    /// private key and the shared secret.
+    /// The shared secret is written to a [`Secret`], which is zeroed when it
+    /// goes out of scope.
+    ///
+    /// ```no_run
+    /// # let mut pka = unsafe { stm32wl_hal::pka::Pka::steal() };
+    /// # let peer_x: [u32; 8] = [0; 8];
+    /// # let peer_y: [u32; 8] = [0; 8];
+    /// use stm32wl_hal::pka::{curve::NIST_P256, EcdsaPublicKey, Secret};
+    ///
+    /// let priv_key: [u32; 8] = [
+    ///     0x49ac8727, 0xcee87484, 0xfe6dfda5, 0x10238ad4, 0x11ace8fe, 0x593a8cb7, 0x0492d659,
+    ///     0xdb81802a,
+    /// ];
+    /// let peer_pub_key: EcdsaPublicKey<8> = EcdsaPublicKey {
+    ///     curve_pt_x: &peer_x,
+    ///     curve_pt_y: &peer_y,
+    /// };
+    /// let mut shared_secret: Secret<8> = Secret::new([0; 8]);
+    /// pka.ecdh_shared_secret(&NIST_P256, &priv_key, &peer_pub_key, &mut shared_secret)?;
+    /// # Ok::<(), stm32wl_hal::pka::EccError>(())
+    /// ```
+    pub fn ecdh_shared_secret<const MODULUS_SIZE: usize, const PRIME_ORDER_SIZE: usize>(
+        &mut self,
+        curve: &EllipticCurve<MODULUS_SIZE, PRIME_ORDER_SIZE>,
+        priv_key: &[u32; PRIME_ORDER_SIZE],
+        peer_pub_key: &EcdsaPublicKey<MODULUS_SIZE>,
+        shared_secret: &mut Secret<MODULUS_SIZE>,
+    ) -> Result<(), EccError> {
+        self.point_on_curve(curve, peer_pub_key)?;
+
+        let mut out_y: Secret<MODULUS_SIZE> = Secret::new([0; MODULUS_SIZE]);
+        self.ecc_scalar_mul(
+            curve,
+            priv_key,
+            peer_pub_key,
+            shared_secret.expose_mut(),
+            out_y.expose_mut(),
+        )
+    }
+
+    /// Modular exponentiation.
+    ///
+    /// Computes base<sup>exponent</sup> mod modulus.
+    ///
+    /// This is the blocking modular exponentiation method, equivalent to
+    /// calling [`modular_exp_start`](Self::modular_exp_start) then polling
+    /// [`modular_exp_result`](Self::modular_exp_result).
+    ///
+    /// # Panics
+    ///
+    /// * `OPERAND_SIZE` or `EXPONENT_SIZE` is greater than
+    ///   [`MAX_OPERAND_SIZE`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut pka = unsafe { stm32wl_hal::pka::Pka::steal() };
+    /// let mut result: [u32; 1] = [0];
+    ///
+    /// // blocking
+    /// pka.modular_exp(&[4], &[13], &[497], &mut result)?;
+    /// assert_eq!(result, [445]);
+    ///
+    /// // non-blocking
+    /// pka.modular_exp_start(&[4], &[13], &[497])?;
+    /// nb::block!(pka.modular_exp_result(&mut result))?;
+    /// assert_eq!(result, [445]);
+    /// # Ok::<(), stm32wl_hal::pka::RsaError>(())
+    /// ```
+    pub fn modular_exp<const OPERAND_SIZE: usize, const EXPONENT_SIZE: usize>(
+        &mut self,
+        base: &[u32; OPERAND_SIZE],
+        exponent: &[u32; EXPONENT_SIZE],
+        modulus: &[u32; OPERAND_SIZE],
+        result: &mut [u32; OPERAND_SIZE],
+    ) -> Result<(), RsaError> {
+        self.modular_exp_start(base, exponent, modulus)?;
+        nb::block!(self.modular_exp_result(result))
+    }
+
+    /// Start a modular exponentiation operation.
+    ///
+    /// This will enable all the PKA IRQs.
+    ///
+    /// Use the [`modular_exp_result`](Self::modular_exp_result) method
+    /// to poll for completion, or to get the result in an interrupt handler.
+    ///
+    /// # Panics
+    ///
+    /// * `OPERAND_SIZE` or `EXPONENT_SIZE` is greater than
+    ///   [`MAX_OPERAND_SIZE`].
+    pub fn modular_exp_start<const OPERAND_SIZE: usize, const EXPONENT_SIZE: usize>(
+        &mut self,
+        base: &[u32; OPERAND_SIZE],
+        exponent: &[u32; EXPONENT_SIZE],
+        modulus: &[u32; OPERAND_SIZE],
+    ) -> Result<(), RsaError> {
+        assert!(OPERAND_SIZE <= MAX_OPERAND_SIZE);
+        assert!(EXPONENT_SIZE <= MAX_OPERAND_SIZE);
+
+        self.zero_ram();
+        let e_length: u32 = (EXPONENT_SIZE * size_of::<u32>() * 8) as u32;
+        let n_length: u32 = bit_len(modulus);
+
+        unsafe {
+            write_volatile(MODULAR_EXP_E_LEN as *mut u32, e_length);
+            write_volatile(MODULAR_EXP_N_LEN as *mut u32, n_length);
+            self.write_ram(MODULAR_EXP_A, base);
+            self.write_ram(MODULAR_EXP_E, exponent);
+            self.write_ram(MODULAR_EXP_N, modulus);
+        }
+        let sr = self.pka.sr.read();
+        if sr.addrerrf().bit_is_set() {
+            self.clear_all_flags();
+            self.zero_ram();
+            Err(RsaError::Address)
+        } else if sr.ramerrf().bit_is_set() {
+            self.clear_all_flags();
+            self.zero_ram();
+            Err(RsaError::Ram)
+        } else {
+            self.start_process(PkaOpcode::MontgomeryParameterExponentiation);
+            Ok(())
+        }
+    }
+
+    /// Get the result of a modular exponentiation operation.
+    ///
+    /// Use this after starting a modular exponentiation operation with
+    /// [`modular_exp_start`](Self::modular_exp_start), or an RSA public key
+    /// operation with [`rsa_public_start`](Self::rsa_public_start).
+    pub fn modular_exp_result<const OPERAND_SIZE: usize>(
+        &mut self,
+        result: &mut [u32; OPERAND_SIZE],
+    ) -> nb::Result<(), RsaError> {
+        let mode: u8 = self.pka.cr.read().mode().bits();
+        if mode != PkaOpcode::MontgomeryParameterExponentiation.into() {
+            return RsaError::mode(mode);
+        }
+        let sr = self.pka.sr.read();
+        if sr.addrerrf().bit_is_set() {
+            self.clear_all_flags();
+            self.zero_ram();
+            Err(nb::Error::Other(RsaError::Address))
+        } else if sr.ramerrf().bit_is_set() {
+            self.clear_all_flags();
+            self.zero_ram();
+            Err(nb::Error::Other(RsaError::Ram))
+        } else if sr.procendf().is_in_progress() {
+            Err(nb::Error::WouldBlock)
+        } else {
+            self.clear_all_flags();
+
+            unsafe { self.read_ram(MODULAR_EXP_OUT, result) };
+            self.zero_ram();
+            Ok(())
+        }
+    }
+
+    /// RSA public key operation.
+    ///
+    /// Computes input<sup>e</sup> mod n, this is the RSA encryption and the
+    /// raw RSA signature verification primitive.
+    ///
+    /// This is the blocking method, equivalent to calling
+    /// [`rsa_public_start`](Self::rsa_public_start) then polling
+    /// [`modular_exp_result`](Self::modular_exp_result).
+    ///
+    /// # Panics
+    ///
+    /// * `MODULUS_SIZE` is greater than [`MAX_OPERAND_SIZE`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut pka = unsafe { stm32wl_hal::pka::Pka::steal() };
+    /// # let modulus: [u32; 64] = [0; 64];
+    /// # let signature: [u32; 64] = [0; 64];
+    /// use stm32wl_hal::pka::RsaPublicKey;
+    ///
+    /// let key: RsaPublicKey<64> = RsaPublicKey {
+    ///     modulus: &modulus,
+    ///     exponent: 65537,
+    /// };
+    ///
+    /// let mut encoded_msg: [u32; 64] = [0; 64];
+    /// pka.rsa_public(&key, &signature, &mut encoded_msg)?;
+    /// # Ok::<(), stm32wl_hal::pka::RsaError>(())
+    /// ```
+    pub fn rsa_public<const MODULUS_SIZE: usize>(
+        &mut self,
+        key: &RsaPublicKey<MODULUS_SIZE>,
+        input: &[u32; MODULUS_SIZE],
+        output: &mut [u32; MODULUS_SIZE],
+    ) -> Result<(), RsaError> {
+        self.rsa_public_start(key, input)?;
+        nb::block!(self.modular_exp_result(output))
+    }
+
+    /// Start an RSA public key operation.
+    ///
+    /// This will enable all the PKA IRQs.
+    ///
+    /// Use the [`modular_exp_result`](Self::modular_exp_result) method
+    /// to poll for completion, or to get the result in an interrupt handler.
+    ///
+    /// # Panics
+    ///
+    /// * `MODULUS_SIZE` is greater than [`MAX_OPERAND_SIZE`].
+    pub fn rsa_public_start<const MODULUS_SIZE: usize>(
+        &mut self,
+        key: &RsaPublicKey<MODULUS_SIZE>,
+        input: &[u32; MODULUS_SIZE],
+    ) -> Result<(), RsaError> {
+        self.modular_exp_start(input, &[key.exponent], key.modulus)
+    }
+
+    /// RSASSA-PKCS1-v1_5 signature verification with a SHA-256 hash.
+    ///
+    /// Returns [`RsaError::Invalid`] if the signature does not match the
+    /// hash.
+    ///
+    /// The hash is in the same big-endian word order as the ECDSA hash input.
+    ///
+    /// # Panics
+    ///
+    /// * `MODULUS_SIZE` is less than 16 (512-bits), or greater than
+    ///   [`MAX_OPERAND_SIZE`].
+    ///
+    /// # Example
+    ///
+    /// Verify an RSA-2048 signature.
+    ///
+    /// ```no_run
+    /// # let mut pka = unsafe { stm32wl_hal::pka::Pka::steal() };
+    /// # let modulus: [u32; 64] = [0; 64];
+    /// # let signature: [u32; 64] = [0; 64];
+    /// # let hash: [u32; 8] = [0; 8];
+    /// use stm32wl_hal::pka::RsaPublicKey;
+    ///
+    /// let key: RsaPublicKey<64> = RsaPublicKey {
+    ///     modulus: &modulus,
+    ///     exponent: 65537,
+    /// };
+    ///
+    /// pka.rsa_pkcs1v15_sha256_verify(&key, &signature, &hash)?;
+    /// # Ok::<(), stm32wl_hal::pka::RsaError>(())
+    /// ```
+    pub fn rsa_pkcs1v15_sha256_verify<const MODULUS_SIZE: usize>(
+        &mut self,
+        key: &RsaPublicKey<MODULUS_SIZE>,
+        signature: &[u32; MODULUS_SIZE],
+        hash: &[u32; 8],
+    ) -> Result<(), RsaError> {
+        assert!(MODULUS_SIZE >= 16);
+        let mut encoded_msg: [u32; MODULUS_SIZE] = [0; MODULUS_SIZE];
+        self.rsa_public(key, signature, &mut encoded_msg)?;
+
+        let len: usize = MODULUS_SIZE * size_of::<u32>();
+        let valid: bool = encoded_msg.iter().enumerate().all(|(idx, word)| {
+            let mut expected: [u8; 4] = [0; 4];
+            expected.iter_mut().enumerate().for_each(|(n, byte)| {
+                *byte = pkcs1v15_sha256_byte(idx * 4 + n, len, hash);
+            });
+            u32::from_be_bytes(expected) == *word
+        });
+
+        if valid {
+            Ok(())
+        } else {
+            Err(RsaError::Invalid)
+        }
+    }
+
+    /// RSA private key operation with the Chinese remainder theorem (CRT).
+    ///
+    /// Computes input<sup>d</sup> mod n, this is the RSA decryption and the
+    /// raw RSA signing primitive.
+    ///
+    /// The output is written to a [`Secret`], a decrypted message must not
+    /// be left in memory.
+    /// Use [`Secret::expose`] to read a signature.
+    ///
+    /// This is the blocking method, equivalent to calling
+    /// [`rsa_crt_start`](Self::rsa_crt_start) then polling
+    /// [`rsa_crt_result`](Self::rsa_crt_result).
+    ///
+    /// # Panics
+    ///
+    /// * `MODULUS_SIZE` is not twice `PRIME_SIZE`.
+    /// * `MODULUS_SIZE` is greater than [`MAX_OPERAND_SIZE`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut pka = unsafe { stm32wl_hal::pka::Pka::steal() };
+    /// # let p: [u32; 32] = [0; 32];
+    /// # let q: [u32; 32] = [0; 32];
+    /// # let dp: [u32; 32] = [0; 32];
+    /// # let dq: [u32; 32] = [0; 32];
+    /// # let qinv: [u32; 32] = [0; 32];
+    /// # let encoded_msg: [u32; 64] = [0; 64];
+    /// use stm32wl_hal::pka::{RsaCrtKey, Secret};
+    ///
+    /// let key: RsaCrtKey<32> = RsaCrtKey {
+    ///     prime_p: &p,
+    ///     prime_q: &q,
+    ///     dp: &dp,
+    ///     dq: &dq,
+    ///     qinv: &qinv,
+    /// };
+    ///
+    /// let mut signature: Secret<64> = Secret::new([0; 64]);
+    ///
+    /// // blocking
+    /// pka.rsa_crt(&key, &encoded_msg, &mut signature)?;
+    ///
+    /// // non-blocking
+    /// pka.rsa_crt_start(&key, &encoded_msg)?;
+    /// nb::block!(pka.rsa_crt_result(&mut signature))?;
+    /// # Ok::<(), stm32wl_hal::pka::RsaError>(())
+    /// ```
+    pub fn rsa_crt<const MODULUS_SIZE: usize, const PRIME_SIZE: usize>(
+        &mut self,
+        key: &RsaCrtKey<PRIME_SIZE>,
+        input: &[u32; MODULUS_SIZE],
+        output: &mut Secret<MODULUS_SIZE>,
+    ) -> Result<(), RsaError> {
+        self.rsa_crt_start(key, input)?;
+        nb::block!(self.rsa_crt_result(output))
+    }
+
+    /// Start an RSA CRT exponentiation operation.
+    ///
+    /// This will enable all the PKA IRQs.
+    ///
+    /// Use the [`rsa_crt_result`](Self::rsa_crt_result) method
+    /// to poll for completion, or to get the result in an interrupt handler.
+    ///
+    /// # Panics
+    ///
+    /// * `MODULUS_SIZE` is not twice `PRIME_SIZE`.
+    /// * `MODULUS_SIZE` is greater than [`MAX_OPERAND_SIZE`].
+    pub fn rsa_crt_start<const MODULUS_SIZE: usize, const PRIME_SIZE: usize>(
+        &mut self,
+        key: &RsaCrtKey<PRIME_SIZE>,
+        input: &[u32; MODULUS_SIZE],
+    ) -> Result<(), RsaError> {
+        assert_eq!(MODULUS_SIZE, PRIME_SIZE * 2);
+        assert!(MODULUS_SIZE <= MAX_OPERAND_SIZE);
+
+        self.zero_ram();
+        let n_length: u32 = (MODULUS_SIZE * size_of::<u32>() * 8) as u32;
+
+        unsafe {
+            write_volatile(RSA_CRT_N_LEN as *mut u32, n_length);
+            self.write_ram(RSA_CRT_DP, key.dp);
+            self.write_ram(RSA_CRT_DQ, key.dq);
+            self.write_ram(RSA_CRT_QINV, key.qinv);
+            self.write_ram(RSA_CRT_P, key.prime_p);
+            self.write_ram(RSA_CRT_Q, key.prime_q);
+            self.write_ram(RSA_CRT_A, input);
+        }
+        let sr = self.pka.sr.read();
+        if sr.addrerrf().bit_is_set() {
+            self.clear_all_flags();
+            self.zero_ram();
+            Err(RsaError::Address)
+        } else if sr.ramerrf().bit_is_set() {
+            self.clear_all_flags();
+            self.zero_ram();
+            Err(RsaError::Ram)
+        } else {
+            self.start_process(PkaOpcode::RsaCrt);
+            Ok(())
+        }
+    }
+
+    /// Get the result of an RSA CRT exponentiation operation.
+    ///
+    /// Use this after starting an RSA CRT exponentiation operation with
+    /// [`rsa_crt_start`](Self::rsa_crt_start).
+    pub fn rsa_crt_result<const MODULUS_SIZE: usize>(
+        &mut self,
+        output: &mut Secret<MODULUS_SIZE>,
+    ) -> nb::Result<(), RsaError> {
+        let mode: u8 = self.pka.cr.read().mode().bits();
+        if mode != PkaOpcode::RsaCrt.into() {
+            return RsaError::mode(mode);
+        }
+        let sr = self.pka.sr.read();
+        if sr.addrerrf().bit_is_set() {
+            self.clear_all_flags();
+            self.zero_ram();
+            Err(nb::Error::Other(RsaError::Address))
+        } else if sr.ramerrf().bit_is_set() {
+            self.clear_all_flags();
+            self.zero_ram();
+            Err(nb::Error::Other(RsaError::Ram))
+        } else if sr.procendf().is_in_progress() {
+            Err(nb::Error::WouldBlock)
+        } else {
+            self.clear_all_flags();
+
+            unsafe { self.read_ram(RSA_CRT_OUT, output.expose_mut()) };
+            self.zero_ram();
+            Ok(())
+        }
+    }
+
+    /// Arithmetic addition.
+    ///
+    /// Computes a + b, returning the carry.
+    ///
+    /// # Panics
+    ///
+    /// * `SIZE` is greater than [`MAX_OPERAND_SIZE`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut pka = unsafe { stm32wl_hal::pka::Pka::steal() };
+    /// let mut sum: [u32; 2] = [0; 2];
+    /// let carry: bool = pka.arith_add(&[0, 0xFFFF_FFFF], &[0, 1], &mut sum)?;
+    /// assert_eq!(sum, [1, 0]);
+    /// assert!(!carry);
+    /// # Ok::<(), stm32wl_hal::pka::ArithmeticError>(())
+    /// ```
+    pub fn arith_add<const SIZE: usize>(
+        &mut self,
+        a: &[u32; SIZE],
+        b: &[u32; SIZE],
+        result: &mut [u32; SIZE],
+    ) -> Result<bool, ArithmeticError> {
+        self.arith(PkaOpcode::ArithmeticAdd, a, b, None)?;
+        let carry: u32 = unsafe {
+            self.read_ram(ARITH_OUT, result);
+            read_volatile((ARITH_OUT + SIZE * size_of::<u32>()) as *const u32)
+        };
+        Ok(carry != 0)
+    }
+
+    /// Arithmetic subtraction.
+    ///
+    /// Computes a - b.
+    ///
+    /// `a` must be greater than or equal to `b`, use
+    /// [`arith_cmp`](Self::arith_cmp) to check.
+    ///
+    /// # Panics
+    ///
+    /// * `SIZE` is greater than [`MAX_OPERAND_SIZE`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut pka = unsafe { stm32wl_hal::pka::Pka::steal() };
+    /// let mut diff: [u32; 2] = [0; 2];
+    /// pka.arith_sub(&[1, 0], &[0, 1], &mut diff)?;
+    /// assert_eq!(diff, [0, 0xFFFF_FFFF]);
+    /// # Ok::<(), stm32wl_hal::pka::ArithmeticError>(())
+    /// ```
+    pub fn arith_sub<const SIZE: usize>(
+        &mut self,
+        a: &[u32; SIZE],
+        b: &[u32; SIZE],
+        result: &mut [u32; SIZE],
+    ) -> Result<(), ArithmeticError> {
+        self.arith(PkaOpcode::ArithmeticSub, a, b, None)?;
+        unsafe { self.read_ram(ARITH_OUT, result) };
+        Ok(())
+    }
+
+    /// Arithmetic multiplication.
+    ///
+    /// Computes a × b.
+    ///
+    /// # Panics
+    ///
+    /// * `RESULT_SIZE` is not twice `SIZE`.
+    /// * `SIZE` is greater than [`MAX_OPERAND_SIZE`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut pka = unsafe { stm32wl_hal::pka::Pka::steal() };
+    /// let mut product: [u32; 2] = [0; 2];
+    /// pka.arith_mul(&[0xFFFF_FFFF], &[0xFFFF_FFFF], &mut product)?;
+    /// assert_eq!(product, [0xFFFF_FFFE, 0x0000_0001]);
+    /// # Ok::<(), stm32wl_hal::pka::ArithmeticError>(())
+    /// ```
+    pub fn arith_mul<const SIZE: usize, const RESULT_SIZE: usize>(
+        &mut self,
+        a: &[u32; SIZE],
+        b: &[u32; SIZE],
+        result: &mut [u32; RESULT_SIZE],
+    ) -> Result<(), ArithmeticError> {
+        assert_eq!(RESULT_SIZE, SIZE * 2);
+        self.arith(PkaOpcode::ArithmeticMul, a, b, None)?;
+        unsafe { self.read_ram(ARITH_OUT, result) };
+        Ok(())
+    }
+
+    /// Arithmetic comparison.
+    ///
+    /// Returns the ordering of a compared to b.
+    ///
+    /// # Panics
+    ///
+    /// * `SIZE` is greater than [`MAX_OPERAND_SIZE`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut pka = unsafe { stm32wl_hal::pka::Pka::steal() };
+    /// use core::cmp::Ordering;
+    ///
+    /// assert_eq!(pka.arith_cmp(&[1, 0], &[0, 1])?, Ordering::Greater);
+    /// # Ok::<(), stm32wl_hal::pka::ArithmeticError>(())
+    /// ```
+    pub fn arith_cmp<const SIZE: usize>(
+        &mut self,
+        a: &[u32; SIZE],
+        b: &[u32; SIZE],
+    ) -> Result<Ordering, ArithmeticError> {
+        self.arith(PkaOpcode::ArithmeticCmp, a, b, None)?;
+        let raw: u32 = unsafe { read_volatile(ARITH_OUT as *const u32) };
+        Ok(match raw {
+            0 => Ordering::Equal,
+            1 => Ordering::Greater,
+            _ => Ordering::Less,
+        })
+    }
+
+    /// Modular reduction.
+    ///
+    /// Computes a mod n.
+    ///
+    /// # Panics
+    ///
+    /// * `SIZE` or `MODULUS_SIZE` is greater than [`MAX_OPERAND_SIZE`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut pka = unsafe { stm32wl_hal::pka::Pka::steal() };
+    /// let mut remainder: [u32; 1] = [0];
+    /// pka.mod_red(&[1, 0], &[497], &mut remainder)?;
+    /// assert_eq!(remainder, [151]);
+    /// # Ok::<(), stm32wl_hal::pka::ArithmeticError>(())
+    /// ```
+    pub fn mod_red<const SIZE: usize, const MODULUS_SIZE: usize>(
+        &mut self,
+        a: &[u32; SIZE],
+        modulus: &[u32; MODULUS_SIZE],
+        result: &mut [u32; MODULUS_SIZE],
+    ) -> Result<(), ArithmeticError> {
+        assert!(SIZE <= MAX_OPERAND_SIZE);
+        assert!(MODULUS_SIZE <= MAX_OPERAND_SIZE);
+
+        self.zero_ram();
+        let a_length: u32 = (SIZE * size_of::<u32>() * 8) as u32;
+        let n_length: u32 = (MODULUS_SIZE * size_of::<u32>() * 8) as u32;
+
+        unsafe {
+            write_volatile(MODULAR_RED_A_LEN as *mut u32, a_length);
+            write_volatile(MODULAR_RED_N_LEN as *mut u32, n_length);
+            self.write_ram(MODULAR_RED_A, a);
+            self.write_ram(MODULAR_RED_N, modulus);
+        }
+        self.process(PkaOpcode::ModularRed)?;
+        unsafe { self.read_ram(MODULAR_RED_OUT, result) };
+        Ok(())
+    }
+
+    /// Modular addition.
+    ///
+    /// Computes (a + b) mod n.
+    ///
+    /// `a` and `b` must be less than n.
+    ///
+    /// # Panics
+    ///
+    /// * `SIZE` is greater than [`MAX_OPERAND_SIZE`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut pka = unsafe { stm32wl_hal::pka::Pka::steal() };
+    /// let mut sum: [u32; 1] = [0];
+    /// pka.mod_add(&[400], &[200], &[497], &mut sum)?;
+    /// assert_eq!(sum, [103]);
+    /// # Ok::<(), stm32wl_hal::pka::ArithmeticError>(())
+    /// ```
+    pub fn mod_add<const SIZE: usize>(
+        &mut self,
+        a: &[u32; SIZE],
+        b: &[u32; SIZE],
+        modulus: &[u32; SIZE],
+        result: &mut [u32; SIZE],
+    ) -> Result<(), ArithmeticError> {
+        self.arith(PkaOpcode::ModularAdd, a, b, Some(modulus))?;
+        unsafe { self.read_ram(ARITH_OUT, result) };
+        Ok(())
+    }
+
+    /// Modular subtraction.
+    ///
+    /// Computes (a - b) mod n.
+    ///
+    /// `a` and `b` must be less than n.
+    ///
+    /// # Panics
+    ///
+    /// * `SIZE` is greater than [`MAX_OPERAND_SIZE`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut pka = unsafe { stm32wl_hal::pka::Pka::steal() };
+    /// let mut diff: [u32; 1] = [0];
+    /// pka.mod_sub(&[200], &[400], &[497], &mut diff)?;
+    /// assert_eq!(diff, [297]);
+    /// # Ok::<(), stm32wl_hal::pka::ArithmeticError>(())
+    /// ```
+    pub fn mod_sub<const SIZE: usize>(
+        &mut self,
+        a: &[u32; SIZE],
+        b: &[u32; SIZE],
+        modulus: &[u32; SIZE],
+        result: &mut [u32; SIZE],
+    ) -> Result<(), ArithmeticError> {
+        self.arith(PkaOpcode::ModularSub, a, b, Some(modulus))?;
+        unsafe { self.read_ram(ARITH_OUT, result) };
+        Ok(())
+    }
+
+    /// Modular inversion.
+    ///
+    /// Computes a<sup>-1</sup> mod n.
+    ///
+    /// `a` must be less than n, and `a` and n must be coprime.
+    ///
+    /// # Panics
+    ///
+    /// * `SIZE` is greater than [`MAX_OPERAND_SIZE`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut pka = unsafe { stm32wl_hal::pka::Pka::steal() };
+    /// let mut inv: [u32; 1] = [0];
+    /// pka.mod_inv(&[3], &[497], &mut inv)?;
+    /// assert_eq!(inv, [166]);
+    /// # Ok::<(), stm32wl_hal::pka::ArithmeticError>(())
+    /// ```
+    pub fn mod_inv<const SIZE: usize>(
+        &mut self,
+        a: &[u32; SIZE],
+        modulus: &[u32; SIZE],
+        result: &mut [u32; SIZE],
+    ) -> Result<(), ArithmeticError> {
+        self.arith(PkaOpcode::ModularInversion, a, modulus, None)?;
+        unsafe { self.read_ram(ARITH_OUT, result) };
+        Ok(())
+    }
+
+    /// Montgomery parameter computation.
+    ///
+    /// Computes the Montgomery parameter R² mod n used to convert operands
+    /// to and from the Montgomery domain for
+    /// [`montgomery_mul`](Self::montgomery_mul).
+    ///
+    /// The modulus must be odd.
+    ///
+    /// # Panics
+    ///
+    /// * `SIZE` is greater than [`MAX_OPERAND_SIZE`].
+    ///
+    /// # Example
+    ///
+    /// See [`montgomery_mul`](Self::montgomery_mul).
+    pub fn montgomery_param<const SIZE: usize>(
+        &mut self,
+        modulus: &[u32; SIZE],
+        r2: &mut [u32; SIZE],
+    ) -> Result<(), ArithmeticError> {
+        assert!(SIZE <= MAX_OPERAND_SIZE);
+
+        self.zero_ram();
+        let n_length: u32 = bit_len(modulus);
+
+        unsafe {
+            write_volatile(MONTGOMERY_PARAM_N_LEN as *mut u32, n_length);
+            self.write_ram(MONTGOMERY_PARAM_N, modulus);
+        }
+        self.process(PkaOpcode::MontgomeryParameter)?;
+        unsafe { self.read_ram(MONTGOMERY_PARAM_OUT, r2) };
+        Ok(())
+    }
+
+    /// Montgomery multiplication.
+    ///
+    /// Computes a × b × R<sup>-1</sup> mod n.
+    ///
+    /// `a` and `b` must be less than n, and the modulus must be odd.
+    ///
+    /// # Panics
+    ///
+    /// * `SIZE` is greater than [`MAX_OPERAND_SIZE`].
+    ///
+    /// # Example
+    ///
+    /// Modular multiplication with two Montgomery multiplications.
+    ///
+    /// ```no_run
+    /// # let mut pka = unsafe { stm32wl_hal::pka::Pka::steal() };
+    /// const N: [u32; 1] = [497];
+    ///
+    /// let mut r2: [u32; 1] = [0];
+    /// pka.montgomery_param(&N, &mut r2)?;
+    ///
+    /// // a × b × R⁻¹
+    /// let mut tmp: [u32; 1] = [0];
+    /// pka.montgomery_mul(&[400], &[200], &N, &mut tmp)?;
+    ///
+    /// // (a × b × R⁻¹) × R² × R⁻¹ = a × b
+    /// let mut product: [u32; 1] = [0];
+    /// pka.montgomery_mul(&tmp, &r2, &N, &mut product)?;
+    /// assert_eq!(product, [(400 * 200) % 497]);
+    /// # Ok::<(), stm32wl_hal::pka::ArithmeticError>(())
+    /// ```
+    pub fn montgomery_mul<const SIZE: usize>(
+        &mut self,
+        a: &[u32; SIZE],
+        b: &[u32; SIZE],
+        modulus: &[u32; SIZE],
+        result: &mut [u32; SIZE],
+    ) -> Result<(), ArithmeticError> {
+        // R depends on the operand length, this must match the length used
+        // by montgomery_param
+        let n_length: u32 = bit_len(modulus);
+        self.arith_len(PkaOpcode::MontgomeryMul, n_length, a, b, Some(modulus))?;
+        unsafe { self.read_ram(ARITH_OUT, result) };
+        Ok(())
+    }
+}
+
+#[cfg(feature = "async")]
+static WAKER: cortex_m::interrupt::Mutex<core::cell::RefCell<Option<core::task::Waker>>> =
+    cortex_m::interrupt::Mutex::new(core::cell::RefCell::new(None));
+
+// future polling a `_result` method, woken by `Pka::on_irq`
+#[cfg(feature = "async")]
+struct ResultFuture<'a, F> {
+    pka: &'a mut Pka,
+    result: F,
+}
+
+#[cfg(feature = "async")]
+impl<'a, T, E, F> core::future::Future for ResultFuture<'a, F>
+where
+    F: FnMut(&mut Pka) -> nb::Result<T, E> + Unpin,
+{
+    type Output = Result<T, E>;
+
+    fn poll(
+        self: core::pin::Pin<&mut Self>,
+        cx: &mut core::task::Context<'_>,
+    ) -> core::task::Poll<Self::Output> {
+        let this: &mut Self = self.get_mut();
+
+        // register the waker before checking the result to avoid missing
+        // an interrupt in-between
+        cortex_m::interrupt::free(|cs| {
+            WAKER.borrow(cs).replace(Some(cx.waker().clone()));
+        });
+
+        match (this.result)(this.pka) {
+            Err(nb::Error::WouldBlock) => core::task::Poll::Pending,
+            Err(nb::Error::Other(e)) => core::task::Poll::Ready(Err(e)),
+            Ok(x) => core::task::Poll::Ready(Ok(x)),
+        }
+    }
+}
+
+/// Asynchronous operations.
+///
+/// These require the PKA IRQ to be unmasked with [`Pka::unmask_irq`], and
+/// the PKA interrupt handler to call [`Pka::on_irq`].
+#[cfg(feature = "async")]
+#[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+impl Pka {
+    fn wait<T, E, F>(&mut self, result: F) -> ResultFuture<'_, F>
+    where
+        F: FnMut(&mut Pka) -> nb::Result<T, E> + Unpin,
+    {
+        ResultFuture { pka: self, result }
+    }
+
+    /// Asynchronous ECDSA signing.
+    ///
+    /// See [`ecdsa_sign`](Self::ecdsa_sign) for details.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn doctest() -> Result<(), stm32wl_hal::pka::EcdsaSignError> {
+    /// # let mut pka = unsafe { stm32wl_hal::pka::Pka::steal() };
+    /// # let nonce: [u32; 8] = [0; 8];
+    /// # let priv_key: [u32; 8] = [0; 8];
+    /// # let hash: [u32; 8] = [0; 8];
+    /// use stm32wl_hal::pka::curve::NIST_P256;
+    ///
+    /// let mut r_sign: [u32; 8] = [0; 8];
+    /// let mut s_sign: [u32; 8] = [0; 8];
+    /// pka.ecdsa_sign_async(&NIST_P256, &nonce, &priv_key, &hash, &mut r_sign, &mut s_sign)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn ecdsa_sign_async<const MODULUS_SIZE: usize, const PRIME_ORDER_SIZE: usize>(
+        &mut self,
+        curve: &EllipticCurve<MODULUS_SIZE, PRIME_ORDER_SIZE>,
+        nonce: &[u32; PRIME_ORDER_SIZE],
+        priv_key: &[u32; PRIME_ORDER_SIZE],
+        hash: &[u32; PRIME_ORDER_SIZE],
+        r_sign: &mut [u32; MODULUS_SIZE],
+        s_sign: &mut [u32; MODULUS_SIZE],
+    ) -> Result<(), EcdsaSignError> {
+        self.ecdsa_sign_start(curve, nonce, priv_key, hash)?;
+        self.wait(|pka| pka.ecdsa_sign_result(r_sign, s_sign)).await
+    }
+
+    /// Asynchronous ECDSA verification.
+    ///
+    /// See [`ecdsa_verify`](Self::ecdsa_verify) for details.
+    pub async fn ecdsa_verify_async<const MODULUS_SIZE: usize, const PRIME_ORDER_SIZE: usize>(
+        &mut self,
+        curve: &EllipticCurve<MODULUS_SIZE, PRIME_ORDER_SIZE>,
+        sig: &EcdsaSignature<'_, MODULUS_SIZE>,
+        pub_key: &EcdsaPublicKey<'_, MODULUS_SIZE>,
+        hash: &[u32; PRIME_ORDER_SIZE],
+    ) -> Result<(), EcdsaVerifyError> {
+        self.ecdsa_verify_start(curve, sig, pub_key, hash)?;
+        self.wait(|pka| pka.ecdsa_verify_result()).await
+    }
+
+    /// Asynchronous ECC scalar multiplication.
+    ///
+    /// See [`ecc_scalar_mul`](Self::ecc_scalar_mul) for details.
+    pub async fn ecc_scalar_mul_async<const MODULUS_SIZE: usize, const PRIME_ORDER_SIZE: usize>(
+        &mut self,
+        curve: &EllipticCurve<MODULUS_SIZE, PRIME_ORDER_SIZE>,
+        k: &[u32; PRIME_ORDER_SIZE],
+        point: &EcdsaPublicKey<'_, MODULUS_SIZE>,
+        out_x: &mut [u32; MODULUS_SIZE],
+        out_y: &mut [u32; MODULUS_SIZE],
+    ) -> Result<(), EccError> {
+        self.ecc_scalar_mul_start(curve, k, point)?;
+        self.wait(|pka| pka.ecc_scalar_mul_result(out_x, out_y))
+            .await
+    }
+
+    /// Asynchronous modular exponentiation.
+    ///
+    /// See [`modular_exp`](Self::modular_exp) for details.
+    pub async fn modular_exp_async<const OPERAND_SIZE: usize, const EXPONENT_SIZE: usize>(
+        &mut self,
+        base: &[u32; OPERAND_SIZE],
+        exponent: &[u32; EXPONENT_SIZE],
+        modulus: &[u32; OPERAND_SIZE],
+        result: &mut [u32; OPERAND_SIZE],
+    ) -> Result<(), RsaError> {
+        self.modular_exp_start(base, exponent, modulus)?;
+        self.wait(|pka| pka.modular_exp_result(result)).await
+    }
+
+    /// Asynchronous RSA private key operation.
+    ///
+    /// See [`rsa_crt`](Self::rsa_crt) for details.
+    pub async fn rsa_crt_async<const MODULUS_SIZE: usize, const PRIME_SIZE: usize>(
+        &mut self,
+        key: &RsaCrtKey<'_, PRIME_SIZE>,
+        input: &[u32; MODULUS_SIZE],
+        output: &mut Secret<MODULUS_SIZE>,
+    ) -> Result<(), RsaError> {
+        self.rsa_crt_start(key, input)?;
+        self.wait(|pka| pka.rsa_crt_result(output)).await
+    }
+}
+
+/// Sign bit for ECDSA coefficient signing and verification.
+#[repr(u32)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Sign {
+    /// Positive.
+    Pos = 0,
+    /// Negative.
+    Neg = 1,
+}
+
+impl From<Sign> for u32 {
+    fn from(s: Sign) -> Self {
+        s as u32
+    }
+}
+
+/// ECDSA signature.
+#[derive(Debug, PartialEq, Eq)]
+pub struct EcdsaSignature<'a, const MODULUS_SIZE: usize> {
+    /// Signature part r.
+    pub r_sign: &'a [u32; MODULUS_SIZE],
+    /// Signature part s.
+    pub s_sign: &'a [u32; MODULUS_SIZE],
+}
+
+#[cfg(feature = "defmt")]
+impl<'a, const MODULUS_SIZE: usize> defmt::Format for EcdsaSignature<'a, MODULUS_SIZE> {
+    fn format(&self, fmt: defmt::Formatter) {
+        defmt::write!(
+            fmt,
+            "EcdsaSignature {{ r_sign: {}, s_sign: {} }}",
+            self.r_sign.as_ref(),
+            self.s_sign.as_ref()
+        )
+    }
+}
+
+/// ECDSA public key.
+#[derive(Debug, PartialEq, Eq)]
+pub struct EcdsaPublicKey<'a, const MODULUS_SIZE: usize> {
+    /// Public-key curve point xQ.
+    pub curve_pt_x: &'a [u32; MODULUS_SIZE],
+    /// Public-key curve point yQ.
+    pub curve_pt_y: &'a [u32; MODULUS_SIZE],
+}
+
+/// RSA public key.
+#[derive(Debug, PartialEq, Eq)]
+pub struct RsaPublicKey<'a, const MODULUS_SIZE: usize> {
+    /// Modulus n.
+    pub modulus: &'a [u32; MODULUS_SIZE],
+    /// Public exponent e, commonly 65537.
+    pub exponent: u32,
+}
+
+/// RSA private key in Chinese remainder theorem (CRT) form.
+///
+/// `PRIME_SIZE` is half the size of the modulus.
+#[derive(PartialEq, Eq)]
+pub struct RsaCrtKey<'a, const PRIME_SIZE: usize> {
+    /// Prime factor p of the modulus.
+    pub prime_p: &'a [u32; PRIME_SIZE],
+    /// Prime factor q of the modulus.
+    pub prime_q: &'a [u32; PRIME_SIZE],
+    /// CRT exponent d mod (p - 1).
+    pub dp: &'a [u32; PRIME_SIZE],
+    /// CRT exponent d mod (q - 1).
+    pub dq: &'a [u32; PRIME_SIZE],
+    /// CRT coefficient q<sup>-1</sup> mod p.
+    pub qinv: &'a [u32; PRIME_SIZE],
+}
+
+impl<'a, const PRIME_SIZE: usize> core::fmt::Debug for RsaCrtKey<'a, PRIME_SIZE> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("RsaCrtKey { .. }")
+    }
+}
+
+/// Elliptic curve.
+///
+/// Used for ECDSA signing and verification, ECC scalar multiplication, and
+/// point checks.
+///
+/// Use one of the pre-defined curves in [`curve`], or [`EllipticCurve::new`]
+/// for other curves.
+/// Prefer [`EllipticCurve::new`] over a struct literal, it is not affected
+/// when fields are added.
+#[derive(Debug, PartialEq, Eq)]
+pub struct EllipticCurve<const MODULUS_SIZE: usize, const PRIME_ORDER_SIZE: usize> {
+    /// Curve coefficient a sign.
+    ///
+    /// **Note:** 0 for positive, 1 for negative.
+    pub coef_sign: Sign,
+    /// Curve coefficient |a|.
+    ///
+    /// **Note:** Absolute value, |a| < p.
+    pub coef: [u32; MODULUS_SIZE],
+    /// Curve coefficient b.
+    ///
+    /// **Note:** b < p.
+    /// Only used by the [point check](Pka::point_on_curve).
+    pub coef_b: [u32; MODULUS_SIZE],
+    /// Curve modulus value p.
+    ///
+    /// **Note:** Odd integer prime, 0 < p < 2<sup>640</sup>
+    pub modulus: [u32; MODULUS_SIZE],
+    /// Curve base point G coordinate x.
+    ///
+    /// **Note:** x < p
+    pub base_point_x: [u32; MODULUS_SIZE],
+    /// Curve base point G coordinate y.
+    ///
+    /// **Note:** y < p
+    pub base_point_y: [u32; MODULUS_SIZE],
+    /// Curve prime order n.
+    ///
+    /// **Note:** Integer prime.
+    pub prime_order: [u32; PRIME_ORDER_SIZE],
+}
+
+impl<const MODULUS_SIZE: usize, const PRIME_ORDER_SIZE: usize>
+    EllipticCurve<MODULUS_SIZE, PRIME_ORDER_SIZE>
+{
+    /// Create a new elliptic curve.
+    ///
+    /// The arguments are the struct fields, in the same order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stm32wl_hal::pka::{curve::NIST_P192, EllipticCurve};
+    ///
+    /// const P192: EllipticCurve<6, 6> = EllipticCurve::new(
+    ///     NIST_P192.coef_sign,
+    ///     NIST_P192.coef,
+    ///     NIST_P192.coef_b,
+    ///     NIST_P192.modulus,
+    ///     NIST_P192.base_point_x,
+    ///     NIST_P192.base_point_y,
+    ///     NIST_P192.prime_order,
+    /// );
+    /// assert_eq!(P192, NIST_P192);
+    /// ```
+    pub const fn new(
+        coef_sign: Sign,
+        coef: [u32; MODULUS_SIZE],
+        coef_b: [u32; MODULUS_SIZE],
+        modulus: [u32; MODULUS_SIZE],
+        base_point_x: [u32; MODULUS_SIZE],
+        base_point_y: [u32; MODULUS_SIZE],
+        prime_order: [u32; PRIME_ORDER_SIZE],
+    ) -> Self {
+        EllipticCurve {
+            coef_sign,
+            coef,
+            coef_b,
+            modulus,
+            base_point_x,
+            base_point_y,
+            prime_order,
+        }
+    }
+}
+
+/// Pre-defined elliptic curves.
+///
+/// The constants are checked by host tests, the base point is on the curve,
+/// and the modulus and prime order are prime.
+pub mod curve {
+    use super::{
+        EllipticCurve,
+        Sign::{Neg, Pos},
+    };
+
+    /// NIST P-192, also known as secp192r1 and prime192v1.
+    pub const NIST_P192: EllipticCurve<6, 6> = EllipticCurve {
+        coef_sign: Neg,
+        coef: [
+            0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000003,
+        ],
+        coef_b: NIST_P192_B,
+        modulus: [
+            0xffffffff, 0xffffffff, 0xffffffff, 0xfffffffe, 0xffffffff, 0xffffffff,
+        ],
+        base_point_x: [
+            0x188da80e, 0xb03090f6, 0x7cbf20eb, 0x43a18800, 0xf4ff0afd, 0x82ff1012,
+        ],
+        base_point_y: [
+            0x07192b95, 0xffc8da78, 0x631011ed, 0x6b24cdd5, 0x73f977a1, 0x1e794811,
+        ],
+        prime_order: [
+            0xffffffff, 0xffffffff, 0xffffffff, 0x99def836, 0x146bc9b1, 0xb4d22831,
+        ],
+    };
+
+    /// Coefficient b of [`NIST_P192`].
+    pub const NIST_P192_B: [u32; 6] = [
+        0x64210519, 0xe59c80e7, 0x0fa7e9ab, 0x72243049, 0xfeb8deec, 0xc146b9b1,
+    ];
+
+    /// NIST P-224, also known as secp224r1.
+    pub const NIST_P224: EllipticCurve<7, 7> = EllipticCurve {
+        coef_sign: Neg,
+        coef: [
+            0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000003,
+        ],
+        coef_b: [
+            0xb4050a85, 0x0c04b3ab, 0xf5413256, 0x5044b0b7, 0xd7bfd8ba, 0x270b3943, 0x2355ffb4,
+        ],
+        modulus: [
+            0xffffffff, 0xffffffff, 0xffffffff, 0xffffffff, 0x00000000, 0x00000000, 0x00000001,
+        ],
+        base_point_x: [
+            0xb70e0cbd, 0x6bb4bf7f, 0x321390b9, 0x4a03c1d3, 0x56c21122, 0x343280d6, 0x115c1d21,
+        ],
+        base_point_y: [
+            0xbd376388, 0xb5f723fb, 0x4c22dfe6, 0xcd4375a0, 0x5a074764, 0x44d58199, 0x85007e34,
+        ],
+        prime_order: [
+            0xffffffff, 0xffffffff, 0xffffffff, 0xffff16a2, 0xe0b8f03e, 0x13dd2945, 0x5c5c2a3d,
+        ],
+    };
+
+    /// NIST P-256, also known as secp256r1 and prime256v1.
+    pub const NIST_P256: EllipticCurve<8, 8> = EllipticCurve {
+        coef_sign: Neg,
+        coef: [
+            0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+            0x00000003,
+        ],
+        coef_b: [
+            0x5ac635d8, 0xaa3a93e7, 0xb3ebbd55, 0x769886bc, 0x651d06b0, 0xcc53b0f6, 0x3bce3c3e,
+            0x27d2604b,
+        ],
+        modulus: [
+            0xffffffff, 0x00000001, 0x00000000, 0x00000000, 0x00000000, 0xffffffff, 0xffffffff,
+            0xffffffff,
+        ],
+        base_point_x: [
+            0x6b17d1f2, 0xe12c4247, 0xf8bce6e5, 0x63a440f2, 0x77037d81, 0x2deb33a0, 0xf4a13945,
+            0xd898c296,
+        ],
+        base_point_y: [
+            0x4fe342e2, 0xfe1a7f9b, 0x8ee7eb4a, 0x7c0f9e16, 0x2bce3357, 0x6b315ece, 0xcbb64068,
+            0x37bf51f5,
+        ],
+        prime_order: [
+            0xffffffff, 0x00000000, 0xffffffff, 0xffffffff, 0xbce6faad, 0xa7179e84, 0xf3b9cac2,
+            0xfc632551,
+        ],
+    };
+
+    /// NIST P-384, also known as secp384r1.
+    pub const NIST_P384: EllipticCurve<12, 12> = EllipticCurve {
+        coef_sign: Neg,
+        coef: [
+            0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+            0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000003,
+        ],
+        coef_b: [
+            0xb3312fa7, 0xe23ee7e4, 0x988e056b, 0xe3f82d19, 0x181d9c6e, 0xfe814112, 0x0314088f,
+            0x5013875a, 0xc656398d, 0x8a2ed19d, 0x2a85c8ed, 0xd3ec2aef,
+        ],
+        modulus: [
+            0xffffffff, 0xffffffff, 0xffffffff, 0xffffffff, 0xffffffff, 0xffffffff, 0xffffffff,
+            0xfffffffe, 0xffffffff, 0x00000000, 0x00000000, 0xffffffff,
+        ],
+        base_point_x: [
+            0xaa87ca22, 0xbe8b0537, 0x8eb1c71e, 0xf320ad74, 0x6e1d3b62, 0x8ba79b98, 0x59f741e0,
+            0x82542a38, 0x5502f25d, 0xbf55296c, 0x3a545e38, 0x72760ab7,
+        ],
+        base_point_y: [
+            0x3617de4a, 0x96262c6f, 0x5d9e98bf, 0x9292dc29, 0xf8f41dbd, 0x289a147c, 0xe9da3113,
+            0xb5f0b8c0, 0x0a60b1ce, 0x1d7e819d, 0x7a431d7c, 0x90ea0e5f,
+        ],
+        prime_order: [
+            0xffffffff, 0xffffffff, 0xffffffff, 0xffffffff, 0xffffffff, 0xffffffff, 0xc7634d81,
+            0xf4372ddf, 0x581a0db2, 0x48b0a77a, 0xecec196a, 0xccc52973,
+        ],
+    };
+
+    /// NIST P-521, also known as secp521r1.
+    pub const NIST_P521: EllipticCurve<17, 17> = EllipticCurve {
+        coef_sign: Neg,
+        coef: [
+            0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+            0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+            0x00000000, 0x00000000, 0x00000003,
+        ],
+        coef_b: [
+            0x00000051, 0x953eb961, 0x8e1c9a1f, 0x929a21a0, 0xb68540ee, 0xa2da725b, 0x99b315f3,
+            0xb8b48991, 0x8ef109e1, 0x56193951, 0xec7e937b, 0x1652c0bd, 0x3bb1bf07, 0x3573df88,
+            0x3d2c34f1, 0xef451fd4, 0x6b503f00,
+        ],
+        modulus: [
+            0x000001ff, 0xffffffff, 0xffffffff, 0xffffffff, 0xffffffff, 0xffffffff, 0xffffffff,
+            0xffffffff, 0xffffffff, 0xffffffff, 0xffffffff, 0xffffffff, 0xffffffff, 0xffffffff,
+            0xffffffff, 0xffffffff, 0xffffffff,
+        ],
+        base_point_x: [
+            0x000000c6, 0x858e06b7, 0x0404e9cd, 0x9e3ecb66, 0x2395b442, 0x9c648139, 0x053fb521,
+            0xf828af60, 0x6b4d3dba, 0xa14b5e77, 0xefe75928, 0xfe1dc127, 0xa2ffa8de, 0x3348b3c1,
+            0x856a429b, 0xf97e7e31, 0xc2e5bd66,
+        ],
+        base_point_y: [
+            0x00000118, 0x39296a78, 0x9a3bc004, 0x5c8a5fb4, 0x2c7d1bd9, 0x98f54449, 0x579b4468,
+            0x17afbd17, 0x273e662c, 0x97ee7299, 0x5ef42640, 0xc550b901, 0x3fad0761, 0x353c7086,
+            0xa272c240, 0x88be9476, 0x9fd16650,
+        ],
+        prime_order: [
+            0x000001ff, 0xffffffff, 0xffffffff, 0xffffffff, 0xffffffff, 0xffffffff, 0xffffffff,
+            0xffffffff, 0xfffffffa, 0x51868783, 0xbf2f966b, 0x7fcc0148, 0xf709a5d0, 0x3bb5c9b8,
+            0x899c47ae, 0xbb6fb71e, 0x91386409,
+        ],
+    };
+
+    /// SEC 2 secp256k1.
+    pub const SECP256K1: EllipticCurve<8, 8> = EllipticCurve {
+        coef_sign: Pos,
+        coef: [
+            0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+            0x00000000,
+        ],
+        coef_b: [
+            0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+            0x00000007,
+        ],
+        modulus: [
+            0xffffffff, 0xffffffff, 0xffffffff, 0xffffffff, 0xffffffff, 0xffffffff, 0xfffffffe,
+            0xfffffc2f,
+        ],
+        base_point_x: [
+            0x79be667e, 0xf9dcbbac, 0x55a06295, 0xce870b07, 0x029bfcdb, 0x2dce28d9, 0x59f2815b,
+            0x16f81798,
+        ],
+        base_point_y: [
+            0x483ada77, 0x26a3c465, 0x5da4fbfc, 0x0e1108a8, 0xfd17b448, 0xa6855419, 0x9c47d08f,
+            0xfb10d4b8,
+        ],
+        prime_order: [
+            0xffffffff, 0xffffffff, 0xffffffff, 0xfffffffe, 0xbaaedce6, 0xaf48a03b, 0xbfd25e8c,
+            0xd0364141,
+        ],
+    };
+
+    /// Brainpool P224r1.
+    pub const P224R1: EllipticCurve<7, 7> = EllipticCurve {
+        coef_sign: Pos,
+        coef: [
+            0x68a5e62c, 0xa9ce6c1c, 0x299803a6, 0xc1530b51, 0x4e182ad8, 0xb0042a59, 0xcad29f43,
+        ],
+        coef_b: P224R1_B,
+        modulus: [
+            0xd7c134aa, 0x26436686, 0x2a183025, 0x75d1d787, 0xb09f0757, 0x97da89f5, 0x7ec8c0ff,
+        ],
+        base_point_x: [
+            0x0d9029ad, 0x2c7e5cf4, 0x340823b2, 0xa87dc68c, 0x9e4ce317, 0x4c1e6efd, 0xee12c07d,
+        ],
+        base_point_y: [
+            0x58aa56f7, 0x72c0726f, 0x24c6b89e, 0x4ecdac24, 0x354b9e99, 0xcaa3f6d3, 0x761402cd,
+        ],
+        prime_order: [
+            0xd7c134aa, 0x26436686, 0x2a183025, 0x75d0fb98, 0xd116bc4b, 0x6ddebca3, 0xa5a7939f,
+        ],
+    };
+
+    /// Coefficient b of [`P224R1`].
+    pub const P224R1_B: [u32; 7] = [
+        0x2580f63c, 0xcfe44138, 0x870713b1, 0xa92369e3, 0x3e2135d2, 0x66dbb372, 0x386c400b,
+    ];
+
+    /// Brainpool P256r1.
+    pub const P256R1: EllipticCurve<8, 8> = EllipticCurve {
+        coef_sign: Pos,
+        coef: [
+            0x7d5a0975, 0xfc2c3057, 0xeef67530, 0x417affe7, 0xfb8055c1, 0x26dc5c6c, 0xe94a4b44,
+            0xf330b5d9,
+        ],
+        coef_b: [
+            0x26dc5c6c, 0xe94a4b44, 0xf330b5d9, 0xbbd77cbf, 0x95841629, 0x5cf7e1ce, 0x6bccdc18,
+            0xff8c07b6,
+        ],
+        modulus: [
+            0xa9fb57db, 0xa1eea9bc, 0x3e660a90, 0x9d838d72, 0x6e3bf623, 0xd5262028, 0x2013481d,
+            0x1f6e5377,
+        ],
+        base_point_x: [
+            0x8bd2aeb9, 0xcb7e57cb, 0x2c4b482f, 0xfc81b7af, 0xb9de27e1, 0xe3bd23c2, 0x3a4453bd,
+            0x9ace3262,
+        ],
+        base_point_y: [
+            0x547ef835, 0xc3dac4fd, 0x97f8461a, 0x14611dc9, 0xc2774513, 0x2ded8e54, 0x5c1d54c7,
+            0x2f046997,
+        ],
+        prime_order: [
+            0xa9fb57db, 0xa1eea9bc, 0x3e660a90, 0x9d838d71, 0x8c397aa3, 0xb561a6f7, 0x901e0e82,
+            0x974856a7,
+        ],
+    };
+
+    /// Brainpool P384r1.
+    pub const P384R1: EllipticCurve<12, 12> = EllipticCurve {
+        coef_sign: Pos,
+        coef: [
+            0x7bc382c6, 0x3d8c150c, 0x3c72080a, 0xce05afa0, 0xc2bea28e, 0x4fb22787, 0x139165ef,
+            0xba91f90f, 0x8aa5814a, 0x503ad4eb, 0x04a8c7dd, 0x22ce2826,
+        ],
+        coef_b: [
+            0x04a8c7dd, 0x22ce2826, 0x8b39b554, 0x16f0447c, 0x2fb77de1, 0x07dcd2a6, 0x2e880ea5,
+            0x3eeb62d5, 0x7cb43902, 0x95dbc994, 0x3ab78696, 0xfa504c11,
+        ],
+        modulus: [
+            0x8cb91e82, 0xa3386d28, 0x0f5d6f7e, 0x50e641df, 0x152f7109, 0xed5456b4, 0x12b1da19,
+            0x7fb71123, 0xacd3a729, 0x901d1a71, 0x87470013, 0x3107ec53,
+        ],
+        base_point_x: [
+            0x1d1c64f0, 0x68cf45ff, 0xa2a63a81, 0xb7c13f6b, 0x8847a3e7, 0x7ef14fe3, 0xdb7fcafe,
+            0x0cbd10e8, 0xe826e034, 0x36d646aa, 0xef87b2e2, 0x47d4af1e,
+        ],
+        base_point_y: [
+            0x8abe1d75, 0x20f9c2a4, 0x5cb1eb8e, 0x95cfd552, 0x62b70b29, 0xfeec5864, 0xe19c054f,
+            0xf9912928, 0x0e464621, 0x77918111, 0x42820341, 0x263c5315,
+        ],
+        prime_order: [
+            0x8cb91e82, 0xa3386d28, 0x0f5d6f7e, 0x50e641df, 0x152f7109, 0xed5456b3, 0x1f166e6c,
+            0xac0425a7, 0xcf3ab6af, 0x6b7fc310, 0x3b883202, 0xe9046565,
+        ],
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use super::{curve::*, EllipticCurve, Sign};
+    use std::cmp::Ordering;
+
+    // minimal arbitrary precision arithmetic to check the curve constants,
+    // little-endian words without leading zeros
+    type Big = Vec<u32>;
+
+    fn big(be: &[u32]) -> Big {
+        let mut ret: Big = be.iter().rev().copied().collect();
+        trim(&mut ret);
+        ret
+    }
+
+    fn trim(a: &mut Big) {
+        while a.last() == Some(&0) {
+            a.pop();
+        }
+    }
+
+    fn cmp(a: &Big, b: &Big) -> Ordering {
+        a.len()
+            .cmp(&b.len())
+            .then_with(|| a.iter().rev().cmp(b.iter().rev()))
+    }
+
+    fn add(a: &Big, b: &Big) -> Big {
+        let mut ret: Big = Vec::with_capacity(a.len().max(b.len()) + 1);
+        let mut carry: u64 = 0;
+        for idx in 0..a.len().max(b.len()) {
+            let sum: u64 = carry
+                + u64::from(a.get(idx).copied().unwrap_or(0))
+                + u64::from(b.get(idx).copied().unwrap_or(0));
+            ret.push(sum as u32);
+            carry = sum >> 32;
+        }
+        ret.push(carry as u32);
+        trim(&mut ret);
+        ret
+    }
+
+    // a - b, a must be greater than or equal to b
+    fn sub(a: &Big, b: &Big) -> Big {
+        let mut ret: Big = Vec::with_capacity(a.len());
+        let mut borrow: i64 = 0;
+        for (idx, &dw) in a.iter().enumerate() {
+            let mut diff: i64 =
+                i64::from(dw) - i64::from(b.get(idx).copied().unwrap_or(0)) - borrow;
+            borrow = 0;
+            if diff < 0 {
+                diff += 1 << 32;
+                borrow = 1;
+            }
+            ret.push(diff as u32);
+        }
+        assert_eq!(borrow, 0);
+        trim(&mut ret);
+        ret
+    }
+
+    fn mul(a: &Big, b: &Big) -> Big {
+        let mut ret: Big = vec![0; a.len() + b.len()];
+        for (i, &x) in a.iter().enumerate() {
+            let mut carry: u64 = 0;
+            for (j, &y) in b.iter().enumerate() {
+                let acc: u64 = u64::from(x) * u64::from(y) + u64::from(ret[i + j]) + carry;
+                ret[i + j] = acc as u32;
+                carry = acc >> 32;
+            }
+            ret[i + b.len()] = carry as u32;
+        }
+        trim(&mut ret);
+        ret
+    }
+
+    // a mod m with binary long division
+    fn rem(a: &Big, m: &Big) -> Big {
+        let mut ret: Big = Vec::new();
+        for bit in (0..a.len() * 32).rev() {
+            let mut carry: u32 = (a[bit / 32] >> (bit % 32)) & 1;
+            for dw in ret.iter_mut() {
+                let next: u32 = *dw >> 31;
+                *dw = (*dw << 1) | carry;
+                carry = next;
+            }
+            ret.push(carry);
+            trim(&mut ret);
+            if cmp(&ret, m) != Ordering::Less {
+                ret = sub(&ret, m);
+            }
+        }
+        ret
+    }
+
+    fn pow_mod(base: &Big, exp: &Big, m: &Big) -> Big {
+        let mut ret: Big = vec![1];
+        for bit in (0..exp.len() * 32).rev() {
+            ret = rem(&mul(&ret, &ret), m);
+            if (exp[bit / 32] >> (bit % 32)) & 1 == 1 {
+                ret = rem(&mul(&ret, base), m);
+            }
+        }
+        ret
+    }
+
+    // Fermat test, sufficient to catch copy-paste errors
+    fn is_probable_prime(n: &Big) -> bool {
+        let n_minus_one: Big = sub(n, &vec![1]);
+        [2, 3, 5, 7]
+            .iter()
+            .all(|&a| pow_mod(&vec![a], &n_minus_one, n) == vec![1])
+    }
+
+    fn check<const MODULUS_SIZE: usize, const PRIME_ORDER_SIZE: usize>(
+        curve: &EllipticCurve<MODULUS_SIZE, PRIME_ORDER_SIZE>,
+    ) {
+        // no leading zero words
+        assert_ne!(curve.modulus[0], 0);
+        assert_ne!(curve.prime_order[0], 0);
+
+        let p: Big = big(&curve.modulus);
+        let n: Big = big(&curve.prime_order);
+        assert!(is_probable_prime(&p));
+        assert!(is_probable_prime(&n));
+
+        let a: Big = big(&curve.coef);
+        let b: Big = big(&curve.coef_b);
+        let x: Big = big(&curve.base_point_x);
+        let y: Big = big(&curve.base_point_y);
+        for operand in [&a, &b, &x, &y] {
+            assert_eq!(cmp(operand, &p), Ordering::Less);
+        }
+
+        // y² = x³ + ax + b (mod p)
+        let y2: Big = mul(&y, &y);
+        let x3: Big = mul(&mul(&x, &x), &x);
+        let ax: Big = mul(&a, &x);
+        let (lhs, rhs): (Big, Big) = match curve.coef_sign {
+            Sign::Pos => (y2, add(&add(&x3, &ax), &b)),
+            Sign::Neg => (add(&y2, &ax), add(&x3, &b)),
+        };
+        assert_eq!(rem(&lhs, &p), rem(&rhs, &p));
+    }
+
+    #[test]
+    fn nist_p192() {
+        check(&NIST_P192)
+    }
+
+    #[test]
+    fn nist_p224() {
+        check(&NIST_P224)
+    }
+
+    #[test]
+    fn nist_p256() {
+        check(&NIST_P256)
+    }
+
+    #[test]
+    fn nist_p384() {
+        check(&NIST_P384)
+    }
+
+    #[test]
+    fn nist_p521() {
+        check(&NIST_P521)
+    }
+
+    #[test]
+    fn secp256k1() {
+        check(&SECP256K1)
+    }
+
+    #[test]
+    fn brainpool_p224r1() {
+        check(&P224R1)
+    }
+
+    #[test]
+    fn brainpool_p256r1() {
+        check(&P256R1)
+    }
+
+    #[test]
+    fn brainpool_p384r1() {
+        check(&P384R1)
+    }
+}
@@ -0,0 +1,456 @@
+//! Deterministic ECDSA nonce generation, RFC 6979 section 3.2.
+//!
+//! HMAC-SHA-256 is implemented in software, the nonce is only generated once
+//! per signature so performance is not a concern.
+//!
+//! The intermediate values are derived from the private key, every buffer
+//! is zeroed before returning.
+
+use super::{bit_len, EllipticCurve, Secret};
+use crate::util::zeroize;
+
+// SHA-256 round constants, FIPS 180-4 section 4.2.2
+const K: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+// SHA-256 initial hash value, FIPS 180-4 section 5.3.3
+const H0: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+const BLOCK_LEN: usize = 64;
+const DIGEST_LEN: usize = 32;
+
+#[derive(Clone)]
+struct Sha256 {
+    state: [u32; 8],
+    block: [u8; BLOCK_LEN],
+    block_len: usize,
+    len: u64,
+}
+
+impl Sha256 {
+    const fn new() -> Self {
+        Sha256 {
+            state: H0,
+            block: [0; BLOCK_LEN],
+            block_len: 0,
+            len: 0,
+        }
+    }
+
+    fn compress(&mut self) {
+        let mut w: [u32; 64] = [0; 64];
+        w.iter_mut()
+            .zip(self.block.chunks_exact(4))
+            .for_each(|(w, b)| *w = u32::from_be_bytes([b[0], b[1], b[2], b[3]]));
+        for t in 16..64 {
+            let s0: u32 = w[t - 15].rotate_right(7) ^ w[t - 15].rotate_right(18) ^ (w[t - 15] >> 3);
+            let s1: u32 = w[t - 2].rotate_right(17) ^ w[t - 2].rotate_right(19) ^ (w[t - 2] >> 10);
+            w[t] = w[t - 16]
+                .wrapping_add(s0)
+                .wrapping_add(w[t - 7])
+                .wrapping_add(s1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = self.state;
+        for (k, w) in K.iter().zip(w.iter()) {
+            let s1: u32 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+            let ch: u32 = (e & f) ^ (!e & g);
+            let t1: u32 = h
+                .wrapping_add(s1)
+                .wrapping_add(ch)
+                .wrapping_add(*k)
+                .wrapping_add(*w);
+            let s0: u32 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+            let maj: u32 = (a & b) ^ (a & c) ^ (b & c);
+            let t2: u32 = s0.wrapping_add(maj);
+            h = g;
+            g = f;
+            f = e;
+            e = d.wrapping_add(t1);
+            d = c;
+            c = b;
+            b = a;
+            a = t1.wrapping_add(t2);
+        }
+
+        self.state
+            .iter_mut()
+            .zip([a, b, c, d, e, f, g, h].iter())
+            .for_each(|(s, x)| *s = s.wrapping_add(*x));
+        zeroize(&mut w);
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        self.len += data.len() as u64;
+        for &byte in data {
+            self.block[self.block_len] = byte;
+            self.block_len += 1;
+            if self.block_len == BLOCK_LEN {
+                self.compress();
+                self.block_len = 0;
+            }
+        }
+    }
+
+    fn finalize(mut self) -> [u8; DIGEST_LEN] {
+        let msg_bits: u64 = self.len * 8;
+        self.update(&[0x80]);
+        while self.block_len != BLOCK_LEN - 8 {
+            self.update(&[0x00]);
+        }
+        self.update(&msg_bits.to_be_bytes());
+
+        let mut digest: [u8; DIGEST_LEN] = [0; DIGEST_LEN];
+        digest
+            .chunks_exact_mut(4)
+            .zip(self.state.iter())
+            .for_each(|(chunk, s)| chunk.copy_from_slice(&s.to_be_bytes()));
+        digest
+    }
+}
+
+impl Drop for Sha256 {
+    fn drop(&mut self) {
+        zeroize(&mut self.state);
+        zeroize(&mut self.block);
+    }
+}
+
+// HMAC-SHA-256 with a 32 byte key, RFC 2104
+#[derive(Clone)]
+struct HmacSha256 {
+    inner: Sha256,
+    outer: Sha256,
+}
+
+impl HmacSha256 {
+    fn new(key: &[u8; DIGEST_LEN]) -> Self {
+        let mut ipad: [u8; BLOCK_LEN] = [0x36; BLOCK_LEN];
+        let mut opad: [u8; BLOCK_LEN] = [0x5c; BLOCK_LEN];
+        key.iter().enumerate().for_each(|(idx, k)| {
+            ipad[idx] ^= k;
+            opad[idx] ^= k;
+        });
+
+        let mut inner: Sha256 = Sha256::new();
+        inner.update(&ipad);
+        let mut outer: Sha256 = Sha256::new();
+        outer.update(&opad);
+        zeroize(&mut ipad);
+        zeroize(&mut opad);
+        HmacSha256 { inner, outer }
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        self.inner.update(data)
+    }
+
+    fn finalize(self) -> [u8; DIGEST_LEN] {
+        let mut outer: Sha256 = self.outer;
+        let mut inner: [u8; DIGEST_LEN] = self.inner.finalize();
+        outer.update(&inner);
+        zeroize(&mut inner);
+        outer.finalize()
+    }
+}
+
+fn hmac(key: &[u8; DIGEST_LEN], data: &[&[u8]]) -> [u8; DIGEST_LEN] {
+    let mut mac: HmacSha256 = HmacSha256::new(key);
+    data.iter().for_each(|d| mac.update(d));
+    mac.finalize()
+}
+
+// big-endian byte representation of an integer, the last `rlen` bytes are
+// returned by int2octets
+fn to_be_bytes<const N: usize>(x: &[u32; N], buf: &mut [u8]) {
+    buf.chunks_exact_mut(4)
+        .zip(x.iter())
+        .for_each(|(chunk, dw)| chunk.copy_from_slice(&dw.to_be_bytes()));
+}
+
+// bits2int, RFC 6979 section 2.3.2
+fn bits2int<const N: usize>(bits: &[u8], qlen: usize) -> [u32; N] {
+    let rlen: usize = qlen.div_ceil(8);
+    let bits: &[u8] = &bits[..bits.len().min(rlen)];
+    let shift: usize = (bits.len() * 8).saturating_sub(qlen);
+
+    // right aligned big-endian bytes
+    let mut ret: [u32; N] = [0; N];
+    let offset: usize = N * 4 - bits.len();
+    bits.iter().enumerate().for_each(|(idx, &byte)| {
+        let pos: usize = offset + idx;
+        ret[pos / 4] |= u32::from(byte) << (24 - 8 * (pos % 4));
+    });
+
+    if shift != 0 {
+        for idx in (0..N).rev() {
+            let carry: u32 = if idx == 0 {
+                0
+            } else {
+                ret[idx - 1] << (32 - shift)
+            };
+            ret[idx] = (ret[idx] >> shift) | carry;
+        }
+    }
+
+    ret
+}
+
+// a - b, a must be greater than or equal to b
+fn sub<const N: usize>(a: &[u32; N], b: &[u32; N]) -> [u32; N] {
+    let mut ret: [u32; N] = [0; N];
+    let mut borrow: bool = false;
+    for idx in (0..N).rev() {
+        let (diff, b0) = a[idx].overflowing_sub(b[idx]);
+        let (diff, b1) = diff.overflowing_sub(u32::from(borrow));
+        ret[idx] = diff;
+        borrow = b0 || b1;
+    }
+    ret
+}
+
+// largest supported prime order, limited by the buffer size for T
+const MAX_QLEN: usize = 3 * DIGEST_LEN * 8;
+
+/// Generate a deterministic ECDSA nonce as specified in RFC 6979.
+///
+/// The nonce is derived from the private key and the hash with HMAC-SHA-256,
+/// a new nonce does not require a random number generator, and the same
+/// private key and hash always generate the same nonce.
+///
+/// The hash is the same hash input as used for
+/// [`ecdsa_sign`](super::Pka::ecdsa_sign).
+///
+/// This is used by
+/// [`ecdsa_sign_deterministic`](super::Pka::ecdsa_sign_deterministic),
+/// use this directly for the non-blocking and asynchronous signing methods.
+///
+/// The nonce reveals the private key if it is leaked, it is returned in a
+/// [`Secret`] that is zeroed when dropped.
+///
+/// # Panics
+///
+/// * The prime order of the curve is greater than 768 bits.
+///
+/// # Example
+///
+/// ```no_run
+/// # let mut pka = unsafe { stm32wl_hal::pka::Pka::steal() };
+/// # let priv_key: [u32; 8] = [0; 8];
+/// # let hash: [u32; 8] = [0; 8];
+/// use stm32wl_hal::pka::{curve::NIST_P256, rfc6979_nonce, Secret};
+///
+/// let nonce: Secret<8> = rfc6979_nonce(&NIST_P256, &priv_key, &hash);
+/// pka.ecdsa_sign_start(&NIST_P256, nonce.expose(), &priv_key, &hash)?;
+/// # Ok::<(), stm32wl_hal::pka::EcdsaSignError>(())
+/// ```
+pub fn rfc6979_nonce<const MODULUS_SIZE: usize, const PRIME_ORDER_SIZE: usize>(
+    curve: &EllipticCurve<MODULUS_SIZE, PRIME_ORDER_SIZE>,
+    priv_key: &[u32; PRIME_ORDER_SIZE],
+    hash: &[u32; PRIME_ORDER_SIZE],
+) -> Secret<PRIME_ORDER_SIZE> {
+    let q: &[u32; PRIME_ORDER_SIZE] = &curve.prime_order;
+    let qlen: usize = bit_len(q) as usize;
+    assert!(qlen <= MAX_QLEN);
+    let rlen: usize = qlen.div_ceil(8);
+
+    // the hash input is the integer bits2int(H(m)), reduce it modulo q
+    // for bits2octets
+    let z2: [u32; PRIME_ORDER_SIZE] = if hash >= q { sub(hash, q) } else { *hash };
+
+    // int2octets of the private key and the reduced hash
+    let mut x_bytes: [u8; MAX_QLEN / 8 + 4] = [0; MAX_QLEN / 8 + 4];
+    let mut h_bytes: [u8; MAX_QLEN / 8 + 4] = [0; MAX_QLEN / 8 + 4];
+    to_be_bytes(priv_key, &mut x_bytes);
+    to_be_bytes(&z2, &mut h_bytes);
+    let start: usize = PRIME_ORDER_SIZE * 4 - rlen;
+    let x_oct: &[u8] = &x_bytes[start..PRIME_ORDER_SIZE * 4];
+    let h_oct: &[u8] = &h_bytes[start..PRIME_ORDER_SIZE * 4];
+
+    // steps b through g
+    let mut v: [u8; DIGEST_LEN] = [0x01; DIGEST_LEN];
+    let mut k: [u8; DIGEST_LEN] = [0x00; DIGEST_LEN];
+    k = hmac(&k, &[&v, &[0x00], x_oct, h_oct]);
+    v = hmac(&k, &[&v]);
+    k = hmac(&k, &[&v, &[0x01], x_oct, h_oct]);
+    v = hmac(&k, &[&v]);
+
+    zeroize(&mut x_bytes);
+
+    // step h
+    let mut t: [u8; MAX_QLEN / 8] = [0; MAX_QLEN / 8];
+    let nonce: Secret<PRIME_ORDER_SIZE> = loop {
+        let mut tlen: usize = 0;
+        while tlen < rlen {
+            v = hmac(&k, &[&v]);
+            t[tlen..tlen + DIGEST_LEN].copy_from_slice(&v);
+            tlen += DIGEST_LEN;
+        }
+
+        let nonce: Secret<PRIME_ORDER_SIZE> = Secret::new(bits2int(&t[..tlen], qlen));
+        if nonce.expose().iter().any(|&dw| dw != 0) && nonce.expose() < q {
+            break nonce;
+        }
+
+        k = hmac(&k, &[&v, &[0x00]]);
+        v = hmac(&k, &[&v]);
+    };
+
+    zeroize(&mut t);
+    zeroize(&mut k);
+    zeroize(&mut v);
+    nonce
+}
+
+#[cfg(test)]
+mod test {
+    use super::{rfc6979_nonce, HmacSha256, Sha256};
+    use crate::pka::curve::{NIST_P192, NIST_P256, NIST_P521};
+
+    fn sha256(data: &[u8]) -> [u8; 32] {
+        let mut sha: Sha256 = Sha256::new();
+        sha.update(data);
+        sha.finalize()
+    }
+
+    fn words<const N: usize>(bytes: &[u8]) -> [u32; N] {
+        let mut ret: [u32; N] = [0; N];
+        ret.iter_mut()
+            .zip(bytes.chunks_exact(4))
+            .for_each(|(w, b)| *w = u32::from_be_bytes([b[0], b[1], b[2], b[3]]));
+        ret
+    }
+
+    // FIPS 180-2 appendix B
+    #[test]
+    fn sha256_vectors() {
+        assert_eq!(
+            sha256(b"abc"),
+            [
+                0xba, 0x78, 0x16, 0xbf, 0x8f, 0x01, 0xcf, 0xea, 0x41, 0x41, 0x40, 0xde, 0x5d, 0xae,
+                0x22, 0x23, 0xb0, 0x03, 0x61, 0xa3, 0x96, 0x17, 0x7a, 0x9c, 0xb4, 0x10, 0xff, 0x61,
+                0xf2, 0x00, 0x15, 0xad,
+            ]
+        );
+        assert_eq!(
+            sha256(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"),
+            [
+                0x24, 0x8d, 0x6a, 0x61, 0xd2, 0x06, 0x38, 0xb8, 0xe5, 0xc0, 0x26, 0x93, 0x0c, 0x3e,
+                0x60, 0x39, 0xa3, 0x3c, 0xe4, 0x59, 0x64, 0xff, 0x21, 0x67, 0xf6, 0xec, 0xed, 0xd4,
+                0x19, 0xdb, 0x06, 0xc1,
+            ]
+        );
+    }
+
+    // RFC 4231 test case 2, with the key zero padded to 32 bytes
+    #[test]
+    fn hmac_sha256() {
+        let mut key: [u8; 32] = [0; 32];
+        key[..4].copy_from_slice(b"Jefe");
+        let mut mac: HmacSha256 = HmacSha256::new(&key);
+        mac.update(b"what do ya want ");
+        mac.update(b"for nothing?");
+        assert_eq!(
+            mac.finalize(),
+            [
+                0x5b, 0xdc, 0xc1, 0x46, 0xbf, 0x60, 0x75, 0x4e, 0x6a, 0x04, 0x24, 0x26, 0x08, 0x95,
+                0x75, 0xc7, 0x5a, 0x00, 0x3f, 0x08, 0x9d, 0x27, 0x39, 0x83, 0x9d, 0xec, 0x58, 0xb9,
+                0x64, 0xec, 0x38, 0x43,
+            ]
+        );
+    }
+
+    // RFC 6979 appendix A.2.5, P-256 with SHA-256
+    #[test]
+    fn nist_p256() {
+        const X: [u32; 8] = [
+            0xc9afa9d8, 0x45ba7516, 0x6b5c2157, 0x67b1d693, 0x4e50c3db, 0x36e89b12, 0x7b8a622b,
+            0x120f6721,
+        ];
+
+        let hash: [u32; 8] = words(&sha256(b"sample"));
+        assert_eq!(
+            *rfc6979_nonce(&NIST_P256, &X, &hash).expose(),
+            [
+                0xa6e3c57d, 0xd01abe90, 0x08653839, 0x8355dd4c, 0x3b17aa87, 0x3382b0f2, 0x4d612949,
+                0x3d8aad60,
+            ]
+        );
+
+        let hash: [u32; 8] = words(&sha256(b"test"));
+        assert_eq!(
+            *rfc6979_nonce(&NIST_P256, &X, &hash).expose(),
+            [
+                0xd16b6ae8, 0x27f17175, 0xe040871a, 0x1c7ec350, 0x0192c4c9, 0x2677336e, 0xc2537aca,
+                0xee0008e0,
+            ]
+        );
+    }
+
+    // RFC 6979 appendix A.2.3, P-192 with SHA-256
+    #[test]
+    fn nist_p192() {
+        const X: [u32; 6] = [
+            0x6fab0349, 0x34e4c0fc, 0x9ae67f5b, 0x5659a9d7, 0xd1fefd18, 0x7ee09fd4,
+        ];
+
+        // ECDSA hash input is the leftmost 192 bits of the digest
+        let hash: [u32; 6] = words(&sha256(b"sample"));
+        assert_eq!(
+            *rfc6979_nonce(&NIST_P192, &X, &hash).expose(),
+            [0x32b1b6d7, 0xd42a05cb, 0x44906572, 0x7a84804f, 0xb1a3e34d, 0x8f261496]
+        );
+
+        let hash: [u32; 6] = words(&sha256(b"test"));
+        assert_eq!(
+            *rfc6979_nonce(&NIST_P192, &X, &hash).expose(),
+            [0x5c4ce89c, 0xf56d9e7c, 0x77c85853, 0x39b006b9, 0x7b5f0680, 0xb4306c6c]
+        );
+    }
+
+    // RFC 6979 appendix A.2.7, P-521 with SHA-256
+    //
+    // the hash is shorter than the prime order, and the prime order is not a
+    // multiple of 32 bits
+    #[test]
+    fn nist_p521() {
+        const X: [u32; 17] = [
+            0x000000fa, 0xd06daa62, 0xba3b25d2, 0xfb40133d, 0xa757205d, 0xe67f5bb0, 0x018fee8c,
+            0x86e1b68c, 0x7e75caa8, 0x96eb32f1, 0xf47c7085, 0x5836a6d1, 0x6fcc1466, 0xf6d8fbec,
+            0x67db89ec, 0x0c08b0e9, 0x96b83538,
+        ];
+
+        let mut digest: [u8; 68] = [0; 68];
+        digest[36..].copy_from_slice(&sha256(b"sample"));
+        let hash: [u32; 17] = words(&digest);
+        assert_eq!(
+            *rfc6979_nonce(&NIST_P521, &X, &hash).expose(),
+            [
+                0x000000ed, 0xf38afcaa, 0xecab4383, 0x358b34d6, 0x7c9f2216, 0xc8382aae, 0xa44a3dad,
+                0x5fdc9c32, 0x57576179, 0x3fef24eb, 0x0fc276df, 0xc4f6e3ec, 0x476752f0, 0x43cf0141,
+                0x5387470b, 0xcbd8678e, 0xd2c7e1a0,
+            ]
+        );
+
+        digest[36..].copy_from_slice(&sha256(b"test"));
+        let hash: [u32; 17] = words(&digest);
+        assert_eq!(
+            *rfc6979_nonce(&NIST_P521, &X, &hash).expose(),
+            [
+                0x0000001d, 0xe74955ef, 0xaabc4c4f, 0x17f8e84d, 0x881d1310, 0xb5392d77, 0x00275f82,
+                0xf145c61e, 0x843841af, 0x09035bf7, 0xa6210f5a, 0x431a6a9e, 0x81c93233, 0x54a9e691,
+                0x35d44ebd, 0x2fcaa773, 0x1b909258,
+            ]
+        );
+    }
+}
@@ -0,0 +1,96 @@
+use crate::util::zeroize;
+
+/// Private key material that is zeroed when dropped.
+///
+/// This is returned by [`rfc6979_nonce`](super::rfc6979_nonce), and receives
+/// the secret outputs of [`ecdh_shared_secret`](super::Pka::ecdh_shared_secret)
+/// and [`rsa_crt`](super::Pka::rsa_crt).
+///
+/// The words are overwritten with volatile writes when the secret is dropped
+/// or [`zeroize`](Self::zeroize) is called.
+/// The [`Debug`](core::fmt::Debug) implementation does not print the words.
+///
+/// Moving a secret copies the words, the copy left behind at the old location
+/// is not zeroed.
+/// Keep secrets in place and pass them by reference to avoid stray copies.
+///
+/// # Example
+///
+/// ```no_run
+/// # let mut pka = unsafe { stm32wl_hal::pka::Pka::steal() };
+/// # let priv_key: [u32; 8] = [0; 8];
+/// # let peer_pub_key = stm32wl_hal::pka::EcdsaPublicKey { curve_pt_x: &[0; 8], curve_pt_y: &[0; 8] };
+/// use stm32wl_hal::pka::{curve::NIST_P256, Secret};
+///
+/// let mut shared_secret: Secret<8> = Secret::new([0; 8]);
+/// pka.ecdh_shared_secret(&NIST_P256, &priv_key, &peer_pub_key, &mut shared_secret)?;
+/// // ... derive a key from shared_secret.expose()
+/// # Ok::<(), stm32wl_hal::pka::EccError>(())
+/// ```
+pub struct Secret<const N: usize> {
+    pub(super) words: [u32; N],
+}
+
+impl<const N: usize> Secret<N> {
+    /// Create a new secret.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stm32wl_hal::pka::Secret;
+    ///
+    /// let secret: Secret<2> = Secret::new([1, 2]);
+    /// assert_eq!(secret.expose(), &[1, 2]);
+    /// ```
+    pub const fn new(words: [u32; N]) -> Self {
+        Secret { words }
+    }
+
+    /// Borrow the secret words.
+    #[inline]
+    pub fn expose(&self) -> &[u32; N] {
+        &self.words
+    }
+
+    /// Mutably borrow the secret words.
+    #[inline]
+    pub fn expose_mut(&mut self) -> &mut [u32; N] {
+        &mut self.words
+    }
+
+    /// Zero the secret words.
+    ///
+    /// This is called automatically when the secret is dropped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stm32wl_hal::pka::Secret;
+    ///
+    /// let mut secret: Secret<2> = Secret::new([1, 2]);
+    /// secret.zeroize();
+    /// assert_eq!(secret.expose(), &[0, 0]);
+    /// ```
+    pub fn zeroize(&mut self) {
+        zeroize(&mut self.words)
+    }
+}
+
+impl<const N: usize> Drop for Secret<N> {
+    fn drop(&mut self) {
+        self.zeroize()
+    }
+}
+
+impl<const N: usize> core::fmt::Debug for Secret<N> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("Secret { .. }")
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl<const N: usize> defmt::Format for Secret<N> {
+    fn format(&self, fmt: defmt::Formatter) {
+        defmt::write!(fmt, "Secret {{ .. }}")
+    }
+}
@@ -81,6 +81,38 @@ pub fn setup_wakeup_pins(pwr: &mut pac::PWR, wp1: WakeupPin, wp2: WakeupPin, wp3
     });
 }
 
+/// Set the internal wakeup line enable for standby and shutdown modes.
+///
+/// The internal wakeup line is the wakeup source from the RTC (alarms,
+/// wakeup timer, timestamp) and the TAMP for the standby and shutdown
+/// low-power modes.
+/// The EXTI is not powered in these modes, the RTC and TAMP events must be
+/// enabled in their respective peripherals.
+///
+/// # Example
+///
+/// Wakeup from standby with the RTC alarm A.
+///
+/// ```no_run
+/// use stm32wl_hal::{
+///     pac,
+///     pwr::set_internal_wakeup_line,
+///     rtc::{Alarm, AlarmCfg, Rtc},
+/// };
+///
+/// let mut dp: pac::Peripherals = pac::Peripherals::take().unwrap();
+/// let mut rtc: Rtc = unsafe { Rtc::steal() };
+///
+/// rtc.set_alarm(Alarm::A, &AlarmCfg::new().set_minutes(0).set_seconds(0));
+/// rtc.set_alarm_irq(Alarm::A, true);
+/// Rtc::clear_alarm(Alarm::A);
+/// set_internal_wakeup_line(&mut dp.PWR, true);
+/// ```
+#[inline]
+pub fn set_internal_wakeup_line(pwr: &mut pac::PWR, en: bool) {
+    pwr.cr3.modify(|_, w| w.eiwul().bit(en));
+}
+
 /// Enter shutdown mode immediately.
 ///
 /// Wakeup pins should be configured with [`setup_wakeup_pins`] unless
@@ -11,6 +11,17 @@
 //! | `ChaCha8Rng`  | 1,216                 |
 //! | HW            | 410                   |
 //!
+//! The [`CtrDrbg`](crate::aes::CtrDrbg) generates random data with the AES
+//! peripheral, seeded from this RNG.
+//!
+//! # Error recovery
+//!
+//! Seed errors and clock errors are recovered automatically with the
+//! sequences from the reference manual, the number of attempts is set with a
+//! [`RetryPolicy`].
+//! The [`Health`] counters record the errors and recoveries, an
+//! [`Error`] is only returned when the recovery failed.
+//!
 //! [chacha]: https://crates.io/crates/chacha20
 
 use crate::pac;
@@ -30,10 +41,13 @@ pub enum Error {
     /// A seed error (noise error) occured.
     ///
     /// **Note:** There is automatic correction for seed errors included.
-    /// This error means that automatic correction was attempted, but failed.
+    /// This error means that automatic correction was attempted
+    /// [`RetryPolicy::seed_retries`] times, but failed.
     Seed,
     /// RNG frequency is too low.
     ///
+    /// The clock error did not clear after
+    /// [`RetryPolicy::clock_retries`] polls.
     /// Check that the RNG is configured correctly.
     Clock,
 }
@@ -51,11 +65,465 @@ impl From<Error> for rand_core::Error {
 
 pub use pac::rcc::ccipr::RNGSEL_A as Clk;
 
+// depth of the RNG_DR FIFO in 32-bit words
+const FIFO_DW: u8 = 4;
+
+/// RNG clock divider.
+///
+/// Argument of [`Config::set_clk_div`].
+///
+/// The noise source sampling clock is the RNG kernel clock divided by this
+/// value.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[repr(u8)]
+pub enum ClkDiv {
+    /// Divide by 1.
+    Div1 = 0x0,
+    /// Divide by 2.
+    Div2 = 0x1,
+    /// Divide by 4.
+    Div4 = 0x2,
+    /// Divide by 8.
+    Div8 = 0x3,
+    /// Divide by 16.
+    Div16 = 0x4,
+    /// Divide by 32.
+    Div32 = 0x5,
+    /// Divide by 64.
+    Div64 = 0x6,
+    /// Divide by 128.
+    Div128 = 0x7,
+    /// Divide by 256.
+    Div256 = 0x8,
+    /// Divide by 512.
+    Div512 = 0x9,
+    /// Divide by 1024.
+    Div1024 = 0xA,
+    /// Divide by 2048.
+    Div2048 = 0xB,
+    /// Divide by 4096.
+    Div4096 = 0xC,
+    /// Divide by 8192.
+    Div8192 = 0xD,
+    /// Divide by 16384.
+    Div16384 = 0xE,
+    /// Divide by 32768.
+    Div32768 = 0xF,
+}
+
+impl ClkDiv {
+    const fn from_bits(bits: u8) -> ClkDiv {
+        match bits & 0xF {
+            0x0 => ClkDiv::Div1,
+            0x1 => ClkDiv::Div2,
+            0x2 => ClkDiv::Div4,
+            0x3 => ClkDiv::Div8,
+            0x4 => ClkDiv::Div16,
+            0x5 => ClkDiv::Div32,
+            0x6 => ClkDiv::Div64,
+            0x7 => ClkDiv::Div128,
+            0x8 => ClkDiv::Div256,
+            0x9 => ClkDiv::Div512,
+            0xA => ClkDiv::Div1024,
+            0xB => ClkDiv::Div2048,
+            0xC => ClkDiv::Div4096,
+            0xD => ClkDiv::Div8192,
+            0xE => ClkDiv::Div16384,
+            _ => ClkDiv::Div32768,
+        }
+    }
+
+    /// Get the division factor.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stm32wl_hal::rng::ClkDiv;
+    ///
+    /// assert_eq!(ClkDiv::Div1.div(), 1);
+    /// assert_eq!(ClkDiv::Div32768.div(), 32768);
+    /// ```
+    pub const fn div(self) -> u16 {
+        1 << (self as u8)
+    }
+}
+
+/// RNG noise source and conditioning configuration.
+///
+/// Argument of [`Rng::set_config`], and returned by [`Rng::config`].
+///
+/// The values of the RNG_CONFIG1, RNG_CONFIG2, and RNG_CONFIG3 fields are
+/// described in the reference manual table 131 "RNG configurations",
+/// and in the application note AN4230.
+/// Only [`Config::CERTIFIED`] is covered by the NIST SP800-90B entropy
+/// certification.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Config {
+    nistc: bool,
+    config1: u8,
+    config2: u8,
+    config3: u8,
+    clk_div: ClkDiv,
+    ced: bool,
+}
+
+impl Config {
+    /// NIST SP800-90B certified configuration.
+    ///
+    /// This is configuration A from the reference manual table 131
+    /// "RNG configurations", with clock error detection enabled.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stm32wl_hal::rng::{ClkDiv, Config};
+    ///
+    /// assert!(Config::CERTIFIED.nistc());
+    /// assert_eq!(Config::CERTIFIED.config1(), 0x0F);
+    /// assert_eq!(Config::CERTIFIED.config2(), 0x0);
+    /// assert_eq!(Config::CERTIFIED.config3(), 0xD);
+    /// assert_eq!(Config::CERTIFIED.clk_div(), ClkDiv::Div1);
+    /// assert!(Config::CERTIFIED.clock_error_detection());
+    /// ```
+    pub const CERTIFIED: Config = Config {
+        nistc: true,
+        config1: 0x0F,
+        config2: 0x0,
+        config3: 0xD,
+        clk_div: ClkDiv::Div1,
+        ced: true,
+    };
+
+    /// Create a new `Config` structure.
+    ///
+    /// This is the same as `default`, but in a `const` function.
+    ///
+    /// The default is the [certified](Self::CERTIFIED) configuration.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stm32wl_hal::rng::Config;
+    ///
+    /// const CONFIG: Config = Config::new();
+    /// assert_eq!(CONFIG, Config::default());
+    /// assert_eq!(CONFIG, Config::CERTIFIED);
+    /// ```
+    pub const fn new() -> Config {
+        Config::CERTIFIED
+    }
+
+    /// Set the NIST custom configuration bit (NISTC).
+    ///
+    /// When set the RNG_CONFIG1, RNG_CONFIG2, and RNG_CONFIG3 values are
+    /// used, when cleared the hardware default NIST compliant values are
+    /// used.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stm32wl_hal::rng::Config;
+    ///
+    /// const CONFIG: Config = Config::new().set_nistc(false);
+    /// assert!(!CONFIG.nistc());
+    /// ```
+    #[must_use = "set_nistc returns a modified Config"]
+    pub const fn set_nistc(mut self, nistc: bool) -> Config {
+        self.nistc = nistc;
+        self
+    }
+
+    /// Set the RNG_CONFIG1 field, the noise source oscillator configuration.
+    ///
+    /// Values wider than 6 bits are truncated.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stm32wl_hal::rng::Config;
+    ///
+    /// const CONFIG: Config = Config::new().set_config1(0x18);
+    /// assert_eq!(CONFIG.config1(), 0x18);
+    /// assert_eq!(CONFIG.set_config1(0xFF).config1(), 0x3F);
+    /// ```
+    #[must_use = "set_config1 returns a modified Config"]
+    pub const fn set_config1(mut self, config1: u8) -> Config {
+        self.config1 = config1 & 0x3F;
+        self
+    }
+
+    /// Set the RNG_CONFIG2 field, the noise source oscillator configuration.
+    ///
+    /// Values wider than 3 bits are truncated.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stm32wl_hal::rng::Config;
+    ///
+    /// const CONFIG: Config = Config::new().set_config2(0x1);
+    /// assert_eq!(CONFIG.config2(), 0x1);
+    /// ```
+    #[must_use = "set_config2 returns a modified Config"]
+    pub const fn set_config2(mut self, config2: u8) -> Config {
+        self.config2 = config2 & 0x7;
+        self
+    }
+
+    /// Set the RNG_CONFIG3 field, the conditioning configuration.
+    ///
+    /// Values wider than 4 bits are truncated.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stm32wl_hal::rng::Config;
+    ///
+    /// const CONFIG: Config = Config::new().set_config3(0x0);
+    /// assert_eq!(CONFIG.config3(), 0x0);
+    /// ```
+    #[must_use = "set_config3 returns a modified Config"]
+    pub const fn set_config3(mut self, config3: u8) -> Config {
+        self.config3 = config3 & 0xF;
+        self
+    }
+
+    /// Set the clock divider.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stm32wl_hal::rng::{ClkDiv, Config};
+    ///
+    /// const CONFIG: Config = Config::new().set_clk_div(ClkDiv::Div2);
+    /// assert_eq!(CONFIG.clk_div(), ClkDiv::Div2);
+    /// ```
+    #[must_use = "set_clk_div returns a modified Config"]
+    pub const fn set_clk_div(mut self, div: ClkDiv) -> Config {
+        self.clk_div = div;
+        self
+    }
+
+    /// Set the clock error detection enable.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stm32wl_hal::rng::Config;
+    ///
+    /// const CONFIG: Config = Config::new().set_clock_error_detection(false);
+    /// assert!(!CONFIG.clock_error_detection());
+    /// ```
+    #[must_use = "set_clock_error_detection returns a modified Config"]
+    pub const fn set_clock_error_detection(mut self, en: bool) -> Config {
+        self.ced = en;
+        self
+    }
+
+    /// Returns `true` if the NIST custom configuration bit is set.
+    pub const fn nistc(&self) -> bool {
+        self.nistc
+    }
+
+    /// RNG_CONFIG1 field.
+    pub const fn config1(&self) -> u8 {
+        self.config1
+    }
+
+    /// RNG_CONFIG2 field.
+    pub const fn config2(&self) -> u8 {
+        self.config2
+    }
+
+    /// RNG_CONFIG3 field.
+    pub const fn config3(&self) -> u8 {
+        self.config3
+    }
+
+    /// Clock divider.
+    pub const fn clk_div(&self) -> ClkDiv {
+        self.clk_div
+    }
+
+    /// Returns `true` if clock error detection is enabled.
+    pub const fn clock_error_detection(&self) -> bool {
+        self.ced
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// RNG configuration lock error.
+///
+/// Returned by [`Rng::set_config`] when the configuration was locked with
+/// [`Rng::lock_config`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ConfigLocked;
+
+/// Error recovery retry policy.
+///
+/// Argument of [`Rng::set_retry_policy`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct RetryPolicy {
+    seed: u8,
+    clock: u16,
+}
+
+impl RetryPolicy {
+    /// Create a new `RetryPolicy` structure.
+    ///
+    /// This is the same as `default`, but in a `const` function.
+    ///
+    /// The defaults are 3 seed error retries, and 1000 clock error status
+    /// polls.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stm32wl_hal::rng::RetryPolicy;
+    ///
+    /// const POLICY: RetryPolicy = RetryPolicy::new();
+    /// assert_eq!(POLICY, RetryPolicy::default());
+    /// assert_eq!(POLICY.seed_retries(), 3);
+    /// assert_eq!(POLICY.clock_retries(), 1000);
+    /// ```
+    pub const fn new() -> RetryPolicy {
+        RetryPolicy {
+            seed: 3,
+            clock: 1000,
+        }
+    }
+
+    /// Set the number of seed error recovery attempts.
+    ///
+    /// Each attempt resets the conditioning logic and discards the FIFO.
+    /// Setting this to zero disables seed error recovery.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stm32wl_hal::rng::RetryPolicy;
+    ///
+    /// const POLICY: RetryPolicy = RetryPolicy::new().set_seed_retries(8);
+    /// assert_eq!(POLICY.seed_retries(), 8);
+    /// ```
+    #[must_use = "set_seed_retries returns a modified RetryPolicy"]
+    pub const fn set_seed_retries(mut self, retries: u8) -> RetryPolicy {
+        self.seed = retries;
+        self
+    }
+
+    /// Set the number of times the clock error status is polled before
+    /// returning [`Error::Clock`].
+    ///
+    /// This is an iteration count, not a duration.
+    /// Each iteration is one read of the RNG_SR register, the time this takes
+    /// depends on the AHB clock frequency and the CPU, not on the RNG clock.
+    /// The clock error status is updated by the clock checker in the RNG
+    /// clock domain, a slow RNG clock may need more polls to recover.
+    ///
+    /// Setting this to zero returns [`Error::Clock`] as soon as a clock
+    /// error is detected.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stm32wl_hal::rng::RetryPolicy;
+    ///
+    /// const POLICY: RetryPolicy = RetryPolicy::new().set_clock_retries(0);
+    /// assert_eq!(POLICY.clock_retries(), 0);
+    /// ```
+    #[must_use = "set_clock_retries returns a modified RetryPolicy"]
+    pub const fn set_clock_retries(mut self, retries: u16) -> RetryPolicy {
+        self.clock = retries;
+        self
+    }
+
+    /// Number of seed error recovery attempts.
+    pub const fn seed_retries(&self) -> u8 {
+        self.seed
+    }
+
+    /// Number of clock error status polls, this is an iteration count.
+    pub const fn clock_retries(&self) -> u16 {
+        self.clock
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// RNG health counters.
+///
+/// Returned by [`Rng::health`].
+///
+/// All counters saturate when they hit the maximum value.
+/// The number of errors that could not be recovered is the number of errors
+/// minus the number of recoveries.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Health {
+    seed_errors: u32,
+    seed_recoveries: u32,
+    clock_errors: u32,
+    clock_recoveries: u32,
+    discarded: u32,
+}
+
+impl Health {
+    const fn new() -> Health {
+        Health {
+            seed_errors: 0,
+            seed_recoveries: 0,
+            clock_errors: 0,
+            clock_recoveries: 0,
+            discarded: 0,
+        }
+    }
+
+    /// Number of seed errors detected.
+    pub const fn seed_errors(&self) -> u32 {
+        self.seed_errors
+    }
+
+    /// Number of seed errors recovered.
+    pub const fn seed_recoveries(&self) -> u32 {
+        self.seed_recoveries
+    }
+
+    /// Number of clock errors detected.
+    pub const fn clock_errors(&self) -> u32 {
+        self.clock_errors
+    }
+
+    /// Number of clock errors recovered.
+    pub const fn clock_recoveries(&self) -> u32 {
+        self.clock_recoveries
+    }
+
+    /// Number of words discarded from the FIFO after a seed error.
+    pub const fn discarded(&self) -> u32 {
+        self.discarded
+    }
+}
+
 /// RNG driver.
 #[derive(Debug)]
 pub struct Rng {
     rng: pac::RNG,
-    err_cnt: u32,
+    retry: RetryPolicy,
+    health: Health,
 }
 
 impl Rng {
@@ -64,6 +532,9 @@ impl Rng {
     /// This will select the clock source, enable clocks, and reset the RNG
     /// peripheral.
     ///
+    /// The RNG is configured with [`Config::CERTIFIED`], use
+    /// [`set_config`](Self::set_config) for a different configuration.
+    ///
     /// This will **NOT** enable the selected clock source, when in doubt use
     /// MSI because it is enabled with power-on-reset.
     ///
@@ -85,44 +556,121 @@ impl Rng {
         rcc.ahb3rstr.modify(|_, w| w.rngrst().set_bit());
         rcc.ahb3rstr.modify(|_, w| w.rngrst().clear_bit());
 
-        // RNG configuration A
-        // see table 131 "RNG configurations" in the reference manual
-        #[rustfmt::skip]
-        rng.cr.write(|w| {
-            w
-                .condrst().set_bit()
-                .nistc().set_bit()
-                .rng_config1().config_a()
-                .clkdiv().bits(0x0)
-                .rng_config2().config_a_b()
-                .rng_config3().config_a()
-                .ced().enabled()
-                .ie().disabled() // interrupt enable
-                .rngen().set_bit()
-        });
-
-        #[rustfmt::skip]
-        rng.cr.write(|w| {
-            w
-                .condrst().clear_bit()
-                .nistc().set_bit()
-                .rng_config1().config_a()
-                .clkdiv().bits(0x0)
-                .rng_config2().config_a_b()
-                .rng_config3().config_a()
-                .ced().clear_bit()
-                .ie().disabled() // interrupt enable
-                .rngen().set_bit()
-        });
+        let mut rng: Rng = Rng {
+            rng,
+            retry: RetryPolicy::new(),
+            health: Health::new(),
+        };
+        rng.write_config(Config::CERTIFIED);
+        rng
+    }
+
+    // the configuration is written with CONDRST set, clearing CONDRST
+    // restarts the conditioning with the new configuration
+    fn write_config(&mut self, config: Config) {
+        // interrupt enable is not part of the configuration, modify keeps
+        // the IE bit
+        for condrst in [true, false] {
+            // safety: the config setters truncate values to the field widths
+            #[rustfmt::skip]
+            self.rng.cr.modify(|_, w| unsafe {
+                w
+                    .condrst().bit(condrst)
+                    .nistc().bit(config.nistc)
+                    .rng_config1().bits(config.config1)
+                    .clkdiv().bits(config.clk_div as u8)
+                    .rng_config2().bits(config.config2)
+                    .rng_config3().bits(config.config3)
+                    // CED is an active low disable bit
+                    .ced().bit(!config.ced)
+                    .rngen().set_bit()
+            });
+        }
 
         // when CONDRST is set to 0 by software its value goes to 0 when the
         // reset process is done.
         // It takes about 2 AHB clock cycles + 2 RNG clock cycles
-        while rng.cr.read().condrst().bit_is_set() {
+        while self.rng.cr.read().condrst().bit_is_set() {
             compiler_fence(SeqCst);
         }
+    }
 
-        Rng { rng, err_cnt: 0 }
+    /// Set the noise source and conditioning configuration.
+    ///
+    /// This resets the conditioning logic, random data in the FIFO from the
+    /// previous configuration is discarded.
+    ///
+    /// # Errors
+    ///
+    /// * [`ConfigLocked`] The configuration was locked with
+    ///   [`lock_config`](Self::lock_config).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use stm32wl_hal::{
+    ///     pac,
+    ///     rng::{ClkDiv, Clk, Config, Rng},
+    /// };
+    ///
+    /// let mut dp: pac::Peripherals = pac::Peripherals::take().unwrap();
+    /// let mut rng = Rng::new(dp.RNG, Clk::MSI, &mut dp.RCC);
+    ///
+    /// const CONFIG: Config = Config::CERTIFIED.set_clk_div(ClkDiv::Div2);
+    /// rng.set_config(CONFIG)?;
+    /// assert_eq!(rng.config(), CONFIG);
+    /// # Ok::<(), stm32wl_hal::rng::ConfigLocked>(())
+    /// ```
+    pub fn set_config(&mut self, config: Config) -> Result<(), ConfigLocked> {
+        if self.is_config_locked() {
+            Err(ConfigLocked)
+        } else {
+            self.write_config(config);
+            Ok(())
+        }
+    }
+
+    /// Read back the active noise source and conditioning configuration
+    /// from the RNG_CR register.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use stm32wl_hal::{
+    ///     pac,
+    ///     rng::{Clk, Config, Rng},
+    /// };
+    ///
+    /// let mut dp: pac::Peripherals = pac::Peripherals::take().unwrap();
+    /// let rng = Rng::new(dp.RNG, Clk::MSI, &mut dp.RCC);
+    ///
+    /// assert_eq!(rng.config(), Config::CERTIFIED);
+    /// ```
+    pub fn config(&self) -> Config {
+        let cr = self.rng.cr.read();
+        Config {
+            nistc: cr.nistc().bit(),
+            config1: cr.rng_config1().bits(),
+            config2: cr.rng_config2().bits(),
+            config3: cr.rng_config3().bits(),
+            clk_div: ClkDiv::from_bits(cr.clkdiv().bits()),
+            ced: cr.ced().bit_is_clear(),
+        }
+    }
+
+    /// Lock the configuration.
+    ///
+    /// After locking [`set_config`](Self::set_config) returns
+    /// [`ConfigLocked`], the lock is only released by a reset of the RNG
+    /// peripheral.
+    pub fn lock_config(&mut self) {
+        // CONFIGLOCK is write once, the other bits must keep their value
+        self.rng.cr.modify(|_, w| w.configlock().set_bit());
+    }
+
+    /// Returns `true` if the configuration is locked.
+    pub fn is_config_locked(&self) -> bool {
+        self.rng.cr.read().configlock().bit_is_set()
     }
 
     /// Free the RNG peripheral from the driver.
@@ -160,7 +708,8 @@ impl Rng {
     /// You are also responsible for ensuring the RNG peripheral has been setup
     /// correctly.
     ///
-    /// This will also reset the correctable noise error counter.
+    /// This will also reset the [`Health`] counters, and the
+    /// [`RetryPolicy`] to the default.
     ///
     /// # Example
     ///
@@ -177,7 +726,8 @@ impl Rng {
         let dp: pac::Peripherals = pac::Peripherals::steal();
         Rng {
             rng: dp.RNG,
-            err_cnt: 0,
+            retry: RetryPolicy::new(),
+            health: Health::new(),
         }
     }
 
@@ -222,14 +772,69 @@ impl Rng {
 
     /// Returns the number of correctable seed errors that have occured.
     ///
+    /// This is the same as [`Health::seed_recoveries`].
+    ///
     /// This counter will saturate when it hits the maximum value.
     pub fn seed_error_stat(&self) -> u32 {
-        self.err_cnt
+        self.health.seed_recoveries
     }
 
     /// Reset the correctable seed error counter to zero.
     pub fn reset_seed_error_stat(&mut self) {
-        self.err_cnt = 0
+        self.health.seed_recoveries = 0
+    }
+
+    /// Set the error recovery retry policy.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use stm32wl_hal::{
+    ///     pac,
+    ///     rng::{Clk, RetryPolicy, Rng},
+    /// };
+    ///
+    /// let mut dp: pac::Peripherals = pac::Peripherals::take().unwrap();
+    /// let mut rng = Rng::new(dp.RNG, Clk::MSI, &mut dp.RCC);
+    ///
+    /// const POLICY: RetryPolicy = RetryPolicy::new().set_seed_retries(8);
+    /// rng.set_retry_policy(POLICY);
+    /// assert_eq!(rng.retry_policy(), POLICY);
+    /// ```
+    pub fn set_retry_policy(&mut self, retry: RetryPolicy) {
+        self.retry = retry
+    }
+
+    /// Get the error recovery retry policy.
+    pub fn retry_policy(&self) -> RetryPolicy {
+        self.retry
+    }
+
+    /// Get the health counters.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use stm32wl_hal::{
+    ///     pac,
+    ///     rng::{Clk, Health, Rng},
+    /// };
+    ///
+    /// let mut dp: pac::Peripherals = pac::Peripherals::take().unwrap();
+    /// let mut rng = Rng::new(dp.RNG, Clk::MSI, &mut dp.RCC);
+    ///
+    /// // ... use rng
+    ///
+    /// let health: Health = rng.health();
+    /// let unrecovered: u32 = health.seed_errors() - health.seed_recoveries();
+    /// ```
+    pub fn health(&self) -> Health {
+        self.health
+    }
+
+    /// Reset all the health counters to zero.
+    pub fn reset_health(&mut self) {
+        self.health = Health::new()
     }
 
     fn poll_data_valid(&mut self) -> Result<(), Error> {
@@ -238,9 +843,9 @@ impl Rng {
             if sr.drdy().bit_is_set() {
                 return Ok(());
             } else if sr.secs().bit_is_set() {
-                self.recover_from_noise_error()?;
+                self.recover_from_seed_error()?;
             } else if sr.cecs().bit_is_set() {
-                return Err(Error::Clock);
+                self.recover_from_clock_error()?;
             }
         }
     }
@@ -440,9 +1045,29 @@ impl Rng {
     }
 
     // Reference manual section 22.3.7 "Error management"
-    fn recover_from_noise_error(&mut self) -> Result<(), Error> {
-        // software reset by writing CONDRST
-        // this automatically clears the seed error interrupt status
+    fn recover_from_seed_error(&mut self) -> Result<(), Error> {
+        self.health.seed_errors = self.health.seed_errors.saturating_add(1);
+
+        for _ in 0..self.retry.seed {
+            match self.restart_conditioning() {
+                Ok(()) => {
+                    self.health.seed_recoveries = self.health.seed_recoveries.saturating_add(1);
+                    return Ok(());
+                }
+                Err(Error::Seed) => (),
+                Err(Error::Clock) => self.recover_from_clock_error()?,
+            }
+        }
+
+        Err(Error::Seed)
+    }
+
+    fn restart_conditioning(&mut self) -> Result<(), Error> {
+        // clear the seed error interrupt status
+        // CEIS is rc_w0, writing 1 leaves it unchanged
+        self.rng.sr.write(|w| w.seis().clear_bit().ceis().set_bit());
+
+        // software reset by writing CONDRST, this restarts the conditioning
         self.rng.cr.modify(|_, w| w.condrst().set_bit());
         self.rng.cr.modify(|_, w| w.condrst().clear_bit());
 
@@ -453,13 +1078,45 @@ impl Rng {
             compiler_fence(SeqCst);
         }
 
-        let sr = self.rng.sr.read();
-        if sr.secs().bit_is_set() {
-            Err(Error::Seed)
-        } else {
-            self.err_cnt = self.err_cnt.saturating_add(1);
-            Ok(())
+        // the FIFO may contain words generated from the faulty noise source,
+        // read them out and discard them
+        for _ in 0..FIFO_DW {
+            loop {
+                let sr = self.rng.sr.read();
+                if sr.secs().bit_is_set() {
+                    return Err(Error::Seed);
+                } else if sr.cecs().bit_is_set() {
+                    return Err(Error::Clock);
+                } else if sr.drdy().bit_is_set() {
+                    break;
+                }
+            }
+            self.rng.dr.read();
+            self.health.discarded = self.health.discarded.saturating_add(1);
+        }
+
+        Ok(())
+    }
+
+    // Reference manual section 22.3.7 "Error management"
+    fn recover_from_clock_error(&mut self) -> Result<(), Error> {
+        self.health.clock_errors = self.health.clock_errors.saturating_add(1);
+
+        // clear the clock error interrupt status
+        // SEIS is rc_w0, writing 1 leaves it unchanged
+        self.rng.sr.write(|w| w.ceis().clear_bit().seis().set_bit());
+
+        // CECS is cleared by hardware when the RNG clock frequency is
+        // correct again, the retry count is a number of polls of the status
+        // register and not a number of clock cycles
+        for _ in 0..self.retry.clock {
+            if self.rng.sr.read().cecs().bit_is_clear() {
+                self.health.clock_recoveries = self.health.clock_recoveries.saturating_add(1);
+                return Ok(());
+            }
         }
+
+        Err(Error::Clock)
     }
 }
 
@@ -0,0 +1,650 @@
+use super::Rtc;
+use crate::pac;
+use chrono::{NaiveTime, Timelike, Weekday};
+
+// RTC alarms are connected to EXTI line 17
+#[cfg(feature = "stm32wl5x_cm0p")]
+const EXTI_ALARM: u8 = 17;
+
+// RTC weekdays are 1 (Monday) to 7 (Sunday)
+const fn weekday_from_wdu(wdu: u8) -> Option<Weekday> {
+    match wdu {
+        1 => Some(Weekday::Mon),
+        2 => Some(Weekday::Tue),
+        3 => Some(Weekday::Wed),
+        4 => Some(Weekday::Thu),
+        5 => Some(Weekday::Fri),
+        6 => Some(Weekday::Sat),
+        7 => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+/// RTC alarm selection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Alarm {
+    /// Alarm A.
+    A,
+    /// Alarm B.
+    B,
+}
+
+/// RTC alarm configuration.
+///
+/// Argument of [`Rtc::set_alarm`].
+///
+/// Every field starts masked, a masked field is "don't care" in the alarm
+/// comparison.
+/// Setting a field unmasks it.
+/// With all fields masked the alarm triggers every second.
+///
+/// The alarm uses the 24-hour format.
+///
+/// # Example
+///
+/// Alarm every day at 06:30:00.
+///
+/// ```
+/// use stm32wl_hal::rtc::AlarmCfg;
+///
+/// const ALARM: AlarmCfg = AlarmCfg::new().set_hours(6).set_minutes(30).set_seconds(0);
+/// # assert_eq!(ALARM.hours(), Some(6));
+/// # assert_eq!(ALARM.day(), None);
+/// ```
+///
+/// Alarm every minute, 250 ms after the minute (with a 1 Hz calendar clocked
+/// by the LSE the synchronous prescaler counts down from 255).
+///
+/// ```
+/// use stm32wl_hal::rtc::AlarmCfg;
+///
+/// const ALARM: AlarmCfg = AlarmCfg::new().set_seconds(0).set_subseconds(191, 8);
+/// # assert_eq!(ALARM.subseconds(), (191, 8));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct AlarmCfg {
+    seconds: u8,
+    seconds_mask: bool,
+    minutes: u8,
+    minutes_mask: bool,
+    hours: u8,
+    hours_mask: bool,
+    // day of the month, or day of the week when wdsel is set
+    day: u8,
+    wdsel: bool,
+    day_mask: bool,
+    ss: u16,
+    maskss: u8,
+}
+
+impl AlarmCfg {
+    /// Create a new alarm configuration with all fields masked.
+    ///
+    /// This is the same as `default`, but in a `const` function.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stm32wl_hal::rtc::AlarmCfg;
+    ///
+    /// const ALARM: AlarmCfg = AlarmCfg::new();
+    /// assert_eq!(ALARM, AlarmCfg::default());
+    /// assert_eq!(ALARM.seconds(), None);
+    /// ```
+    pub const fn new() -> AlarmCfg {
+        AlarmCfg {
+            seconds: 0,
+            seconds_mask: true,
+            minutes: 0,
+            minutes_mask: true,
+            hours: 0,
+            hours_mask: true,
+            day: 0,
+            wdsel: false,
+            day_mask: true,
+            ss: 0,
+            maskss: 0,
+        }
+    }
+
+    /// Set the seconds to match.
+    ///
+    /// # Panics
+    ///
+    /// * `seconds` is greater than 59.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stm32wl_hal::rtc::AlarmCfg;
+    ///
+    /// const ALARM: AlarmCfg = AlarmCfg::new().set_seconds(42);
+    /// assert_eq!(ALARM.seconds(), Some(42));
+    /// ```
+    #[must_use = "set_seconds returns a modified AlarmCfg"]
+    pub const fn set_seconds(mut self, seconds: u8) -> AlarmCfg {
+        ::core::assert!(seconds < 60);
+        self.seconds = seconds;
+        self.seconds_mask = false;
+        self
+    }
+
+    /// Set the minutes to match.
+    ///
+    /// # Panics
+    ///
+    /// * `minutes` is greater than 59.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stm32wl_hal::rtc::AlarmCfg;
+    ///
+    /// const ALARM: AlarmCfg = AlarmCfg::new().set_minutes(15);
+    /// assert_eq!(ALARM.minutes(), Some(15));
+    /// ```
+    #[must_use = "set_minutes returns a modified AlarmCfg"]
+    pub const fn set_minutes(mut self, minutes: u8) -> AlarmCfg {
+        ::core::assert!(minutes < 60);
+        self.minutes = minutes;
+        self.minutes_mask = false;
+        self
+    }
+
+    /// Set the hours to match, in 24-hour format.
+    ///
+    /// # Panics
+    ///
+    /// * `hours` is greater than 23.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stm32wl_hal::rtc::AlarmCfg;
+    ///
+    /// const ALARM: AlarmCfg = AlarmCfg::new().set_hours(23);
+    /// assert_eq!(ALARM.hours(), Some(23));
+    /// ```
+    #[must_use = "set_hours returns a modified AlarmCfg"]
+    pub const fn set_hours(mut self, hours: u8) -> AlarmCfg {
+        ::core::assert!(hours < 24);
+        self.hours = hours;
+        self.hours_mask = false;
+        self
+    }
+
+    /// Set the hours, minutes, and seconds to match.
+    ///
+    /// Fractional seconds are ignored, use
+    /// [`set_subseconds`](Self::set_subseconds) for sub-second matching.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stm32wl_hal::{chrono::NaiveTime, rtc::AlarmCfg};
+    ///
+    /// let alarm: AlarmCfg = AlarmCfg::new().set_time(NaiveTime::from_hms(12, 34, 56));
+    /// assert_eq!(alarm.hours(), Some(12));
+    /// assert_eq!(alarm.minutes(), Some(34));
+    /// assert_eq!(alarm.seconds(), Some(56));
+    /// ```
+    #[must_use = "set_time returns a modified AlarmCfg"]
+    pub fn set_time(self, time: NaiveTime) -> AlarmCfg {
+        self.set_hours(time.hour() as u8)
+            .set_minutes(time.minute() as u8)
+            .set_seconds(time.second() as u8)
+    }
+
+    /// Set the day of the month to match.
+    ///
+    /// This replaces the weekday set by [`set_weekday`](Self::set_weekday).
+    ///
+    /// # Panics
+    ///
+    /// * `day` is zero or greater than 31.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stm32wl_hal::rtc::AlarmCfg;
+    ///
+    /// const ALARM: AlarmCfg = AlarmCfg::new().set_day(1).set_hours(0);
+    /// assert_eq!(ALARM.day(), Some(1));
+    /// assert_eq!(ALARM.weekday(), None);
+    /// ```
+    #[must_use = "set_day returns a modified AlarmCfg"]
+    pub const fn set_day(mut self, day: u8) -> AlarmCfg {
+        ::core::assert!(day != 0 && day <= 31);
+        self.day = day;
+        self.wdsel = false;
+        self.day_mask = false;
+        self
+    }
+
+    /// Set the day of the week to match.
+    ///
+    /// This replaces the day of the month set by [`set_day`](Self::set_day).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stm32wl_hal::{chrono::Weekday, rtc::AlarmCfg};
+    ///
+    /// const ALARM: AlarmCfg = AlarmCfg::new().set_weekday(Weekday::Fri).set_hours(17);
+    /// assert_eq!(ALARM.weekday(), Some(Weekday::Fri));
+    /// assert_eq!(ALARM.day(), None);
+    /// ```
+    #[must_use = "set_weekday returns a modified AlarmCfg"]
+    pub const fn set_weekday(mut self, weekday: Weekday) -> AlarmCfg {
+        // RTC weekdays are 1 (Monday) to 7 (Sunday)
+        self.day = weekday as u8 + 1;
+        self.wdsel = true;
+        self.day_mask = false;
+        self
+    }
+
+    /// Set the seconds mask.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stm32wl_hal::rtc::AlarmCfg;
+    ///
+    /// const ALARM: AlarmCfg = AlarmCfg::new().set_seconds(42).set_seconds_mask(true);
+    /// assert_eq!(ALARM.seconds(), None);
+    /// ```
+    #[must_use = "set_seconds_mask returns a modified AlarmCfg"]
+    pub const fn set_seconds_mask(mut self, mask: bool) -> AlarmCfg {
+        self.seconds_mask = mask;
+        self
+    }
+
+    /// Set the minutes mask.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stm32wl_hal::rtc::AlarmCfg;
+    ///
+    /// const ALARM: AlarmCfg = AlarmCfg::new().set_minutes_mask(false);
+    /// assert_eq!(ALARM.minutes(), Some(0));
+    /// ```
+    #[must_use = "set_minutes_mask returns a modified AlarmCfg"]
+    pub const fn set_minutes_mask(mut self, mask: bool) -> AlarmCfg {
+        self.minutes_mask = mask;
+        self
+    }
+
+    /// Set the hours mask.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stm32wl_hal::rtc::AlarmCfg;
+    ///
+    /// const ALARM: AlarmCfg = AlarmCfg::new().set_hours(8).set_hours_mask(true);
+    /// assert_eq!(ALARM.hours(), None);
+    /// ```
+    #[must_use = "set_hours_mask returns a modified AlarmCfg"]
+    pub const fn set_hours_mask(mut self, mask: bool) -> AlarmCfg {
+        self.hours_mask = mask;
+        self
+    }
+
+    /// Set the day mask, this masks both the day of the month and the day of
+    /// the week.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stm32wl_hal::rtc::AlarmCfg;
+    ///
+    /// const ALARM: AlarmCfg = AlarmCfg::new().set_day(15).set_day_mask(true);
+    /// assert_eq!(ALARM.day(), None);
+    /// ```
+    #[must_use = "set_day_mask returns a modified AlarmCfg"]
+    pub const fn set_day_mask(mut self, mask: bool) -> AlarmCfg {
+        self.day_mask = mask;
+        self
+    }
+
+    /// Set the sub-seconds to match.
+    ///
+    /// `ss` is compared to the synchronous prescaler counter
+    /// (RTC_SSR), which counts down from `PREDIV_S` to zero every second.
+    ///
+    /// Only the `bits` least significant bits of `ss` are compared.
+    /// Zero bits disables sub-second matching (the default), and 15 bits
+    /// compares the entire sub-second value.
+    ///
+    /// # Panics
+    ///
+    /// * `ss` is greater than `0x7FFF`.
+    /// * `bits` is greater than 15.
+    ///
+    /// # Example
+    ///
+    /// Alarm at the half-second with a 32.768 kHz LSE.
+    ///
+    /// ```
+    /// use stm32wl_hal::rtc::AlarmCfg;
+    ///
+    /// const ALARM: AlarmCfg = AlarmCfg::new().set_subseconds(127, 15);
+    /// assert_eq!(ALARM.subseconds(), (127, 15));
+    /// ```
+    #[must_use = "set_subseconds returns a modified AlarmCfg"]
+    pub const fn set_subseconds(mut self, ss: u16, bits: u8) -> AlarmCfg {
+        ::core::assert!(ss <= 0x7FFF);
+        ::core::assert!(bits <= 15);
+        self.ss = ss;
+        self.maskss = bits;
+        self
+    }
+
+    /// Seconds to match, `None` if masked.
+    pub const fn seconds(&self) -> Option<u8> {
+        if self.seconds_mask {
+            None
+        } else {
+            Some(self.seconds)
+        }
+    }
+
+    /// Minutes to match, `None` if masked.
+    pub const fn minutes(&self) -> Option<u8> {
+        if self.minutes_mask {
+            None
+        } else {
+            Some(self.minutes)
+        }
+    }
+
+    /// Hours to match, `None` if masked.
+    pub const fn hours(&self) -> Option<u8> {
+        if self.hours_mask {
+            None
+        } else {
+            Some(self.hours)
+        }
+    }
+
+    /// Day of the month to match, `None` if masked or if matching on the
+    /// day of the week.
+    pub const fn day(&self) -> Option<u8> {
+        if self.day_mask || self.wdsel {
+            None
+        } else {
+            Some(self.day)
+        }
+    }
+
+    /// Day of the week to match, `None` if masked or if matching on the
+    /// day of the month.
+    pub const fn weekday(&self) -> Option<Weekday> {
+        if self.day_mask || !self.wdsel {
+            None
+        } else {
+            weekday_from_wdu(self.day)
+        }
+    }
+
+    /// Sub-seconds to match, and the number of bits compared.
+    pub const fn subseconds(&self) -> (u16, u8) {
+        (self.ss, self.maskss)
+    }
+}
+
+impl Default for AlarmCfg {
+    fn default() -> Self {
+        AlarmCfg::new()
+    }
+}
+
+// the alarm A and alarm B registers have the same fields with different types
+#[rustfmt::skip]
+macro_rules! write_alarm {
+    ($alrmr:expr, $alrmssr:expr, $cfg:expr) => {{
+        let cfg: &AlarmCfg = $cfg;
+        $alrmr.write(|w| {
+            w
+                .msk4().bit(cfg.day_mask)
+                .wdsel().bit(cfg.wdsel)
+                .dt().bits(cfg.day / 10)
+                .du().bits(cfg.day % 10)
+                .msk3().bit(cfg.hours_mask)
+                .pm().clear_bit() // 24h format
+                .ht().bits(cfg.hours / 10)
+                .hu().bits(cfg.hours % 10)
+                .msk2().bit(cfg.minutes_mask)
+                .mnt().bits(cfg.minutes / 10)
+                .mnu().bits(cfg.minutes % 10)
+                .msk1().bit(cfg.seconds_mask)
+                .st().bits(cfg.seconds / 10)
+                .su().bits(cfg.seconds % 10)
+        });
+        $alrmssr.write(|w| w.maskss().bits(cfg.maskss).ss().bits(cfg.ss));
+    }};
+}
+
+macro_rules! read_alarm {
+    ($alrmr:expr, $alrmssr:expr) => {{
+        let alrmr = $alrmr.read();
+        let alrmssr = $alrmssr.read();
+        AlarmCfg {
+            seconds: alrmr.st().bits() * 10 + alrmr.su().bits(),
+            seconds_mask: alrmr.msk1().bit_is_set(),
+            minutes: alrmr.mnt().bits() * 10 + alrmr.mnu().bits(),
+            minutes_mask: alrmr.msk2().bit_is_set(),
+            hours: alrmr.ht().bits() * 10 + alrmr.hu().bits(),
+            hours_mask: alrmr.msk3().bit_is_set(),
+            day: if alrmr.wdsel().bit_is_set() {
+                alrmr.du().bits()
+            } else {
+                alrmr.dt().bits() * 10 + alrmr.du().bits()
+            },
+            wdsel: alrmr.wdsel().bit_is_set(),
+            day_mask: alrmr.msk4().bit_is_set(),
+            ss: alrmssr.ss().bits(),
+            maskss: alrmssr.maskss().bits(),
+        }
+    }};
+}
+
+impl Rtc {
+    /// Program and enable an alarm.
+    ///
+    /// The alarm is disabled while the alarm registers are written.
+    /// The alarm flag is set when the calendar matches the alarm
+    /// configuration, if enabled with [`set_alarm_irq`](Self::set_alarm_irq)
+    /// an interrupt is also generated.
+    ///
+    /// # Example
+    ///
+    /// Alarm every day at 06:30:00.
+    ///
+    /// ```no_run
+    /// use stm32wl_hal::rtc::{Alarm, AlarmCfg, Rtc};
+    ///
+    /// let mut rtc: Rtc = unsafe { Rtc::steal() };
+    ///
+    /// const ALARM: AlarmCfg = AlarmCfg::new().set_hours(6).set_minutes(30).set_seconds(0);
+    /// rtc.set_alarm(Alarm::A, &ALARM);
+    /// ```
+    pub fn set_alarm(&mut self, alarm: Alarm, cfg: &AlarmCfg) {
+        self.disable_alarm(alarm);
+        match alarm {
+            Alarm::A => write_alarm!(self.rtc.alrmar, self.rtc.alrmassr, cfg),
+            Alarm::B => write_alarm!(self.rtc.alrmbr, self.rtc.alrmbssr, cfg),
+        }
+        self.rtc.cr.modify(|_, w| match alarm {
+            Alarm::A => w.alrae().set_bit(),
+            Alarm::B => w.alrbe().set_bit(),
+        });
+    }
+
+    /// Read the alarm configuration.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use stm32wl_hal::rtc::{Alarm, AlarmCfg, Rtc};
+    ///
+    /// let mut rtc: Rtc = unsafe { Rtc::steal() };
+    ///
+    /// const ALARM: AlarmCfg = AlarmCfg::new().set_seconds(30);
+    /// rtc.set_alarm(Alarm::B, &ALARM);
+    /// assert_eq!(rtc.alarm(Alarm::B), ALARM);
+    /// ```
+    pub fn alarm(&self, alarm: Alarm) -> AlarmCfg {
+        match alarm {
+            Alarm::A => read_alarm!(self.rtc.alrmar, self.rtc.alrmassr),
+            Alarm::B => read_alarm!(self.rtc.alrmbr, self.rtc.alrmbssr),
+        }
+    }
+
+    /// Disable an alarm.
+    ///
+    /// This does not clear the alarm flag.
+    pub fn disable_alarm(&mut self, alarm: Alarm) {
+        self.rtc.cr.modify(|_, w| match alarm {
+            Alarm::A => w.alrae().clear_bit(),
+            Alarm::B => w.alrbe().clear_bit(),
+        });
+    }
+
+    /// Returns `true` if the alarm is enabled.
+    pub fn is_alarm_enabled(&self, alarm: Alarm) -> bool {
+        let cr: pac::rtc::cr::R = self.rtc.cr.read();
+        match alarm {
+            Alarm::A => cr.alrae().bit_is_set(),
+            Alarm::B => cr.alrbe().bit_is_set(),
+        }
+    }
+
+    /// Enable or disable the alarm interrupt.
+    ///
+    /// The interrupt must also be unmasked in the EXTI with
+    /// [`set_alarm_exti`](Self::set_alarm_exti), and in the NVIC.
+    ///
+    /// # Example
+    ///
+    /// Setup alarm A to wakeup the core from stop 2 mode.
+    ///
+    /// ```no_run
+    /// use stm32wl_hal::{
+    ///     pac,
+    ///     rtc::{Alarm, AlarmCfg, Rtc},
+    /// };
+    ///
+    /// let mut dp: pac::Peripherals = pac::Peripherals::take().unwrap();
+    /// let mut rtc: Rtc = unsafe { Rtc::steal() };
+    ///
+    /// rtc.set_alarm(Alarm::A, &AlarmCfg::new().set_seconds(0));
+    /// rtc.set_alarm_irq(Alarm::A, true);
+    /// Rtc::set_alarm_exti(&mut dp.EXTI, true);
+    /// ```
+    pub fn set_alarm_irq(&mut self, alarm: Alarm, en: bool) {
+        self.rtc.cr.modify(|_, w| match alarm {
+            Alarm::A => w.alraie().bit(en),
+            Alarm::B => w.alrbie().bit(en),
+        });
+    }
+
+    /// Returns `true` if the alarm flag is set.
+    ///
+    /// The flag is set when the calendar matches the alarm, regardless of
+    /// the interrupt enable.
+    pub fn is_alarm_pending(&self, alarm: Alarm) -> bool {
+        let sr: pac::rtc::sr::R = self.rtc.sr.read();
+        match alarm {
+            Alarm::A => sr.alraf().bit_is_set(),
+            Alarm::B => sr.alrbf().bit_is_set(),
+        }
+    }
+
+    /// Clear the alarm flag.
+    ///
+    /// This must be called in the interrupt handler, and before entering
+    /// standby mode, otherwise the pending flag will prevent or immediately
+    /// end the next low-power period.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use stm32wl_hal::rtc::{Alarm, Rtc};
+    ///
+    /// // in the RTC alarm interrupt handler
+    /// Rtc::clear_alarm(Alarm::A);
+    /// ```
+    #[inline]
+    pub fn clear_alarm(alarm: Alarm) {
+        // safety: atomic write to a write-1-to-clear register
+        unsafe {
+            (*pac::RTC::ptr()).scr.write(|w| match alarm {
+                Alarm::A => w.calraf().set_bit(),
+                Alarm::B => w.calrbf().set_bit(),
+            })
+        }
+    }
+
+    /// Set the interrupt mask for the RTC alarms in the EXTI.
+    ///
+    /// The RTC alarms are connected to EXTI line 17, a direct line with no
+    /// trigger selection or pending register.
+    /// Unmasking this line allows the alarms to wakeup the core from stop 0,
+    /// 1, and 2 modes.
+    ///
+    /// To wakeup from standby or shutdown modes use
+    /// [`set_internal_wakeup_line`](crate::pwr::set_internal_wakeup_line)
+    /// instead.
+    ///
+    /// This will not mask/unmask the IRQ in the NVIC.
+    ///
+    /// # Example
+    ///
+    /// See [`set_alarm_irq`](Self::set_alarm_irq).
+    #[inline]
+    pub fn set_alarm_exti(exti: &mut pac::EXTI, unmask: bool) {
+        #[cfg(not(feature = "stm32wl5x_cm0p"))]
+        exti.c1imr1.modify(|_, w| w.im17().bit(unmask));
+        #[cfg(feature = "stm32wl5x_cm0p")]
+        super::set_exti_c2_mask(exti, EXTI_ALARM, unmask);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::AlarmCfg;
+    use chrono::Weekday;
+
+    #[test]
+    fn alarm_cfg_fields() {
+        const ALARM: AlarmCfg = AlarmCfg::new()
+            .set_hours(23)
+            .set_minutes(59)
+            .set_seconds(58);
+        assert_eq!(ALARM.hours(), Some(23));
+        assert_eq!(ALARM.minutes(), Some(59));
+        assert_eq!(ALARM.seconds(), Some(58));
+        assert_eq!(ALARM.day(), None);
+        assert_eq!(ALARM.weekday(), None);
+
+        let weekday: AlarmCfg = AlarmCfg::new().set_weekday(Weekday::Sun);
+        assert_eq!(weekday.day, 7);
+        assert!(weekday.wdsel);
+        assert_eq!(weekday.weekday(), Some(Weekday::Sun));
+        assert_eq!(weekday.day(), None);
+
+        let day: AlarmCfg = weekday.set_day(2);
+        assert!(!day.wdsel);
+        assert_eq!(day.day(), Some(2));
+        assert_eq!(day.weekday(), None);
+
+        assert_eq!(
+            AlarmCfg::new().set_subseconds(0x7FFF, 15).subseconds(),
+            (0x7FFF, 15)
+        );
+    }
+}
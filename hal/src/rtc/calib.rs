@@ -0,0 +1,465 @@
+use super::{Mode, Rtc};
+use crate::pac;
+use core::time::Duration;
+
+// maximum value of the 9-bit CALM field
+const CALM_MAX: u16 = 0x1FF;
+
+// number of RTCCLK cycles in the 32 second calibration window
+const CAL_CYCLES: f32 = (1 << 20) as f32;
+
+// CALP inserts 512 RTCCLK pulses per calibration window
+const CALP_PULSES: i32 = 512;
+
+/// RTC calibration and shift errors.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum CalError {
+    /// The frequency error is above the correction range.
+    ///
+    /// The value is the upper limit in ppm.
+    PpmTooHigh(f32),
+    /// The frequency error is below the correction range.
+    ///
+    /// The value is the lower limit in ppm.
+    PpmTooLow(f32),
+    /// The shift is one second or longer, use
+    /// [`Rtc::set_date_time`] instead.
+    ShiftTooLong,
+    /// The shift requires the calendar to be in [`Mode::Bcd`].
+    ShiftMode,
+}
+
+/// Smooth calibration window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum CalWindow {
+    /// 32 second window, 0.954 ppm resolution.
+    Sec32,
+    /// 16 second window, 1.907 ppm resolution.
+    Sec16,
+    /// 8 second window, 3.815 ppm resolution.
+    Sec8,
+}
+
+impl CalWindow {
+    // CALM bits that are ignored in the window
+    const fn granularity(&self) -> i32 {
+        match self {
+            CalWindow::Sec32 => 1,
+            CalWindow::Sec16 => 2,
+            CalWindow::Sec8 => 4,
+        }
+    }
+}
+
+// frequency error in ppm corrected by (CALP × 512 - CALM)
+//
+// RM0453 Rev 2 32.3.12 "RTC smooth digital calibration":
+// FCAL = FRTCCLK × [1 + (CALP × 512 - CALM) / (2^20 + CALM - CALP × 512)]
+fn pulses_to_ppm(pulses: i32) -> f32 {
+    -(pulses as f32) / (CAL_CYCLES - pulses as f32) * 1e6
+}
+
+/// RTC smooth calibration.
+///
+/// Argument of [`Rtc::set_calibration`].
+///
+/// The smooth calibration masks or inserts RTCCLK pulses over a 8, 16, or 32
+/// second window to correct the frequency of the RTC clock.
+/// The correction range is -487.1 ppm to +488.5 ppm.
+///
+/// # Example
+///
+/// Correct a LSE that is measured to be 20 ppm fast.
+///
+/// ```
+/// use stm32wl_hal::rtc::{CalWindow, Calibration};
+///
+/// let cal: Calibration = Calibration::from_ppm_error(20.0, CalWindow::Sec32)?;
+/// assert_eq!(cal.calm(), 21);
+/// assert!(!cal.calp());
+/// # Ok::<(), stm32wl_hal::rtc::CalError>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Calibration {
+    calp: bool,
+    calm: u16,
+    window: CalWindow,
+    lpcal: bool,
+}
+
+impl Calibration {
+    /// No calibration.
+    ///
+    /// This is the same as `default`, and the reset value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stm32wl_hal::rtc::Calibration;
+    ///
+    /// assert_eq!(Calibration::NONE, Calibration::default());
+    /// assert_eq!(Calibration::NONE.ppm_error(), 0.0);
+    /// ```
+    pub const NONE: Calibration = Calibration {
+        calp: false,
+        calm: 0,
+        window: CalWindow::Sec32,
+        lpcal: false,
+    };
+
+    /// Create a calibration value from the raw CALP and CALM fields.
+    ///
+    /// CALM is the number of RTCCLK pulses masked over the 32 second
+    /// window, and CALP inserts 512 pulses.
+    /// The CALM bits that are not used by the window are cleared.
+    ///
+    /// # Panics
+    ///
+    /// * `calm` is greater than 511.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stm32wl_hal::rtc::{CalWindow, Calibration};
+    ///
+    /// const CAL: Calibration = Calibration::new(false, 7, CalWindow::Sec8);
+    /// assert_eq!(CAL.calm(), 4);
+    /// assert_eq!(CAL.window(), CalWindow::Sec8);
+    /// ```
+    pub const fn new(calp: bool, calm: u16, window: CalWindow) -> Calibration {
+        ::core::assert!(calm <= CALM_MAX);
+        Calibration {
+            calp,
+            calm: calm & !(window.granularity() as u16 - 1),
+            window,
+            lpcal: false,
+        }
+    }
+
+    /// Create a calibration value that corrects a measured frequency error.
+    ///
+    /// `ppm` is the measured error of the RTC clock in parts per million,
+    /// positive if the clock is fast, negative if the clock is slow.
+    ///
+    /// The result is rounded to the resolution of the window, use
+    /// [`ppm_error`](Self::ppm_error) to get the corrected error.
+    ///
+    /// # Errors
+    ///
+    /// * [`CalError::PpmTooHigh`] the error is above +487.1 ppm
+    ///   (less with 8 and 16 second windows).
+    /// * [`CalError::PpmTooLow`] the error is below -488.5 ppm.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stm32wl_hal::rtc::{CalWindow, Calibration};
+    ///
+    /// let cal: Calibration = Calibration::from_ppm_error(-30.0, CalWindow::Sec8)?;
+    /// assert!(cal.calp());
+    /// assert_eq!(cal.calm(), 480);
+    /// assert!((cal.ppm_error() + 30.0).abs() < 3.815);
+    ///
+    /// assert!(Calibration::from_ppm_error(500.0, CalWindow::Sec32).is_err());
+    /// assert!(Calibration::from_ppm_error(-500.0, CalWindow::Sec32).is_err());
+    /// # Ok::<(), stm32wl_hal::rtc::CalError>(())
+    /// ```
+    pub fn from_ppm_error(ppm: f32, window: CalWindow) -> Result<Calibration, CalError> {
+        let g: i32 = window.granularity();
+        let max_pulses: i32 = CALP_PULSES;
+        let min_pulses: i32 = -(CALP_PULSES - g);
+
+        // solve for pulses = CALP × 512 - CALM
+        let e: f32 = ppm / 1e6;
+        let pulses: f32 = -e * CAL_CYCLES / (1.0 - e) / g as f32;
+        // round half away from zero, float round is not available in core
+        let pulses: i32 = if pulses < 0.0 {
+            (pulses - 0.5) as i32
+        } else {
+            (pulses + 0.5) as i32
+        } * g;
+
+        if pulses > max_pulses {
+            Err(CalError::PpmTooLow(pulses_to_ppm(max_pulses)))
+        } else if pulses < min_pulses {
+            Err(CalError::PpmTooHigh(pulses_to_ppm(min_pulses)))
+        } else if pulses > 0 {
+            Ok(Calibration::new(
+                true,
+                (CALP_PULSES - pulses) as u16,
+                window,
+            ))
+        } else {
+            Ok(Calibration::new(false, (-pulses) as u16, window))
+        }
+    }
+
+    /// Returns the frequency error in ppm that this calibration corrects.
+    ///
+    /// This has the same sign convention as
+    /// [`from_ppm_error`](Self::from_ppm_error).
+    pub fn ppm_error(&self) -> f32 {
+        let calp: i32 = if self.calp() { CALP_PULSES } else { 0 };
+        pulses_to_ppm(calp - i32::from(self.calm()))
+    }
+
+    /// Returns `true` if 512 pulses are inserted every 32 seconds.
+    pub const fn calp(&self) -> bool {
+        self.calp
+    }
+
+    /// Number of pulses masked every 32 seconds.
+    pub const fn calm(&self) -> u16 {
+        self.calm
+    }
+
+    /// Calibration window.
+    pub const fn window(&self) -> CalWindow {
+        self.window
+    }
+
+    /// Set the low-power calibration mode.
+    ///
+    /// In low-power mode the calibration window is 2<sup>20</sup> ck_apre
+    /// cycles instead of 2<sup>20</sup> RTCCLK cycles.
+    /// This reduces power consumption, the window duration is multiplied by
+    /// the asynchronous prescaler (PREDIV_A + 1).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stm32wl_hal::rtc::{CalWindow, Calibration};
+    ///
+    /// const CAL: Calibration = Calibration::new(false, 10, CalWindow::Sec32).set_low_power(true);
+    /// assert!(CAL.low_power());
+    /// ```
+    #[must_use = "set_low_power returns a modified Calibration"]
+    pub const fn set_low_power(mut self, en: bool) -> Calibration {
+        self.lpcal = en;
+        self
+    }
+
+    /// Returns `true` if the low-power calibration mode is enabled.
+    pub const fn low_power(&self) -> bool {
+        self.lpcal
+    }
+}
+
+impl Default for Calibration {
+    fn default() -> Self {
+        Calibration::NONE
+    }
+}
+
+impl Rtc {
+    /// Set the smooth calibration.
+    ///
+    /// This waits for any previous calibration to apply before writing the
+    /// new calibration.
+    /// The calibration is applied at the start of the next calibration
+    /// window, the calendar is not reset.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use stm32wl_hal::rtc::{CalWindow, Calibration, Rtc};
+    ///
+    /// let mut rtc: Rtc = unsafe { Rtc::steal() };
+    ///
+    /// // measured against network time
+    /// let ppm: f32 = 12.5;
+    /// rtc.set_calibration(&Calibration::from_ppm_error(ppm, CalWindow::Sec32)?);
+    /// # Ok::<(), stm32wl_hal::rtc::CalError>(())
+    /// ```
+    pub fn set_calibration(&mut self, cal: &Calibration) {
+        while self.rtc.icsr.read().recalpf().bit_is_set() {}
+        #[rustfmt::skip]
+        self.rtc.calr.write(|w| {
+            w
+                .calp().bit(cal.calp)
+                .calw8().bit(cal.window == CalWindow::Sec8)
+                .calw16().bit(cal.window == CalWindow::Sec16)
+                .lpcal().bit(cal.lpcal)
+                .calm().bits(cal.calm)
+        });
+    }
+
+    /// Read the smooth calibration.
+    pub fn calibration(&self) -> Calibration {
+        let calr: pac::rtc::calr::R = self.rtc.calr.read();
+        Calibration {
+            calp: calr.calp().bit_is_set(),
+            calm: calr.calm().bits(),
+            window: if calr.calw8().bit_is_set() {
+                CalWindow::Sec8
+            } else if calr.calw16().bit_is_set() {
+                CalWindow::Sec16
+            } else {
+                CalWindow::Sec32
+            },
+            lpcal: calr.lpcal().bit_is_set(),
+        }
+    }
+
+    // RM0453 Rev 2 32.3.11 "RTC synchronization"
+    fn shift(&mut self, add1s: bool, subfs: u16) {
+        while self.rtc.icsr.read().shpf().bit_is_set() {}
+        self.rtc
+            .shiftr
+            .write(|w| w.add1s().bit(add1s).subfs().bits(subfs));
+        while self.rtc.icsr.read().shpf().bit_is_set() {}
+    }
+
+    // sub-second counter ticks per second and ticks in the duration
+    fn shift_ticks(&self, d: Duration) -> Result<(u32, u32), CalError> {
+        if d >= Duration::from_secs(1) {
+            return Err(CalError::ShiftTooLong);
+        }
+        if self.mode() != Mode::Bcd {
+            return Err(CalError::ShiftMode);
+        }
+        let modulus: u32 = u32::from(self.rtc.prer.read().prediv_s().bits()) + 1;
+        let ticks: u64 =
+            (u64::from(d.subsec_nanos()) * u64::from(modulus) + 500_000_000) / 1_000_000_000;
+        Ok((modulus, ticks as u32))
+    }
+
+    /// Advance the calendar by a fraction of a second.
+    ///
+    /// The shift is rounded to the sub-second counter resolution,
+    /// 1 / (PREDIV_S + 1) seconds.
+    /// This is 1/256 seconds with the LSE.
+    ///
+    /// Returns the actual shift.
+    ///
+    /// # Errors
+    ///
+    /// * [`CalError::ShiftTooLong`] the shift is one second or longer, use
+    ///   [`set_date_time`](Self::set_date_time) instead.
+    /// * [`CalError::ShiftMode`] the calendar is not in [`Mode::Bcd`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use core::time::Duration;
+    /// use stm32wl_hal::rtc::Rtc;
+    ///
+    /// let mut rtc: Rtc = unsafe { Rtc::steal() };
+    ///
+    /// // network time is 250 ms ahead of the RTC
+    /// let shift: Duration = rtc.shift_ahead(Duration::from_millis(250))?;
+    /// assert_eq!(shift, Duration::from_millis(250));
+    /// # Ok::<(), stm32wl_hal::rtc::CalError>(())
+    /// ```
+    pub fn shift_ahead(&mut self, d: Duration) -> Result<Duration, CalError> {
+        let (modulus, ticks) = self.shift_ticks(d)?;
+        if ticks != 0 {
+            // add one second, and subtract the remainder
+            self.shift(true, (modulus - ticks) as u16);
+        }
+        Ok(shift_duration(ticks, modulus))
+    }
+
+    /// Delay the calendar by a fraction of a second.
+    ///
+    /// The shift is rounded to the sub-second counter resolution,
+    /// 1 / (PREDIV_S + 1) seconds.
+    /// This is 1/256 seconds with the LSE.
+    ///
+    /// Returns the actual shift.
+    ///
+    /// # Errors
+    ///
+    /// * [`CalError::ShiftTooLong`] the shift is one second or longer, use
+    ///   [`set_date_time`](Self::set_date_time) instead.
+    /// * [`CalError::ShiftMode`] the calendar is not in [`Mode::Bcd`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use core::time::Duration;
+    /// use stm32wl_hal::rtc::Rtc;
+    ///
+    /// let mut rtc: Rtc = unsafe { Rtc::steal() };
+    ///
+    /// // network time is 10 ms behind the RTC
+    /// let shift: Duration = rtc.shift_back(Duration::from_millis(10))?;
+    /// # Ok::<(), stm32wl_hal::rtc::CalError>(())
+    /// ```
+    pub fn shift_back(&mut self, d: Duration) -> Result<Duration, CalError> {
+        let (modulus, ticks) = self.shift_ticks(d)?;
+        // a shift that rounds up to one second is limited to one tick less
+        let ticks: u32 = ticks.min(modulus - 1);
+        if ticks != 0 {
+            self.shift(false, ticks as u16);
+        }
+        Ok(shift_duration(ticks, modulus))
+    }
+}
+
+fn shift_duration(ticks: u32, modulus: u32) -> Duration {
+    Duration::from_nanos(u64::from(ticks) * 1_000_000_000 / u64::from(modulus))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{shift_duration, CalError, CalWindow, Calibration};
+    use core::time::Duration;
+
+    #[test]
+    fn ppm_round_trip() {
+        let windows: [CalWindow; 3] = [CalWindow::Sec32, CalWindow::Sec16, CalWindow::Sec8];
+        for &window in windows.iter() {
+            let resolution: f32 = match window {
+                CalWindow::Sec32 => 0.954,
+                CalWindow::Sec16 => 1.907,
+                CalWindow::Sec8 => 3.815,
+            };
+            let mut ppm: f32 = -488.0;
+            while ppm < 480.0 {
+                let cal: Calibration = Calibration::from_ppm_error(ppm, window).unwrap();
+                assert_eq!(cal.window(), window);
+                assert!(
+                    (cal.ppm_error() - ppm).abs() <= resolution / 2.0 + 0.01,
+                    "{} {:?}",
+                    ppm,
+                    cal
+                );
+                ppm += 0.37;
+            }
+        }
+    }
+
+    #[test]
+    fn ppm_limits() {
+        assert_eq!(
+            Calibration::from_ppm_error(-488.5, CalWindow::Sec32),
+            Ok(Calibration::new(true, 0, CalWindow::Sec32))
+        );
+        assert_eq!(
+            Calibration::from_ppm_error(487.1, CalWindow::Sec32),
+            Ok(Calibration::new(false, 511, CalWindow::Sec32))
+        );
+        assert_eq!(
+            Calibration::from_ppm_error(0.0, CalWindow::Sec8),
+            Ok(Calibration::new(false, 0, CalWindow::Sec8))
+        );
+        assert!(matches!(
+            Calibration::from_ppm_error(-490.0, CalWindow::Sec32),
+            Err(CalError::PpmTooLow(_))
+        ));
+        match Calibration::from_ppm_error(487.1, CalWindow::Sec8) {
+            Err(CalError::PpmTooHigh(limit)) => assert!((limit - 484.2).abs() < 0.1),
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn shift() {
+        assert_eq!(shift_duration(64, 256), Duration::from_millis(250));
+        assert_eq!(shift_duration(256, 256), Duration::from_secs(1));
+    }
+}
@@ -0,0 +1,669 @@
+//! Real-time clock.
+
+mod alarm;
+mod calib;
+mod wakeup;
+
+pub use alarm::{Alarm, AlarmCfg};
+pub use calib::{CalError, CalWindow, Calibration};
+
+use crate::{pac, rcc::lsi_hz};
+
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+
+use pac::{
+    rcc::{
+        bdcr::RTCSEL_A,
+        csr::LSIPRE_A::{DIV1, DIV128},
+    },
+    rtc::icsr::BIN_A,
+};
+
+/// RTC clock selection
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[repr(u8)]
+pub enum Clk {
+    /// LSE oscillator clock selected.
+    Lse = RTCSEL_A::LSE as u8,
+    /// LSI oscillator clock selected.
+    Lsi = RTCSEL_A::LSI as u8,
+    /// HSE32 oscillator clock divided by 32 selected.
+    Hse = RTCSEL_A::HSE32 as u8,
+}
+
+/// Calendar mode.
+///
+/// Argument of [`Rtc::set_mode`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[repr(u8)]
+pub enum Mode {
+    /// BCD calendar, the sub-second counter is reloaded every second.
+    ///
+    /// This is the reset value.
+    Bcd = BIN_A::BCD as u8,
+    /// Free-running 32-bit binary counter, the BCD calendar is not used.
+    Binary = BIN_A::BINARY as u8,
+    /// BCD calendar and free-running 32-bit binary counter.
+    ///
+    /// The calendar second is incremented from the binary counter.
+    Mixed = BIN_A::BINBCD as u8,
+}
+
+// BCDU value for a 1 Hz calendar from a binary counter frequency
+fn bcdu(counter_hz: u32) -> Option<u8> {
+    if counter_hz.is_power_of_two() && (256..=32_768).contains(&counter_hz) {
+        Some((counter_hz.trailing_zeros() - 8) as u8)
+    } else {
+        None
+    }
+}
+
+// fraction of a second in nanoseconds from the sub-second down-counter
+//
+// RM0453 Rev 2 page 1012
+// SS[15:0] is the value in the synchronous prescaler counter.
+// The fraction of a second is given by the formula below:
+// Second fraction = (PREDIV_S - SS) / (PREDIV_S + 1)
+//
+// In mixed mode the modulus is 2^(8 + BCDU) instead of PREDIV_S + 1.
+fn subsec_nanos(ss: u32, modulus: u32) -> u32 {
+    let ss: u64 = (ss % modulus).into();
+    let modulus: u64 = modulus.into();
+    ((modulus - 1 - ss) * 1_000_000_000 / modulus) as u32
+}
+
+// fraction of a second in nanoseconds from the sub-second down-counter in BCD
+// mode, and `true` if the calendar is one second ahead
+//
+// RM0453 Rev 2 page 1012
+// SS can be larger than PREDIV_S only after a shift operation.
+// In that case, the correct time/date is one second less than as
+// indicated by RTC_TR/RTC_DR.
+//
+// The fraction of a second is then (2 × PREDIV_S + 1 - SS) / (PREDIV_S + 1).
+fn bcd_subsec_nanos(ss: u32, pre_s: u32) -> (u32, bool) {
+    let ss: u32 = ss & 0xFFFF;
+    if ss > pre_s {
+        let modulus: u64 = u64::from(pre_s) + 1;
+        let frac: u64 = (2 * u64::from(pre_s) + 1).saturating_sub(ss.into());
+        ((frac * 1_000_000_000 / modulus) as u32, true)
+    } else {
+        (subsec_nanos(ss, pre_s + 1), false)
+    }
+}
+
+fn decode_date(dr: u32) -> Option<NaiveDate> {
+    let yt: i32 = ((dr >> 20) & 0xF) as i32;
+    let yu: i32 = ((dr >> 16) & 0xF) as i32;
+    let mt: u32 = (dr >> 12) & 0x1;
+    let mu: u32 = (dr >> 8) & 0xF;
+    let dt: u32 = (dr >> 4) & 0x3;
+    let du: u32 = dr & 0xF;
+    NaiveDate::from_ymd_opt(2000 + yt * 10 + yu, mt * 10 + mu, dt * 10 + du)
+}
+
+fn decode_time(tr: u32, nano: u32) -> Option<NaiveTime> {
+    let pm: bool = tr & (1 << 22) != 0;
+    let ht: u32 = (tr >> 20) & 0x3;
+    let hu: u32 = (tr >> 16) & 0xF;
+    let mnt: u32 = (tr >> 12) & 0x7;
+    let mnu: u32 = (tr >> 8) & 0xF;
+    let st: u32 = (tr >> 4) & 0x7;
+    let su: u32 = tr & 0xF;
+
+    let mut hour: u32 = ht * 10 + hu;
+    if pm {
+        hour += 12;
+    }
+    NaiveTime::from_hms_nano_opt(hour, mnt * 10 + mnu, st * 10 + su, nano)
+}
+
+// set the CPU2 interrupt mask for an EXTI line,
+// the CPU2 interrupt mask register has no field per line in the PAC
+#[cfg(feature = "stm32wl5x_cm0p")]
+fn set_exti_c2_mask(exti: &mut pac::EXTI, line: u8, unmask: bool) {
+    let mask: u32 = 1 << line;
+    // safety: only the given line is modified
+    exti.c2imr1.modify(|r, w| unsafe {
+        if unmask {
+            w.im().bits(r.im().bits() | mask)
+        } else {
+            w.im().bits(r.im().bits() & !mask)
+        }
+    });
+}
+
+/// Real-time clock driver.
+#[derive(Debug)]
+pub struct Rtc {
+    rtc: pac::RTC,
+}
+
+impl Rtc {
+    /// Create a new real-time clock driver.
+    ///
+    /// # Safety
+    ///
+    /// 1. The RTC is in the backup domain, system resets will not reset the RTC.
+    ///    You are responsible for resetting the backup domain if required.
+    ///    This function does not perform the reset because resetting the backup
+    ///    domain also resets the LSE clock.
+    /// 2. You are responsible for setting up the source clock.
+    ///
+    /// # Panics
+    ///
+    /// * (debug) clock source is not ready.
+    ///
+    /// # Example
+    ///
+    /// LSE clock source (this depends on HW, example valid for NUCLEO board):
+    ///
+    /// ```no_run
+    /// use stm32wl_hal::{
+    ///     pac,
+    ///     rcc::pulse_reset_backup_domain,
+    ///     rtc::{Clk, Rtc},
+    /// };
+    ///
+    /// let mut dp: pac::Peripherals = pac::Peripherals::take().unwrap();
+    ///
+    /// unsafe { pulse_reset_backup_domain(&mut dp.RCC, &mut dp.PWR) };
+    /// dp.PWR.cr1.modify(|_, w| w.dbp().enabled());
+    /// dp.RCC.bdcr.modify(|_, w| w.lseon().on());
+    /// while dp.RCC.bdcr.read().lserdy().is_not_ready() {}
+    ///
+    /// let rtc: Rtc = unsafe { Rtc::new(dp.RTC, Clk::Lse, &mut dp.PWR, &mut dp.RCC) };
+    /// ```
+    ///
+    /// LSI clock source:
+    ///
+    /// ```no_run
+    /// use stm32wl_hal::{
+    ///     pac,
+    ///     rcc::{enable_lsi, pulse_reset_backup_domain},
+    ///     rtc::{Clk, Rtc},
+    /// };
+    ///
+    /// let mut dp: pac::Peripherals = pac::Peripherals::take().unwrap();
+    ///
+    /// unsafe { pulse_reset_backup_domain(&mut dp.RCC, &mut dp.PWR) };
+    /// enable_lsi(&mut dp.RCC);
+    ///
+    /// let rtc: Rtc = unsafe { Rtc::new(dp.RTC, Clk::Lsi, &mut dp.PWR, &mut dp.RCC) };
+    /// ```
+    ///
+    /// HSE clock source (this depends on HW, example valid for NUCLEO board):
+    ///
+    /// ```no_run
+    /// use stm32wl_hal::{
+    ///     pac,
+    ///     rcc::pulse_reset_backup_domain,
+    ///     rtc::{Clk, Rtc},
+    /// };
+    ///
+    /// let mut dp: pac::Peripherals = pac::Peripherals::take().unwrap();
+    ///
+    /// unsafe { pulse_reset_backup_domain(&mut dp.RCC, &mut dp.PWR) };
+    /// dp.RCC
+    ///     .cr
+    ///     .modify(|_, w| w.hseon().enabled().hsebyppwr().vddtcxo());
+    /// while dp.RCC.cr.read().hserdy().is_not_ready() {}
+    ///
+    /// let rtc: Rtc = unsafe { Rtc::new(dp.RTC, Clk::Hse, &mut dp.PWR, &mut dp.RCC) };
+    /// ```
+    pub unsafe fn new(rtc: pac::RTC, clk: Clk, pwr: &mut pac::PWR, rcc: &mut pac::RCC) -> Rtc {
+        pwr.cr1.modify(|_, w| w.dbp().enabled());
+
+        match clk {
+            Clk::Lse => {
+                debug_assert!(rcc.bdcr.read().lserdy().is_ready());
+                rcc.bdcr.modify(|_, w| w.rtcsel().lse().rtcen().enabled());
+            }
+            Clk::Lsi => {
+                debug_assert!(rcc.csr.read().lsirdy().is_ready());
+                rcc.bdcr.modify(|_, w| w.rtcsel().lsi().rtcen().enabled());
+            }
+            Clk::Hse => {
+                debug_assert!(rcc.cr.read().hserdy().is_ready());
+                rcc.bdcr.modify(|_, w| w.rtcsel().hse32().rtcen().enabled());
+            }
+        }
+
+        #[cfg(not(feature = "stm32wl5x_cm0p"))]
+        rcc.apb1enr1.modify(|_, w| w.rtcapben().set_bit());
+        #[cfg(feature = "stm32wl5x_cm0p")]
+        rcc.c2apb1enr1.modify(|_, w| w.rtcapben().set_bit());
+
+        let mut rtc: Rtc = Rtc { rtc };
+        rtc.disable_write_protect();
+        rtc.configure_prescaler(rcc);
+
+        rtc
+    }
+
+    /// Steal the RTC peripheral from whatever is currently using it.
+    ///
+    /// This will **not** initialize the RTC (unlike [`new`]).
+    ///
+    /// # Safety
+    ///
+    /// 1. Ensure that the code stealing the RTC has exclusive access to the
+    ///    peripheral. Singleton checks are bypassed with this method.
+    /// 2. You are responsible for setting up the RTC correctly.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stm32wl_hal::rtc::Rtc;
+    ///
+    /// // ... setup happens here
+    ///
+    /// let rtc: Rtc = unsafe { Rtc::steal() };
+    /// ```
+    ///
+    /// [`new`]: Rtc::new
+    pub unsafe fn steal() -> Rtc {
+        Rtc {
+            rtc: pac::Peripherals::steal().RTC,
+        }
+    }
+
+    /// Source clock frequency in hertz.
+    #[inline]
+    pub fn hz(rcc: &pac::RCC) -> u32 {
+        match rcc.bdcr.read().rtcsel().variant() {
+            RTCSEL_A::NOCLOCK => 0,
+            RTCSEL_A::LSE => 32_768,
+            RTCSEL_A::LSI => lsi_hz(rcc).into(),
+            RTCSEL_A::HSE32 => 1_000_000,
+        }
+    }
+
+    // configure prescaler for a 1Hz clock
+    //
+    // RM0453 Rev 2 page 996:
+    // When both prescalers are used, it is recommended to configure the
+    // asynchronous prescaler to a high value to minimize consumption.
+    //
+    // async is 7 bit (128 max)
+    // sync is 15-bit (32_768 max)
+    fn configure_prescaler(&mut self, rcc: &mut pac::RCC) {
+        let (a_pre, s_pre): (u8, u16) = match rcc.bdcr.read().rtcsel().variant() {
+            RTCSEL_A::NOCLOCK => unreachable!(),
+            // (127 + 1) × (255 + 1) = 32_768 Hz
+            RTCSEL_A::LSE => (127, 255),
+            RTCSEL_A::LSI => match rcc.csr.read().lsipre().variant() {
+                // (124 + 1) × (255 + 1) = 32_000 Hz
+                DIV1 => (124, 255),
+                // (124 + 1) × (1 + 1) = 250 Hz
+                DIV128 => (124, 1),
+            },
+            // (99 + 1) × (9_999 + 1) = 1_000_000 Hz
+            RTCSEL_A::HSE32 => (99, 9_999),
+        };
+
+        // enter initialization mode
+        self.rtc.icsr.modify(|_, w| w.init().init_mode());
+        while self.rtc.icsr.read().initf().is_not_allowed() {}
+
+        self.rtc
+            .prer
+            .write(|w| w.prediv_s().bits(s_pre).prediv_a().bits(a_pre));
+
+        // exit initialization mode
+        self.rtc.icsr.modify(|_, w| w.init().free_running_mode())
+    }
+
+    /// Set the date and time.
+    ///
+    /// The value will take some duration to apply after this function returns:
+    ///
+    /// * LPCAL=0: the counting restarts after 4 RTCCLK clock cycles
+    /// * LPCAL=1: the counting restarts after up to 2 RTCCLK + 1 ck_apre
+    ///
+    /// # Panics
+    ///
+    /// * Year is greater than or equal to 2100.
+    /// * Year is less than 2000.
+    /// * Backup domain write protection is enabled.
+    pub fn set_date_time(&mut self, date_time: chrono::NaiveDateTime) {
+        // safety: atomic read with no side effects
+        assert!(unsafe { (*pac::PWR::ptr()).cr1.read().dbp().bit_is_set() });
+
+        // enter initialization mode
+        self.rtc.icsr.modify(|_, w| w.init().init_mode());
+        while self.rtc.icsr.read().initf().is_not_allowed() {}
+
+        let hour: u8 = date_time.hour() as u8;
+        let ht: u8 = hour / 10;
+        let hu: u8 = hour % 10;
+
+        let minute: u8 = date_time.minute() as u8;
+        let mnt: u8 = minute / 10;
+        let mnu: u8 = minute % 10;
+
+        let second: u8 = date_time.second() as u8;
+        let st: u8 = second / 10;
+        let su: u8 = second % 10;
+
+        #[rustfmt::skip]
+        self.rtc.tr.write(|w| {
+            w
+                .pm().clear_bit() // 24h format
+                .ht().bits(ht)
+                .hu().bits(hu)
+                .mnt().bits(mnt)
+                .mnu().bits(mnu)
+                .st().bits(st)
+                .su().bits(su)
+        });
+
+        let year: i32 = date_time.year();
+        assert!((2000..2100).contains(&year));
+        let yt: u8 = ((year - 2000) / 10) as u8;
+        let yu: u8 = ((year - 2000) % 10) as u8;
+
+        let wdu: u8 = date_time.weekday().number_from_monday() as u8;
+
+        let month: u8 = date_time.month() as u8;
+        let mt: bool = month > 9;
+        let mu: u8 = month % 10;
+
+        let day: u8 = date_time.day() as u8;
+        let dt: u8 = day / 10;
+        let du: u8 = day % 10;
+
+        #[rustfmt::skip]
+        self.rtc.dr.write(|w| unsafe {
+            w
+                .yt().bits(yt)
+                .yu().bits(yu)
+                .wdu().bits(wdu)
+                .mt().bit(mt)
+                .mu().bits(mu)
+                .dt().bits(dt)
+                .du().bits(du)
+        });
+
+        // exit initialization mode
+        self.rtc.icsr.modify(|_, w| w.init().free_running_mode());
+    }
+
+    /// Returns `None` if the calendar is uninitialized
+    fn calendar_initialized(&self) -> Option<()> {
+        use pac::rtc::icsr::INITS_A;
+        match self.rtc.icsr.read().inits().variant() {
+            INITS_A::NOTINITALIZED => None,
+            INITS_A::INITALIZED => Some(()),
+        }
+    }
+
+    /// Set the calendar mode.
+    ///
+    /// The calendar and the sub-second counter are stopped while the mode is
+    /// changed.
+    ///
+    /// In mixed mode the calendar second is incremented from the binary
+    /// counter, this requires the binary counter frequency to be a power of
+    /// two between 256 Hz and 32768 Hz.
+    /// This is the case for the LSE and the LSI without the LSI prescaler.
+    ///
+    /// # Panics
+    ///
+    /// * [`Mode::Mixed`] with a binary counter frequency that is not a power
+    ///   of two between 256 Hz and 32768 Hz.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use stm32wl_hal::{
+    ///     pac,
+    ///     rtc::{Mode, Rtc},
+    /// };
+    ///
+    /// let mut dp: pac::Peripherals = pac::Peripherals::take().unwrap();
+    /// let mut rtc: Rtc = unsafe { Rtc::steal() };
+    ///
+    /// rtc.set_mode(Mode::Mixed, &dp.RCC);
+    /// assert_eq!(rtc.mode(), Mode::Mixed);
+    /// ```
+    pub fn set_mode(&mut self, mode: Mode, rcc: &pac::RCC) {
+        let bcdu: u8 = match mode {
+            Mode::Mixed => unwrap!(
+                bcdu(self.binary_counter_hz(rcc)),
+                "Binary counter frequency is not a power of two between 256 Hz and 32768 Hz"
+            ),
+            Mode::Bcd | Mode::Binary => 0,
+        };
+
+        // enter initialization mode
+        self.rtc.icsr.modify(|_, w| w.init().init_mode());
+        while self.rtc.icsr.read().initf().is_not_allowed() {}
+
+        self.rtc
+            .icsr
+            .modify(|_, w| w.bin().bits(mode as u8).bcdu().bits(bcdu));
+
+        // exit initialization mode
+        self.rtc.icsr.modify(|_, w| w.init().free_running_mode());
+    }
+
+    /// Calendar mode.
+    pub fn mode(&self) -> Mode {
+        match self.rtc.icsr.read().bin().variant() {
+            BIN_A::BCD => Mode::Bcd,
+            BIN_A::BINARY => Mode::Binary,
+            BIN_A::BINBCD | BIN_A::BINBCD2 => Mode::Mixed,
+        }
+    }
+
+    /// Binary counter frequency in hertz.
+    ///
+    /// This is the RTC source clock frequency divided by the asynchronous
+    /// prescaler.
+    pub fn binary_counter_hz(&self, rcc: &pac::RCC) -> u32 {
+        Self::hz(rcc) / (u32::from(self.rtc.prer.read().prediv_a().bits()) + 1)
+    }
+
+    /// Binary counter.
+    ///
+    /// The counter is incremented at [`binary_counter_hz`], and wraps at
+    /// `u32::MAX`.
+    ///
+    /// Returns `None` in [`Mode::Bcd`].
+    ///
+    /// [`binary_counter_hz`]: Self::binary_counter_hz
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use stm32wl_hal::{
+    ///     pac,
+    ///     rtc::{Mode, Rtc},
+    /// };
+    ///
+    /// let mut dp: pac::Peripherals = pac::Peripherals::take().unwrap();
+    /// let mut rtc: Rtc = unsafe { Rtc::steal() };
+    ///
+    /// rtc.set_mode(Mode::Binary, &dp.RCC);
+    /// let hz: u32 = rtc.binary_counter_hz(&dp.RCC);
+    /// let start: u32 = rtc.binary_counter().unwrap();
+    /// // ... do things
+    /// let elapsed_ms: u32 = rtc.binary_counter().unwrap().wrapping_sub(start) * 1000 / hz;
+    /// ```
+    pub fn binary_counter(&self) -> Option<u32> {
+        if self.mode() == Mode::Bcd {
+            return None;
+        }
+
+        // the counter is asynchronous to the APB clock,
+        // read until two consecutive reads are equal
+        loop {
+            let ss: u32 = self.rtc.ssr.read().bits();
+            if ss == self.rtc.ssr.read().bits() {
+                // SSR counts down
+                return Some(!ss);
+            }
+        }
+    }
+
+    // Read the SSR, TR, and DR registers.
+    //
+    // RM0453 Rev 2 32.3.10 page 1002 "Reading the calendar":
+    // Reading SSR or TR locks the values in the higher-order calendar shadow
+    // registers until DR is read.
+    // The registers are read twice to handle slow APB clocks relative to the
+    // RTC clock, a read that spans an RTC clock edge is retried.
+    fn calendar(&self) -> (u32, u32, u32) {
+        loop {
+            let first: (u32, u32, u32) = (
+                self.rtc.ssr.read().bits(),
+                self.rtc.tr.read().bits(),
+                self.rtc.dr.read().bits(),
+            );
+            let second: (u32, u32, u32) = (
+                self.rtc.ssr.read().bits(),
+                self.rtc.tr.read().bits(),
+                self.rtc.dr.read().bits(),
+            );
+            if first == second {
+                return first;
+            }
+        }
+    }
+
+    // Sub-second nanoseconds from a SSR value, and `true` if the calendar is
+    // one second ahead after a shift.
+    fn ss_to_ns(&self, ss: u32) -> (u32, bool) {
+        let icsr: pac::rtc::icsr::R = self.rtc.icsr.read();
+        if icsr.bin().is_bcd() {
+            let pre_s: u32 = self.rtc.prer.read().prediv_s().bits().into();
+            bcd_subsec_nanos(ss, pre_s)
+        } else {
+            // mixed mode, the second is incremented every 2^(8 + BCDU) ticks
+            let modulus: u32 = 1 << (8 + icsr.bcdu().bits());
+            (subsec_nanos(ss, modulus), false)
+        }
+    }
+
+    /// Calendar Date
+    ///
+    /// Returns `None` if the calendar has not been initialized, or in
+    /// [`Mode::Binary`].
+    pub fn date(&self) -> Option<NaiveDate> {
+        self.date_time().map(|dt| dt.date())
+    }
+
+    /// Current Time
+    ///
+    /// The time includes the fraction of a second from the sub-second
+    /// counter.
+    ///
+    /// Returns `None` if the calendar has not been initialized, or in
+    /// [`Mode::Binary`].
+    pub fn time(&self) -> Option<NaiveTime> {
+        self.date_time().map(|dt| dt.time())
+    }
+
+    /// Calendar Date and Time
+    ///
+    /// The time includes the fraction of a second from the sub-second
+    /// counter.
+    /// The sub-second counter, time, and date are read as one consistent
+    /// snapshot.
+    ///
+    /// Returns `None` if the calendar has not been initialized, or in
+    /// [`Mode::Binary`].
+    pub fn date_time(&self) -> Option<NaiveDateTime> {
+        self.calendar_initialized()?;
+        if self.mode() == Mode::Binary {
+            return None;
+        }
+
+        let (ss, tr, dr) = self.calendar();
+        let (nanos, ahead) = self.ss_to_ns(ss);
+        let date: NaiveDate = decode_date(dr)?;
+        let time: NaiveTime = decode_time(tr, nanos)?;
+        if ahead {
+            date.and_time(time)
+                .checked_sub_signed(chrono::Duration::seconds(1))
+        } else {
+            Some(date.and_time(time))
+        }
+    }
+
+    /// Disable the RTC write protection.
+    #[inline]
+    pub fn disable_write_protect(&mut self) {
+        self.rtc.wpr.write(|w| w.key().deactivate1());
+        self.rtc.wpr.write(|w| w.key().deactivate2());
+    }
+
+    /// Enable the RTC write protection.
+    ///
+    /// # Safety
+    ///
+    /// * You must call [`disable_write_protect`] before using any other
+    ///   `&mut self` RTC method.
+    ///
+    /// [`disable_write_protect`]: Self::disable_write_protect
+    #[inline]
+    pub unsafe fn enable_write_protect(&mut self) {
+        self.rtc.wpr.write(|w| w.key().activate());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{bcd_subsec_nanos, bcdu, decode_date, decode_time, subsec_nanos};
+    use chrono::{NaiveDate, NaiveTime};
+
+    #[test]
+    fn bcdu_from_hz() {
+        assert_eq!(bcdu(256), Some(0));
+        assert_eq!(bcdu(1024), Some(2));
+        assert_eq!(bcdu(32_768), Some(7));
+        assert_eq!(bcdu(128), None);
+        assert_eq!(bcdu(65_536), None);
+        assert_eq!(bcdu(320), None);
+    }
+
+    #[test]
+    fn subsec() {
+        // LSE with PREDIV_S = 255
+        assert_eq!(subsec_nanos(255, 256), 0);
+        assert_eq!(subsec_nanos(127, 256), 500_000_000);
+        assert_eq!(subsec_nanos(0, 256), 996_093_750);
+        // mixed mode, only the low bits are used
+        assert_eq!(subsec_nanos(0xFFFF_FF7F, 256), 500_000_000);
+    }
+
+    #[test]
+    fn subsec_after_shift() {
+        assert_eq!(bcd_subsec_nanos(127, 255), (500_000_000, false));
+        assert_eq!(bcd_subsec_nanos(255, 255), (0, false));
+        // SS > PREDIV_S after a shift, the calendar is one second ahead
+        assert_eq!(bcd_subsec_nanos(256, 255), (996_093_750, true));
+        assert_eq!(bcd_subsec_nanos(255 + 64, 255), (750_000_000, true));
+        assert_eq!(bcd_subsec_nanos(2 * 255, 255), (3_906_250, true));
+    }
+
+    #[test]
+    fn decode() {
+        assert_eq!(
+            decode_date(0x0021_7020),
+            NaiveDate::from_ymd_opt(2021, 10, 20)
+        );
+        assert_eq!(decode_date(0x0021_0000), None);
+        assert_eq!(
+            decode_time(0x0012_0205, 250_000_000),
+            NaiveTime::from_hms_milli_opt(12, 2, 5, 250)
+        );
+        assert_eq!(
+            decode_time(0x0041_0000, 0),
+            NaiveTime::from_hms_opt(13, 0, 0)
+        );
+    }
+}
@@ -0,0 +1,261 @@
+use super::Rtc;
+use crate::pac;
+use core::time::Duration;
+
+// RTC wakeup timer is connected to EXTI line 20
+#[cfg(feature = "stm32wl5x_cm0p")]
+const EXTI_WAKEUP: u8 = 20;
+
+// WUT is 16-bits, ck_spre with WUCKSEL=11x adds 2^16 to the counter
+const WUT_MAX: u64 = 1 << 16;
+
+const NANOS_PER_SEC: u64 = 1_000_000_000;
+
+// wakeup timer clock selections, from lowest to highest power consumption
+//
+// (WUCKSEL, minimum count, maximum count)
+//
+// the prescaler of the ck_spre selections is (PREDIV_A + 1) × (PREDIV_S + 1)
+// and is filled in at runtime
+//
+// WUTR = 0 is forbidden with RTCCLK / 2, the minimum count is 2
+const CLK_SEL: [(u32, u64, u64); 6] = [
+    // ck_spre, 2^16 added to the WUT counter value
+    (0b110, WUT_MAX + 1, 2 * WUT_MAX),
+    // ck_spre
+    (0b100, 1, WUT_MAX),
+    // RTCCLK / 16
+    (0b000, 1, WUT_MAX),
+    // RTCCLK / 8
+    (0b001, 1, WUT_MAX),
+    // RTCCLK / 4
+    (0b010, 1, WUT_MAX),
+    // RTCCLK / 2
+    (0b011, 2, WUT_MAX),
+];
+
+const fn prescaler(wucksel: u32, spre: u64) -> u64 {
+    match wucksel {
+        0b000 => 16,
+        0b001 => 8,
+        0b010 => 4,
+        0b011 => 2,
+        _ => spre,
+    }
+}
+
+// Select the wakeup clock and counter value with the smallest error.
+//
+// Returns (WUCKSEL, WUTR, number of RTCCLK cycles in the period).
+fn wakeup_cfg(rtc_hz: u32, spre: u64, period: Duration) -> (u32, u32, u64) {
+    let hz: u64 = rtc_hz.into();
+    // anything longer than the maximum period is clamped,
+    // limiting the period first avoids overflow in the conversion to cycles
+    let period: Duration = period.min(Duration::from_secs(2 * WUT_MAX * spre / hz + 1));
+    // period in thousandths of RTCCLK cycles
+    let period_mcyc: u64 = period.as_secs() * hz * 1000
+        + u64::from(period.subsec_nanos()) * hz / (NANOS_PER_SEC / 1000);
+
+    let mut best: (u32, u32, u64) = (0, 0, 0);
+    let mut best_err: u64 = u64::MAX;
+
+    for &(wucksel, min, max) in CLK_SEL.iter() {
+        let pre: u64 = prescaler(wucksel, spre);
+        let pre_mcyc: u64 = pre * 1000;
+        let count: u64 = ((period_mcyc + pre_mcyc / 2) / pre_mcyc).clamp(min, max);
+        let cyc: u64 = count * pre;
+        let err: u64 = (cyc * 1000).abs_diff(period_mcyc);
+        // strictly less than to prefer the lower power clocks on a tie
+        if err < best_err {
+            best_err = err;
+            best = (wucksel, ((count - 1) % WUT_MAX) as u32, cyc);
+        }
+    }
+
+    best
+}
+
+fn cycles_to_duration(rtc_hz: u32, cyc: u64) -> Duration {
+    let hz: u64 = rtc_hz.into();
+    Duration::new(cyc / hz, ((cyc % hz) * NANOS_PER_SEC / hz) as u32)
+}
+
+impl Rtc {
+    /// Setup and enable the periodic wakeup timer.
+    ///
+    /// The wakeup timer clock and reload value are selected to get the
+    /// closest period to the requested period.
+    /// When two selections have the same period the selection with the
+    /// lower power consumption is used.
+    ///
+    /// The wakeup timer runs independently of the calendar, this does not
+    /// modify the date and time.
+    ///
+    /// Periods shorter than 4 RTCCLK cycles or longer than 2<sup>17</sup>
+    /// seconds are clamped.
+    ///
+    /// Returns the actual period programmed.
+    ///
+    /// # Example
+    ///
+    /// Wakeup from stop 2 mode every 10 seconds.
+    ///
+    /// ```no_run
+    /// use core::time::Duration;
+    /// use stm32wl_hal::{pac, rtc::Rtc};
+    ///
+    /// let mut dp: pac::Peripherals = pac::Peripherals::take().unwrap();
+    /// let mut rtc: Rtc = unsafe { Rtc::steal() };
+    ///
+    /// let period: Duration = rtc.setup_wakeup_timer(Duration::from_secs(10), &dp.RCC);
+    /// assert_eq!(period, Duration::from_secs(10));
+    /// rtc.set_wakeup_timer_irq(true);
+    /// Rtc::set_wakeup_timer_exti(&mut dp.EXTI, true);
+    /// ```
+    pub fn setup_wakeup_timer(&mut self, period: Duration, rcc: &pac::RCC) -> Duration {
+        let rtc_hz: u32 = Self::hz(rcc);
+        let prer = self.rtc.prer.read();
+        let spre: u64 =
+            (u64::from(prer.prediv_a().bits()) + 1) * (u64::from(prer.prediv_s().bits()) + 1);
+
+        let (wucksel, wut, cyc) = wakeup_cfg(rtc_hz, spre, period);
+
+        // the reload value and clock can only be written when the wakeup
+        // timer is disabled and WUTWF is set
+        self.disable_wakeup_timer();
+        while self.rtc.icsr.read().wutwf().bit_is_clear() {}
+
+        self.rtc.wutr.write(|w| w.wut().bits(wut as u16));
+        // safety: wucksel is one of the selections in CLK_SEL
+        self.rtc
+            .cr
+            .modify(|_, w| unsafe { w.wucksel().bits(wucksel as u8) });
+        self.rtc.cr.modify(|_, w| w.wute().set_bit());
+
+        cycles_to_duration(rtc_hz, cyc)
+    }
+
+    /// Disable the wakeup timer.
+    ///
+    /// This does not clear the wakeup timer flag.
+    pub fn disable_wakeup_timer(&mut self) {
+        self.rtc.cr.modify(|_, w| w.wute().clear_bit());
+    }
+
+    /// Returns `true` if the wakeup timer is enabled.
+    pub fn is_wakeup_timer_enabled(&self) -> bool {
+        self.rtc.cr.read().wute().bit_is_set()
+    }
+
+    /// Enable or disable the wakeup timer interrupt.
+    ///
+    /// The interrupt must also be unmasked in the EXTI with
+    /// [`set_wakeup_timer_exti`](Self::set_wakeup_timer_exti), and in the
+    /// NVIC.
+    ///
+    /// # Example
+    ///
+    /// See [`setup_wakeup_timer`](Self::setup_wakeup_timer).
+    pub fn set_wakeup_timer_irq(&mut self, en: bool) {
+        self.rtc.cr.modify(|_, w| w.wutie().bit(en));
+    }
+
+    /// Returns `true` if the wakeup timer flag is set.
+    pub fn is_wakeup_timer_pending(&self) -> bool {
+        self.rtc.sr.read().wutf().bit_is_set()
+    }
+
+    /// Clear the wakeup timer flag.
+    ///
+    /// This must be called in the interrupt handler, and before entering
+    /// standby mode.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use stm32wl_hal::rtc::Rtc;
+    ///
+    /// // in the RTC wakeup timer interrupt handler
+    /// Rtc::clear_wakeup_timer();
+    /// ```
+    #[inline]
+    pub fn clear_wakeup_timer() {
+        // safety: atomic write to a write-1-to-clear register
+        unsafe { (*pac::RTC::ptr()).scr.write(|w| w.cwutf().set_bit()) }
+    }
+
+    /// Set the interrupt mask for the RTC wakeup timer in the EXTI.
+    ///
+    /// The RTC wakeup timer is connected to EXTI line 20, a direct line with
+    /// no trigger selection or pending register.
+    /// Unmasking this line allows the wakeup timer to wakeup the core from
+    /// stop 0, 1, and 2 modes.
+    ///
+    /// To wakeup from standby or shutdown modes use
+    /// [`set_internal_wakeup_line`](crate::pwr::set_internal_wakeup_line)
+    /// instead.
+    ///
+    /// This will not mask/unmask the IRQ in the NVIC.
+    ///
+    /// # Example
+    ///
+    /// See [`setup_wakeup_timer`](Self::setup_wakeup_timer).
+    #[inline]
+    pub fn set_wakeup_timer_exti(exti: &mut pac::EXTI, unmask: bool) {
+        #[cfg(not(feature = "stm32wl5x_cm0p"))]
+        exti.c1imr1.modify(|_, w| w.im20().bit(unmask));
+        #[cfg(feature = "stm32wl5x_cm0p")]
+        super::set_exti_c2_mask(exti, EXTI_WAKEUP, unmask);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{cycles_to_duration, wakeup_cfg};
+    use core::time::Duration;
+
+    const LSE: u32 = 32_768;
+    const LSE_SPRE: u64 = 128 * 256;
+
+    #[test]
+    fn whole_seconds_use_ck_spre() {
+        assert_eq!(
+            wakeup_cfg(LSE, LSE_SPRE, Duration::from_secs(10)),
+            (0b100, 9, 10 * 32_768)
+        );
+        assert_eq!(
+            wakeup_cfg(LSE, LSE_SPRE, Duration::from_secs(100_000)),
+            (0b110, 100_000 - 1 - 65_536, 100_000 * 32_768)
+        );
+    }
+
+    #[test]
+    fn sub_second() {
+        // 1 ms is 32.768 RTCCLK cycles, every prescaler rounds to 32 cycles
+        // and the lowest power prescaler wins the tie
+        let (wucksel, wut, cyc) = wakeup_cfg(LSE, LSE_SPRE, Duration::from_millis(1));
+        assert_eq!((wucksel, wut), (0b000, 1));
+        assert_eq!(cycles_to_duration(LSE, cyc), Duration::from_nanos(976_562));
+
+        // exact with RTCCLK / 16
+        assert_eq!(
+            wakeup_cfg(LSE, LSE_SPRE, Duration::from_millis(500)),
+            (0b000, 1023, 16_384)
+        );
+    }
+
+    #[test]
+    fn clamp() {
+        // WUTR = 0 is forbidden with RTCCLK / 2, the shortest period is
+        // 4 RTCCLK cycles and the lower power RTCCLK / 4 wins the tie
+        assert_eq!(wakeup_cfg(LSE, LSE_SPRE, Duration::ZERO), (0b010, 0, 4));
+        assert_eq!(
+            wakeup_cfg(LSE, LSE_SPRE, Duration::from_secs(1 << 20)),
+            (0b110, 0xFFFF, (1 << 17) * 32_768)
+        );
+        assert_eq!(
+            wakeup_cfg(LSE, LSE_SPRE, Duration::MAX),
+            (0b110, 0xFFFF, (1 << 17) * 32_768)
+        );
+    }
+}
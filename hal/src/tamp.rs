@@ -0,0 +1,578 @@
+//! Tamper and backup registers.
+//!
+//! The TAMP peripheral contains the backup registers, and detects tamper
+//! events on the external tamper inputs and from internal sources.
+//!
+//! The backup registers are in the backup domain, they retain their contents
+//! through system resets and in standby and shutdown modes.
+//!
+//! By default a tamper event erases the device secrets:
+//!
+//! * Backup registers
+//! * SRAM2
+//! * PKA SRAM
+//! * AES key registers
+//!
+//! The secrets are erased when the tamper flag is set, and access is blocked
+//! until the tamper flag is cleared.
+//! The erase can be disabled per tamper source with
+//! [`TamperCfg::set_erase`].
+//!
+//! # Example
+//!
+//! Store a reset counter in a backup register.
+//!
+//! ```no_run
+//! use stm32wl_hal::{pac, tamp::Tamp};
+//!
+//! let mut dp: pac::Peripherals = pac::Peripherals::take().unwrap();
+//! let mut tamp: Tamp = Tamp::new(dp.TAMP, &mut dp.PWR, &mut dp.RCC);
+//!
+//! let resets: u32 = tamp.backup(0);
+//! tamp.set_backup(0, resets.wrapping_add(1));
+//! ```
+
+use crate::pac;
+
+/// Number of backup registers.
+pub const NUM_BACKUP: usize = 20;
+
+// the backup registers are distinct types in the PAC,
+// evaluate `$body` with `$reg` bound to the backup register at `$idx`
+macro_rules! with_backup {
+    ($tamp:expr, $idx:expr, |$reg:ident| $body:expr) => {
+        with_backup!(
+            @arms $tamp, $idx, $reg, $body,
+            0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17 18 19
+        )
+    };
+    (@arms $tamp:expr, $idx:expr, $reg:ident, $body:expr, $($n:literal)*) => {
+        paste::paste! {
+            match $idx {
+                $($n => {
+                    let $reg = &$tamp.[<bkp $n r>];
+                    $body
+                })*
+                _ => panic!("Backup register index out of range"),
+            }
+        }
+    };
+}
+
+// the tamper sources are distinct fields in the PAC,
+// evaluate `$r.<field>()` followed by `$op` where `<field>` is the PAC name
+// of the tamper source between `$pre` and `$suf`, e.g. `tamp1e` or `citamp3f`
+//
+// `@ext` and `@int` only match the external or internal tamper sources
+#[rustfmt::skip]
+macro_rules! with_tamper {
+    (@ext $tamper:expr, $r:ident.[$($pre:ident)? _ $suf:ident] $($op:tt)*) => {
+        paste::paste! {
+            match $tamper {
+                Tamper::In1 => $r.[<$($pre)? tamp1 $suf>]() $($op)*,
+                Tamper::In2 => $r.[<$($pre)? tamp2 $suf>]() $($op)*,
+                Tamper::In3 => $r.[<$($pre)? tamp3 $suf>]() $($op)*,
+                _ => unreachable!(),
+            }
+        }
+    };
+    (@int $tamper:expr, $r:ident.[$($pre:ident)? _ $suf:ident] $($op:tt)*) => {
+        paste::paste! {
+            match $tamper {
+                Tamper::LseCss => $r.[<$($pre)? itamp3 $suf>]() $($op)*,
+                Tamper::CalendarOverflow => $r.[<$($pre)? itamp5 $suf>]() $($op)*,
+                Tamper::DebugAccess => $r.[<$($pre)? itamp6 $suf>]() $($op)*,
+                Tamper::CounterOverflow => $r.[<$($pre)? itamp8 $suf>]() $($op)*,
+                _ => unreachable!(),
+            }
+        }
+    };
+    ($tamper:expr, $($tt:tt)*) => {
+        if $tamper.is_external() {
+            with_tamper!(@ext $tamper, $($tt)*)
+        } else {
+            with_tamper!(@int $tamper, $($tt)*)
+        }
+    };
+}
+
+/// Tamper sources.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Tamper {
+    /// External tamper input 1, TAMP_IN1.
+    In1,
+    /// External tamper input 2, TAMP_IN2.
+    In2,
+    /// External tamper input 3, TAMP_IN3.
+    In3,
+    /// Internal tamper 3, LSE monitoring (LSE clock security system).
+    LseCss,
+    /// Internal tamper 5, RTC calendar overflow.
+    CalendarOverflow,
+    /// Internal tamper 6, JTAG/SWD access with readout protection level 1.
+    DebugAccess,
+    /// Internal tamper 8, monotonic counter overflow.
+    CounterOverflow,
+}
+
+impl Tamper {
+    const fn is_external(&self) -> bool {
+        matches!(self, Tamper::In1 | Tamper::In2 | Tamper::In3)
+    }
+}
+
+/// External tamper trigger.
+///
+/// The meaning depends on the [`Filter`], edge detection is used with
+/// [`Filter::Edge`], level detection is used otherwise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Trigger {
+    /// Rising edge with edge detection, low level with level detection.
+    RisingOrLow,
+    /// Falling edge with edge detection, high level with level detection.
+    FallingOrHigh,
+}
+
+/// Tamper source configuration.
+///
+/// Argument of [`Tamp::enable`].
+///
+/// # Example
+///
+/// ```
+/// use stm32wl_hal::tamp::{TamperCfg, Trigger};
+///
+/// const CFG: TamperCfg = TamperCfg::new()
+///     .set_trigger(Trigger::FallingOrHigh)
+///     .set_erase(false)
+///     .set_irq_en(true);
+/// # assert!(!CFG.erase());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct TamperCfg {
+    trigger: Trigger,
+    erase: bool,
+    irq_en: bool,
+}
+
+impl TamperCfg {
+    /// Create a new tamper configuration.
+    ///
+    /// This is the same as `default`, but in a `const` function.
+    ///
+    /// The default configuration erases the device secrets on tamper, with
+    /// the interrupt disabled, and a [`Trigger::RisingOrLow`] trigger.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stm32wl_hal::tamp::TamperCfg;
+    ///
+    /// const CFG: TamperCfg = TamperCfg::new();
+    /// assert_eq!(CFG, TamperCfg::default());
+    /// assert!(CFG.erase());
+    /// ```
+    pub const fn new() -> TamperCfg {
+        TamperCfg {
+            trigger: Trigger::RisingOrLow,
+            erase: true,
+            irq_en: false,
+        }
+    }
+
+    /// Set the trigger.
+    ///
+    /// This is ignored for internal tamper sources.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stm32wl_hal::tamp::{TamperCfg, Trigger};
+    ///
+    /// const CFG: TamperCfg = TamperCfg::new().set_trigger(Trigger::FallingOrHigh);
+    /// assert_eq!(CFG.trigger(), Trigger::FallingOrHigh);
+    /// ```
+    #[must_use = "set_trigger returns a modified TamperCfg"]
+    pub const fn set_trigger(mut self, trigger: Trigger) -> TamperCfg {
+        self.trigger = trigger;
+        self
+    }
+
+    /// Set the erase of the device secrets on tamper.
+    ///
+    /// When enabled the backup registers, SRAM2, PKA SRAM, and the AES key
+    /// registers are erased when the tamper flag is set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stm32wl_hal::tamp::TamperCfg;
+    ///
+    /// const CFG: TamperCfg = TamperCfg::new().set_erase(false);
+    /// assert!(!CFG.erase());
+    /// ```
+    #[must_use = "set_erase returns a modified TamperCfg"]
+    pub const fn set_erase(mut self, erase: bool) -> TamperCfg {
+        self.erase = erase;
+        self
+    }
+
+    /// Set the tamper interrupt enable.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stm32wl_hal::tamp::TamperCfg;
+    ///
+    /// const CFG: TamperCfg = TamperCfg::new().set_irq_en(true);
+    /// assert!(CFG.irq_en());
+    /// ```
+    #[must_use = "set_irq_en returns a modified TamperCfg"]
+    pub const fn set_irq_en(mut self, en: bool) -> TamperCfg {
+        self.irq_en = en;
+        self
+    }
+
+    /// Trigger.
+    pub const fn trigger(&self) -> Trigger {
+        self.trigger
+    }
+
+    /// Returns `true` if the device secrets are erased on tamper.
+    pub const fn erase(&self) -> bool {
+        self.erase
+    }
+
+    /// Returns `true` if the tamper interrupt is enabled.
+    pub const fn irq_en(&self) -> bool {
+        self.irq_en
+    }
+}
+
+impl Default for TamperCfg {
+    fn default() -> Self {
+        TamperCfg::new()
+    }
+}
+
+/// External tamper input filter.
+///
+/// Argument of [`Tamp::set_filter`].
+///
+/// The filter applies to all external tamper inputs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[repr(u8)]
+pub enum Filter {
+    /// Edge detection, no filter and no pull-up precharge.
+    ///
+    /// This is the reset value.
+    Edge = 0b00,
+    /// Level detection, activated after 2 consecutive samples.
+    Samples2 = 0b01,
+    /// Level detection, activated after 4 consecutive samples.
+    Samples4 = 0b10,
+    /// Level detection, activated after 8 consecutive samples.
+    Samples8 = 0b11,
+}
+
+/// External tamper input sampling frequency with level detection.
+///
+/// Argument of [`Tamp::set_filter`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[repr(u8)]
+pub enum SampleFreq {
+    /// RTCCLK / 32768, 1 Hz with the LSE.
+    Div32768 = 0,
+    /// RTCCLK / 16384.
+    Div16384 = 1,
+    /// RTCCLK / 8192.
+    Div8192 = 2,
+    /// RTCCLK / 4096.
+    Div4096 = 3,
+    /// RTCCLK / 2048.
+    Div2048 = 4,
+    /// RTCCLK / 1024.
+    Div1024 = 5,
+    /// RTCCLK / 512.
+    Div512 = 6,
+    /// RTCCLK / 256, 128 Hz with the LSE.
+    Div256 = 7,
+}
+
+/// Tamper and backup register driver.
+#[derive(Debug)]
+pub struct Tamp {
+    tamp: pac::TAMP,
+}
+
+impl Tamp {
+    /// Create a new tamper and backup register driver.
+    ///
+    /// This will enable the RTC APB clock, which also clocks the TAMP, and
+    /// disable the backup domain write protection.
+    ///
+    /// This does not reset the backup domain, the backup registers and
+    /// tamper configuration are retained.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use stm32wl_hal::{pac, tamp::Tamp};
+    ///
+    /// let mut dp: pac::Peripherals = pac::Peripherals::take().unwrap();
+    /// let tamp: Tamp = Tamp::new(dp.TAMP, &mut dp.PWR, &mut dp.RCC);
+    /// ```
+    pub fn new(tamp: pac::TAMP, pwr: &mut pac::PWR, rcc: &mut pac::RCC) -> Tamp {
+        pwr.cr1.modify(|_, w| w.dbp().enabled());
+
+        #[cfg(not(feature = "stm32wl5x_cm0p"))]
+        rcc.apb1enr1.modify(|_, w| w.rtcapben().set_bit());
+        #[cfg(feature = "stm32wl5x_cm0p")]
+        rcc.c2apb1enr1.modify(|_, w| w.rtcapben().set_bit());
+
+        Tamp { tamp }
+    }
+
+    /// Free the TAMP peripheral from the driver.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use stm32wl_hal::{pac, tamp::Tamp};
+    ///
+    /// let mut dp: pac::Peripherals = pac::Peripherals::take().unwrap();
+    /// let tamp: Tamp = Tamp::new(dp.TAMP, &mut dp.PWR, &mut dp.RCC);
+    /// // ... use TAMP
+    /// let tamp: pac::TAMP = tamp.free();
+    /// ```
+    pub fn free(self) -> pac::TAMP {
+        self.tamp
+    }
+
+    /// Steal the TAMP peripheral from whatever is currently using it.
+    ///
+    /// This will **not** initialize the TAMP (unlike [`new`]).
+    ///
+    /// # Safety
+    ///
+    /// 1. Ensure that the code stealing the TAMP has exclusive access to the
+    ///    peripheral. Singleton checks are bypassed with this method.
+    /// 2. You are responsible for setting up the TAMP correctly.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stm32wl_hal::tamp::Tamp;
+    ///
+    /// // ... setup happens here
+    ///
+    /// let tamp: Tamp = unsafe { Tamp::steal() };
+    /// ```
+    ///
+    /// [`new`]: Tamp::new
+    pub unsafe fn steal() -> Tamp {
+        Tamp {
+            tamp: pac::Peripherals::steal().TAMP,
+        }
+    }
+
+    /// Read a backup register.
+    ///
+    /// Returns zero if the backup registers have been erased by a tamper
+    /// event and the tamper flag has not been cleared.
+    ///
+    /// # Panics
+    ///
+    /// * `idx` is greater than or equal to [`NUM_BACKUP`].
+    ///
+    /// # Example
+    ///
+    /// See the [module level documentation](self).
+    pub fn backup(&self, idx: usize) -> u32 {
+        with_backup!(self.tamp, idx, |bkpr| bkpr.read().bkp().bits())
+    }
+
+    /// Write a backup register.
+    ///
+    /// The backup domain write protection must be disabled, this is done by
+    /// [`new`](Self::new).
+    ///
+    /// # Panics
+    ///
+    /// * `idx` is greater than or equal to [`NUM_BACKUP`].
+    ///
+    /// # Example
+    ///
+    /// See the [module level documentation](self).
+    pub fn set_backup(&mut self, idx: usize, val: u32) {
+        with_backup!(self.tamp, idx, |bkpr| bkpr.write(|w| w.bkp().bits(val)))
+    }
+
+    /// Read all backup registers.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use stm32wl_hal::tamp::{Tamp, NUM_BACKUP};
+    ///
+    /// let tamp: Tamp = unsafe { Tamp::steal() };
+    /// let backup: [u32; NUM_BACKUP] = tamp.backups();
+    /// ```
+    pub fn backups(&self) -> [u32; NUM_BACKUP] {
+        let mut ret: [u32; NUM_BACKUP] = [0; NUM_BACKUP];
+        ret.iter_mut()
+            .enumerate()
+            .for_each(|(idx, val)| *val = self.backup(idx));
+        ret
+    }
+
+    /// Erase all backup registers.
+    ///
+    /// This only erases the backup registers, not the other device secrets.
+    pub fn erase_backups(&mut self) {
+        (0..NUM_BACKUP).for_each(|idx| self.set_backup(idx, 0))
+    }
+
+    /// Set the external tamper input filter.
+    ///
+    /// This applies to all external tamper inputs.
+    ///
+    /// `pull_up` enables the precharge of the inputs with the internal
+    /// pull-up before each sample, this is only used with level detection.
+    ///
+    /// # Example
+    ///
+    /// Level detection of an external switch to ground with the internal
+    /// pull-up, sampled at 128 Hz with the LSE.
+    ///
+    /// ```no_run
+    /// use stm32wl_hal::tamp::{Filter, SampleFreq, Tamp, Tamper, TamperCfg, Trigger};
+    ///
+    /// let mut tamp: Tamp = unsafe { Tamp::steal() };
+    /// tamp.set_filter(Filter::Samples4, SampleFreq::Div256, true);
+    /// tamp.enable(
+    ///     Tamper::In1,
+    ///     &TamperCfg::new().set_trigger(Trigger::RisingOrLow),
+    /// );
+    /// ```
+    pub fn set_filter(&mut self, filter: Filter, freq: SampleFreq, pull_up: bool) {
+        // filter can only be changed when all external tampers are disabled
+        let cr1: pac::tamp::cr1::R = self.tamp.cr1.read();
+        #[rustfmt::skip]
+        self.tamp.cr1.modify(|_, w| {
+            w
+                .tamp1e().clear_bit()
+                .tamp2e().clear_bit()
+                .tamp3e().clear_bit()
+        });
+        // TAMPPRCH is left at 1 RTCCLK cycle
+        #[rustfmt::skip]
+        self.tamp.fltcr.write(|w| {
+            w
+                .tamppudis().bit(!pull_up)
+                .tampflt().bits(filter as u8)
+                .tampfreq().bits(freq as u8)
+        });
+        #[rustfmt::skip]
+        self.tamp.cr1.modify(|_, w| {
+            w
+                .tamp1e().bit(cr1.tamp1e().bit_is_set())
+                .tamp2e().bit(cr1.tamp2e().bit_is_set())
+                .tamp3e().bit(cr1.tamp3e().bit_is_set())
+        });
+    }
+
+    /// Enable a tamper source.
+    ///
+    /// # Example
+    ///
+    /// Detect a falling edge on TAMP_IN2 without erasing the device secrets,
+    /// and detect the LSE failing with an erase.
+    ///
+    /// ```no_run
+    /// use stm32wl_hal::tamp::{Tamp, Tamper, TamperCfg, Trigger};
+    ///
+    /// let mut tamp: Tamp = unsafe { Tamp::steal() };
+    ///
+    /// const IN2: TamperCfg = TamperCfg::new()
+    ///     .set_trigger(Trigger::FallingOrHigh)
+    ///     .set_erase(false)
+    ///     .set_irq_en(true);
+    /// tamp.enable(Tamper::In2, &IN2);
+    /// tamp.enable(Tamper::LseCss, &TamperCfg::new());
+    /// ```
+    pub fn enable(&mut self, tamper: Tamper, cfg: &TamperCfg) {
+        self.disable(tamper);
+
+        if tamper.is_external() {
+            let trg: bool = matches!(cfg.trigger, Trigger::FallingOrHigh);
+            self.tamp.cr2.modify(|_, w| {
+                with_tamper!(@ext tamper, w.[_ noer].bit(!cfg.erase));
+                with_tamper!(@ext tamper, w.[_ trg].bit(trg))
+            });
+        } else {
+            self.tamp
+                .cr3
+                .modify(|_, w| with_tamper!(@int tamper, w.[_ noer].bit(!cfg.erase)));
+        }
+
+        self.tamp
+            .ier
+            .modify(|_, w| with_tamper!(tamper, w.[_ ie].bit(cfg.irq_en)));
+        self.tamp
+            .cr1
+            .modify(|_, w| with_tamper!(tamper, w.[_ e].set_bit()));
+    }
+
+    /// Disable a tamper source.
+    ///
+    /// This does not clear the tamper flag.
+    pub fn disable(&mut self, tamper: Tamper) {
+        self.tamp
+            .cr1
+            .modify(|_, w| with_tamper!(tamper, w.[_ e].clear_bit()));
+    }
+
+    /// Returns `true` if the tamper source is enabled.
+    pub fn is_enabled(&self, tamper: Tamper) -> bool {
+        let cr1: pac::tamp::cr1::R = self.tamp.cr1.read();
+        with_tamper!(tamper, cr1.[_ e].bit_is_set())
+    }
+
+    /// Returns `true` if the tamper flag is set.
+    pub fn is_pending(&self, tamper: Tamper) -> bool {
+        let sr: pac::tamp::sr::R = self.tamp.sr.read();
+        with_tamper!(tamper, sr.[_ f].bit_is_set())
+    }
+
+    /// Clear the tamper flag.
+    ///
+    /// If the tamper source erases the device secrets, the secrets are
+    /// accessible again after the flag is cleared.
+    ///
+    /// For an external tamper with level detection the flag is set again
+    /// while the level is active.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use stm32wl_hal::tamp::{Tamp, Tamper};
+    ///
+    /// // in the tamper interrupt handler
+    /// Tamp::clear(Tamper::In1);
+    /// ```
+    #[inline]
+    pub fn clear(tamper: Tamper) {
+        // safety: atomic write to a write-1-to-clear register
+        unsafe {
+            (*pac::TAMP::ptr())
+                .scr
+                .write(|w| with_tamper!(tamper, w.[c _ f].set_bit()))
+        }
+    }
+}
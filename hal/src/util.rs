@@ -1,6 +1,9 @@
 //! Miscellaneous utilities
 use crate::pac;
-use core::ptr::write_volatile;
+use core::{
+    ptr::write_volatile,
+    sync::atomic::{compiler_fence, Ordering::SeqCst},
+};
 use cortex_m::{delay::Delay, peripheral::syst::SystClkSource};
 
 /// Create a new [`cortex_m::delay::Delay`] from the current CPU systick
@@ -45,3 +48,11 @@ pub fn reset_cycle_count(dwt: &mut pac::DWT) {
     const DWT_CYCCNT: usize = 0xE0001004;
     unsafe { write_volatile(DWT_CYCCNT as *mut u32, 0) };
 }
+
+// overwrite a buffer with the default value,
+// the writes are volatile so they are not removed as dead stores
+pub(crate) fn zeroize<T: Copy + Default>(buf: &mut [T]) {
+    buf.iter_mut()
+        .for_each(|x| unsafe { write_volatile(x, T::default()) });
+    compiler_fence(SeqCst);
+}
@@ -6,7 +6,7 @@ use defmt_rtt as _; // global logger
 use panic_probe as _;
 use stm32wl_hal::{
     pac::{self, DWT},
-    pka::{curve::NIST_P256, EcdsaPublicKey, EcdsaSignature, Pka},
+    pka::{curve::NIST_P256, EcdsaPublicKey, EcdsaSignature, Pka, RsaCrtKey, RsaPublicKey},
     rcc,
     util::reset_cycle_count,
 };
@@ -17,6 +17,15 @@ const CYC_PER_MICRO: u32 = FREQ / 1000 / 1000;
 // WARNING will wrap-around eventually, use this for relative timing only
 defmt::timestamp!("{=u32:µs}", DWT::get_cycle_count() / CYC_PER_MICRO);
 
+// PKA RAM, checked for leftover key material
+const PKA_RAM_BASE: usize = 0x5800_2400;
+const PKA_RAM_NUM_DW: usize = 894;
+
+fn pka_ram_is_zero() -> bool {
+    (0..PKA_RAM_NUM_DW)
+        .all(|dw| unsafe { core::ptr::read_volatile((PKA_RAM_BASE + dw * 4) as *const u32) == 0 })
+}
+
 // Message hash
 const HASH: [u32; 8] = [
     0x44acf6b7, 0xe36c1342, 0xc2c58972, 0x04fe0950, 0x4e1e2efb, 0x1a900377, 0xdbc4e7a6, 0xa133ec56,
@@ -47,6 +56,75 @@ const CURVE_PT_Y: [u32; 8] = [
     0xce4014c6, 0x8811f9a2, 0x1a1fdb2c, 0x0e6113e0, 0x6db7ca93, 0xb7404e78, 0xdc7ccd5c, 0xa89a4ca9,
 ];
 
+// RSA-2048 key, SHA-256 hash, and RSASSA-PKCS1-v1_5 signature
+// generated with python
+const RSA_N: [u32; 64] = [
+    0xd1922518, 0x34c7dfe7, 0x47cf8af2, 0x212353e3, 0xc36b2ccf, 0x94e102ba, 0x73af9102, 0xe16a2681,
+    0x441eb435, 0xfa8fd76e, 0xba3f3a2c, 0x7f9f8ef5, 0xf775f6be, 0x1196eddf, 0x0a1068dd, 0xe913668a,
+    0xc31ba1db, 0x56d657d5, 0xa956bbc0, 0xb56f3846, 0xd23c4d6a, 0x779cbc71, 0x1e46ed18, 0x2eadac1d,
+    0xbc12b534, 0xdc0e153c, 0x350e2de1, 0x74a0821a, 0x409c6bb0, 0x6c9dc2bc, 0x5403fe65, 0x3fe3dd99,
+    0x3388e799, 0x4d23f575, 0xdaec4e69, 0x4f48820b, 0xe76a52a7, 0x476b6568, 0x1375e28b, 0x5ff01d9f,
+    0x3b778eac, 0x07fb9ec9, 0xceb03877, 0x889d09c1, 0x90651a9f, 0x267bb597, 0xf1ea7d7d, 0xca9de63e,
+    0x1fa7b571, 0x0e6fbb13, 0x2fcf54bf, 0x42685d7a, 0xe8729a8c, 0x2a1ccbd7, 0x2bb60c41, 0xc7949ff8,
+    0x2925ab88, 0xf4ce4aed, 0x7c679649, 0x7a8ab352, 0x9129f690, 0x87cd09ed, 0x759e8214, 0x38dbbd4b,
+];
+const RSA_P: [u32; 32] = [
+    0xebeab7e4, 0x9da0cc3f, 0x656046fe, 0x2f32de4c, 0x91bfdc29, 0x5ab79eb2, 0x3538734e, 0x295a5b27,
+    0xcd7c6cb5, 0x4b390edc, 0xcb2dfc58, 0xecdff2a9, 0x4aede476, 0x150a0154, 0x9c1f3adc, 0xca2a21c0,
+    0x54c8f27b, 0x123fb527, 0xcdd2d62a, 0xfd41732a, 0x887aabfe, 0xbde3e42b, 0x8bc941dc, 0x6f95683d,
+    0x7fe9c798, 0x25c547fc, 0xb292f41d, 0x482b8c11, 0x2efd1b34, 0x059e5ede, 0xde2ec921, 0x83632aa5,
+];
+const RSA_Q: [u32; 32] = [
+    0xe3694646, 0x67ee15ba, 0xaaec7d0f, 0xc013586a, 0x77e77b1f, 0x65f2aa97, 0xe97a2ea6, 0x04df00e2,
+    0xc7d52c5c, 0x6df869f0, 0x63c4424f, 0x58516852, 0x05e3f276, 0x6bf015bc, 0x04db172e, 0xd4415f4b,
+    0x79125193, 0xf211e671, 0x39f74c22, 0x8d77aca2, 0x2176ac7d, 0x1766b875, 0xe2e0ac43, 0xb1eb323c,
+    0x549aeca7, 0x3a95e7f7, 0x432ba313, 0xcd717145, 0x4262d4bc, 0xb66aac31, 0xfe53203a, 0x0dcbf52f,
+];
+const RSA_DP: [u32; 32] = [
+    0xa830efa7, 0xb2654818, 0x4e6b0452, 0x054e4bc3, 0xe50916a9, 0xf8fdfdca, 0xe93c74a0, 0x1dc54845,
+    0xa92ef2c5, 0x917a7d49, 0x162593c9, 0xc61ea6fe, 0xe0e79740, 0x44219cef, 0x385adf79, 0x373c3051,
+    0xd1606dd7, 0x261c7b47, 0x846ab2bb, 0x7550ab0d, 0x80bd8c4f, 0x920b9dc9, 0x19e082ee, 0xe832695d,
+    0xa70b017c, 0x4c3948e8, 0x345ba664, 0x15e6cdf6, 0x163ce69f, 0x00fe767e, 0x87826d6c, 0x522269ed,
+];
+const RSA_DQ: [u32; 32] = [
+    0x4286b58c, 0x3b332720, 0x28886e3d, 0x814f277a, 0x5e0155bf, 0x65af8bc0, 0x4d08f544, 0x940038de,
+    0x1edf79ce, 0xc04cff90, 0xd75ebe97, 0x3f9af10d, 0xadb37566, 0xb744d0d6, 0xe404c3cf, 0x6b408484,
+    0x2bb2f432, 0x778a4d03, 0xaa9571ac, 0x59fe3c7e, 0x6193b9fa, 0x0e327a9b, 0xe1f71fed, 0xddca9433,
+    0x99fc8866, 0x9378ef17, 0x228b4d75, 0xa9901a36, 0xcca8e4db, 0x1ff22cd5, 0xa334e624, 0x958ca8f9,
+];
+const RSA_QINV: [u32; 32] = [
+    0xab4dec6b, 0xde5c54c9, 0x1137e2f3, 0xda3658a8, 0x5a022239, 0xabbb338d, 0x87bd6f35, 0x9dae60e5,
+    0xf9565cae, 0x7621978b, 0xb46f03d9, 0x73b13ac8, 0x41a75e4c, 0x61a5b267, 0x57de4133, 0x02f27b8f,
+    0xcb559e2e, 0x5f6ae6d0, 0x95e696b1, 0x2482cd19, 0xb21d140e, 0xefa91179, 0x432eead9, 0xf07b7bb5,
+    0xb044b317, 0x57d01eae, 0x6ee09a5d, 0x0f534d07, 0x141b27c9, 0x794b7e21, 0xe619b533, 0x4d1d29ee,
+];
+const RSA_HASH: [u32; 8] = [
+    0x0f814760, 0x3ae345ba, 0x7f46673b, 0x5956429f, 0xad2613eb, 0x62a522c9, 0x1b077157, 0x27c79a71,
+];
+const RSA_SIG: [u32; 64] = [
+    0x50168164, 0x9e8dd92c, 0xe2e9cc59, 0x36407929, 0x1475e429, 0xb7c485a7, 0x63705382, 0x89702e3e,
+    0x8485bca4, 0xb9b2cdba, 0x6ba4ea36, 0x875b003f, 0x4faa97e4, 0xc46cc0cc, 0x90b7364a, 0x41bd5a50,
+    0x8f3c261e, 0x78cc4faf, 0x12d662f1, 0xe581ef77, 0x8827882c, 0x0cdb672b, 0xb2707382, 0xaec437ab,
+    0xf8b7a0cd, 0x4df51972, 0xdb31f8ba, 0xec8e0840, 0x186dc804, 0xb129bf84, 0xec46afc7, 0x9134c751,
+    0x263c637d, 0xb21a1998, 0x7684c345, 0x8d77960a, 0xd7b8b1b7, 0x4a09ff23, 0x5e686e89, 0x98a5fe83,
+    0x8eeeddae, 0x2eb46b6b, 0xf1c0e50b, 0xae616c42, 0x13f67954, 0xa79f8116, 0xdcbdb386, 0x59c2c7d1,
+    0xb16cd086, 0x6a79c0f8, 0xd495d8e8, 0x02338fa7, 0xaebab289, 0x2d69188b, 0x9a5b7ac7, 0xc2ead416,
+    0x8627bc6e, 0x1d7a8059, 0x0b8e0a6e, 0x2814353a, 0xc84c4215, 0x03b83cc3, 0x92c75012, 0x228ea41e,
+];
+
+const RSA_PUB_KEY: RsaPublicKey<64> = RsaPublicKey {
+    modulus: &RSA_N,
+    exponent: 65537,
+};
+
+const RSA_CRT_KEY: RsaCrtKey<32> = RsaCrtKey {
+    prime_p: &RSA_P,
+    prime_q: &RSA_Q,
+    dp: &RSA_DP,
+    dq: &RSA_DQ,
+    qinv: &RSA_QINV,
+};
+
 const SIGNATURE: EcdsaSignature<8> = EcdsaSignature {
     r_sign: &R_SIGN,
     s_sign: &S_SIGN,
@@ -57,9 +135,16 @@ const PUB_KEY: EcdsaPublicKey<8> = EcdsaPublicKey {
     curve_pt_y: &CURVE_PT_Y,
 };
 
+// 2^61 - 1, the modulus is shorter than the 128-bit operands
+const SHORT_N: [u32; 4] = [0, 0, 0x1FFF_FFFF, 0xFFFF_FFFF];
+const SHORT_A: [u32; 4] = [0, 0, 0x0123_4567, 0x89AB_CDEF];
+
 #[defmt_test::tests]
 mod tests {
-    use stm32wl_hal::pka::{EcdsaSignError, EcdsaSignature, EcdsaVerifyError};
+    use stm32wl_hal::pka::{
+        EccError, EcdsaSignError, EcdsaSignature, EcdsaVerifyError, PointCheckError, RsaError,
+        Secret,
+    };
 
     use super::*;
 
@@ -229,4 +314,361 @@ mod tests {
 
         unwrap!(pka.ecdsa_verify(&NIST_P256, &sig, &pub_key, &hash));
     }
+
+    #[test]
+    fn ecc_scalar_mul_pub_key(pka: &mut Pka) {
+        let base_point: EcdsaPublicKey<8> = EcdsaPublicKey {
+            curve_pt_x: &NIST_P256.base_point_x,
+            curve_pt_y: &NIST_P256.base_point_y,
+        };
+        let mut pub_x: [u32; 8] = [0; 8];
+        let mut pub_y: [u32; 8] = [0; 8];
+        unwrap!(pka.ecc_scalar_mul(
+            &NIST_P256,
+            &PRIVATE_KEY,
+            &base_point,
+            &mut pub_x,
+            &mut pub_y
+        ));
+        defmt::assert_eq!(pub_x, CURVE_PT_X);
+        defmt::assert_eq!(pub_y, CURVE_PT_Y);
+    }
+
+    #[test]
+    fn ecc_scalar_mul_nb(pka: &mut Pka) {
+        let base_point: EcdsaPublicKey<8> = EcdsaPublicKey {
+            curve_pt_x: &NIST_P256.base_point_x,
+            curve_pt_y: &NIST_P256.base_point_y,
+        };
+        unwrap!(pka.ecc_scalar_mul_start(&NIST_P256, &PRIVATE_KEY, &base_point));
+        let mut pub_x: [u32; 8] = [0; 8];
+        let mut pub_y: [u32; 8] = [0; 8];
+        unwrap!(nb::block!(pka.ecc_scalar_mul_result(&mut pub_x, &mut pub_y)));
+        defmt::assert_eq!(pub_x, CURVE_PT_X);
+        defmt::assert_eq!(pub_y, CURVE_PT_Y);
+    }
+
+    #[test]
+    fn ecc_scalar_mul_mode_err(pka: &mut Pka) {
+        unwrap!(pka.ecdsa_verify_start(&NIST_P256, &SIGNATURE, &PUB_KEY, &HASH));
+        let mut out_x: [u32; 8] = [0; 8];
+        let mut out_y: [u32; 8] = [0; 8];
+        defmt::assert_eq!(
+            nb::block!(pka.ecc_scalar_mul_result(&mut out_x, &mut out_y)),
+            Err(EccError::Mode { mode: 0b100110 })
+        );
+        unwrap!(nb::block!(pka.ecdsa_verify_result()));
+    }
+
+    #[test]
+    fn ecdh_shared_secret(pka: &mut Pka) {
+        // key pair from module-level documentation in PKA
+        const DOC_PRIV_KEY: [u32; 8] = [
+            0x49ac8727, 0xcee87484, 0xfe6dfda5, 0x10238ad4, 0x11ace8fe, 0x593a8cb7, 0x0492d659,
+            0xdb81802a,
+        ];
+        const DOC_PUB_KEY: EcdsaPublicKey<8> = EcdsaPublicKey {
+            curve_pt_x: &[
+                0xfa655759, 0xdec39028, 0x96460a43, 0x2bae1d00, 0x9126e1b4, 0x88789ff4, 0xef6b9a9b,
+                0xde1bc363,
+            ],
+            curve_pt_y: &[
+                0x8fa02ac4, 0xc421ca88, 0x4f0651f4, 0xe985e3cf, 0xd0af4069, 0xcc87f3a8, 0x8a8e95e7,
+                0x556ced97,
+            ],
+        };
+
+        let mut secret_a: Secret<8> = Secret::new([0; 8]);
+        unwrap!(pka.ecdh_shared_secret(&NIST_P256, &PRIVATE_KEY, &DOC_PUB_KEY, &mut secret_a));
+        let mut secret_b: Secret<8> = Secret::new([0; 8]);
+        unwrap!(pka.ecdh_shared_secret(&NIST_P256, &DOC_PRIV_KEY, &PUB_KEY, &mut secret_b));
+        defmt::assert_eq!(secret_a.expose(), secret_b.expose());
+        defmt::assert_ne!(secret_a.expose(), &[0; 8]);
+    }
+
+    #[test]
+    fn point_on_curve(pka: &mut Pka) {
+        unwrap!(pka.point_on_curve(&NIST_P256, &PUB_KEY));
+    }
+
+    #[test]
+    fn point_on_curve_nb(pka: &mut Pka) {
+        unwrap!(pka.point_on_curve_start(&NIST_P256, &PUB_KEY));
+        unwrap!(nb::block!(pka.point_on_curve_result()));
+    }
+
+    #[test]
+    fn point_not_on_curve(pka: &mut Pka) {
+        let mut curve_pt_y: [u32; 8] = CURVE_PT_Y;
+        curve_pt_y[7] ^= 1;
+        let pub_key: EcdsaPublicKey<8> = EcdsaPublicKey {
+            curve_pt_x: &CURVE_PT_X,
+            curve_pt_y: &curve_pt_y,
+        };
+
+        defmt::assert_eq!(
+            pka.point_on_curve(&NIST_P256, &pub_key),
+            Err(PointCheckError::NotOnCurve)
+        );
+        defmt::assert_eq!(
+            pka.ecdsa_verify_checked(&NIST_P256, &SIGNATURE, &pub_key, &HASH),
+            Err(EcdsaVerifyError::PublicKey)
+        );
+        let mut secret: Secret<8> = Secret::new([0; 8]);
+        defmt::assert_eq!(
+            pka.ecdh_shared_secret(&NIST_P256, &PRIVATE_KEY, &pub_key, &mut secret),
+            Err(EccError::NotOnCurve)
+        );
+    }
+
+    #[test]
+    fn point_on_curve_mode_error(pka: &mut Pka) {
+        unwrap!(pka.ecdsa_verify_start(&NIST_P256, &SIGNATURE, &PUB_KEY, &HASH));
+        defmt::assert_eq!(
+            nb::block!(pka.point_on_curve_result()),
+            Err(PointCheckError::Mode { mode: 0b100110 })
+        );
+        unwrap!(nb::block!(pka.ecdsa_verify_result()));
+    }
+
+    #[test]
+    fn ecdsa_verify_checked(pka: &mut Pka) {
+        unwrap!(pka.ecdsa_verify_checked(&NIST_P256, &SIGNATURE, &PUB_KEY, &HASH));
+    }
+
+    #[test]
+    fn modular_exp(pka: &mut Pka) {
+        let mut result: [u32; 1] = [0];
+        unwrap!(pka.modular_exp(&[4], &[13], &[497], &mut result));
+        defmt::assert_eq!(result, [445]);
+    }
+
+    #[test]
+    fn modular_exp_nb(pka: &mut Pka) {
+        let mut result: [u32; 1] = [0];
+        unwrap!(pka.modular_exp_start(&[4], &[13], &[497]));
+        unwrap!(nb::block!(pka.modular_exp_result(&mut result)));
+        defmt::assert_eq!(result, [445]);
+    }
+
+    #[test]
+    fn modular_exp_short_modulus(pka: &mut Pka) {
+        let mut result: [u32; 4] = [0; 4];
+        unwrap!(pka.modular_exp(&SHORT_A, &[65537], &SHORT_N, &mut result));
+        defmt::assert_eq!(result, [0, 0, 0x1637_3176, 0x1004_1A2F]);
+    }
+
+    #[test]
+    fn rsa_2048_crt_public(pka: &mut Pka) {
+        let mut encoded_msg: [u32; 64] = [0; 64];
+        unwrap!(pka.rsa_public(&RSA_PUB_KEY, &RSA_SIG, &mut encoded_msg));
+
+        let mut sig: Secret<64> = Secret::new([0; 64]);
+        unwrap!(pka.rsa_crt(&RSA_CRT_KEY, &encoded_msg, &mut sig));
+
+        defmt::assert_eq!(sig.expose().as_ref(), RSA_SIG.as_ref());
+    }
+
+    #[test]
+    fn rsa_2048_verify(pka: &mut Pka) {
+        unwrap!(pka.rsa_pkcs1v15_sha256_verify(&RSA_PUB_KEY, &RSA_SIG, &RSA_HASH));
+
+        let mut hash: [u32; 8] = RSA_HASH;
+        hash[0] ^= 1;
+        defmt::assert_eq!(
+            pka.rsa_pkcs1v15_sha256_verify(&RSA_PUB_KEY, &RSA_SIG, &hash),
+            Err(RsaError::Invalid)
+        );
+    }
+
+    #[test]
+    fn rsa_crt_mode_error(pka: &mut Pka) {
+        unwrap!(pka.modular_exp_start(&[4], &[13], &[497]));
+        let mut output: Secret<64> = Secret::new([0; 64]);
+        defmt::assert_eq!(
+            nb::block!(pka.rsa_crt_result(&mut output)),
+            Err(RsaError::Mode { mode: 0b000000 })
+        );
+        let mut result: [u32; 1] = [0];
+        unwrap!(nb::block!(pka.modular_exp_result(&mut result)));
+    }
+
+    #[test]
+    fn arith_add(pka: &mut Pka) {
+        let mut sum: [u32; 2] = [0; 2];
+        defmt::assert!(!unwrap!(pka.arith_add(
+            &[0, 0xFFFF_FFFF],
+            &[0, 1],
+            &mut sum
+        )));
+        defmt::assert_eq!(sum, [1, 0]);
+
+        defmt::assert!(unwrap!(pka.arith_add(
+            &[0xFFFF_FFFF, 0],
+            &[0x8000_0000, 1],
+            &mut sum
+        )));
+        defmt::assert_eq!(sum, [0x7FFF_FFFF, 1]);
+    }
+
+    #[test]
+    fn arith_sub(pka: &mut Pka) {
+        let mut diff: [u32; 2] = [0; 2];
+        unwrap!(pka.arith_sub(&[1, 0], &[0, 1], &mut diff));
+        defmt::assert_eq!(diff, [0, 0xFFFF_FFFF]);
+    }
+
+    #[test]
+    fn arith_mul(pka: &mut Pka) {
+        let mut product: [u32; 4] = [0; 4];
+        unwrap!(pka.arith_mul(&[0xFFFF_FFFF, 0xFFFF_FFFF], &[0, 2], &mut product));
+        defmt::assert_eq!(product, [0, 1, 0xFFFF_FFFF, 0xFFFF_FFFE]);
+    }
+
+    #[test]
+    fn arith_cmp(pka: &mut Pka) {
+        use core::cmp::Ordering;
+
+        defmt::assert!(unwrap!(pka.arith_cmp(&[1, 0], &[0, 1])) == Ordering::Greater);
+        defmt::assert!(unwrap!(pka.arith_cmp(&[0, 1], &[1, 0])) == Ordering::Less);
+        defmt::assert!(unwrap!(pka.arith_cmp(&[1, 1], &[1, 1])) == Ordering::Equal);
+    }
+
+    #[test]
+    fn mod_red(pka: &mut Pka) {
+        let mut remainder: [u32; 1] = [0];
+        unwrap!(pka.mod_red(&[1, 0], &[497], &mut remainder));
+        defmt::assert_eq!(remainder, [151]);
+    }
+
+    #[test]
+    fn mod_add_sub(pka: &mut Pka) {
+        let mut result: [u32; 1] = [0];
+        unwrap!(pka.mod_add(&[400], &[200], &[497], &mut result));
+        defmt::assert_eq!(result, [103]);
+        unwrap!(pka.mod_sub(&[200], &[400], &[497], &mut result));
+        defmt::assert_eq!(result, [297]);
+    }
+
+    #[test]
+    fn mod_inv(pka: &mut Pka) {
+        let mut inv: [u32; 8] = [0; 8];
+        unwrap!(pka.mod_inv(&INTEGER, &NIST_P256.prime_order, &mut inv));
+
+        let mut r2: [u32; 8] = [0; 8];
+        unwrap!(pka.montgomery_param(&NIST_P256.prime_order, &mut r2));
+        let mut tmp: [u32; 8] = [0; 8];
+        unwrap!(pka.montgomery_mul(&INTEGER, &inv, &NIST_P256.prime_order, &mut tmp));
+        let mut product: [u32; 8] = [0; 8];
+        unwrap!(pka.montgomery_mul(&tmp, &r2, &NIST_P256.prime_order, &mut product));
+        defmt::assert_eq!(product, [0, 0, 0, 0, 0, 0, 0, 1]);
+    }
+
+    #[test]
+    fn montgomery_mul(pka: &mut Pka) {
+        const N: [u32; 1] = [497];
+
+        let mut r2: [u32; 1] = [0];
+        unwrap!(pka.montgomery_param(&N, &mut r2));
+        let mut tmp: [u32; 1] = [0];
+        unwrap!(pka.montgomery_mul(&[400], &[200], &N, &mut tmp));
+        let mut product: [u32; 1] = [0];
+        unwrap!(pka.montgomery_mul(&tmp, &r2, &N, &mut product));
+        defmt::assert_eq!(product, [(400 * 200) % 497]);
+    }
+
+    #[test]
+    fn montgomery_mul_short_modulus(pka: &mut Pka) {
+        const B: [u32; 4] = [0, 0, 0x0FED_CBA9, 0x8765_4321];
+
+        let mut r2: [u32; 4] = [0; 4];
+        unwrap!(pka.montgomery_param(&SHORT_N, &mut r2));
+        let mut tmp: [u32; 4] = [0; 4];
+        unwrap!(pka.montgomery_mul(&SHORT_A, &B, &SHORT_N, &mut tmp));
+        let mut product: [u32; 4] = [0; 4];
+        unwrap!(pka.montgomery_mul(&tmp, &r2, &SHORT_N, &mut product));
+        defmt::assert_eq!(product, [0, 0, 0x02B4_6A89, 0x5512_0470]);
+    }
+
+    #[test]
+    fn curve_base_points(pka: &mut Pka) {
+        use stm32wl_hal::pka::{curve::*, EllipticCurve};
+
+        fn check<const MODULUS_SIZE: usize, const PRIME_ORDER_SIZE: usize>(
+            pka: &mut Pka,
+            curve: &EllipticCurve<MODULUS_SIZE, PRIME_ORDER_SIZE>,
+        ) {
+            let base_point: EcdsaPublicKey<MODULUS_SIZE> = EcdsaPublicKey {
+                curve_pt_x: &curve.base_point_x,
+                curve_pt_y: &curve.base_point_y,
+            };
+            unwrap!(pka.point_on_curve(curve, &base_point));
+        }
+
+        check(pka, &NIST_P192);
+        check(pka, &NIST_P224);
+        check(pka, &NIST_P256);
+        check(pka, &NIST_P384);
+        check(pka, &NIST_P521);
+        check(pka, &SECP256K1);
+        check(pka, &P224R1);
+        check(pka, &P256R1);
+        check(pka, &P384R1);
+    }
+
+    // RFC 6979 appendix A.2.5, P-256 with SHA-256, message "sample"
+    #[test]
+    fn ecdsa_sign_deterministic(pka: &mut Pka) {
+        const X: [u32; 8] = [
+            0xc9afa9d8, 0x45ba7516, 0x6b5c2157, 0x67b1d693, 0x4e50c3db, 0x36e89b12, 0x7b8a622b,
+            0x120f6721,
+        ];
+        const H: [u32; 8] = [
+            0xaf2bdbe1, 0xaa9b6ec1, 0xe2ade1d6, 0x94f41fc7, 0x1a831d02, 0x68e98915, 0x62113d8a,
+            0x62add1bf,
+        ];
+        const R: [u32; 8] = [
+            0xefd48b2a, 0xacb6a8fd, 0x1140dd9c, 0xd45e81d6, 0x9d2c877b, 0x56aaf991, 0xc34d0ea8,
+            0x4eaf3716,
+        ];
+        const S: [u32; 8] = [
+            0xf7cb1c94, 0x2d657c41, 0xd436c7a1, 0xb6e29f65, 0xf3e900db, 0xb9aff406, 0x4dc4ab2f,
+            0x843acda8,
+        ];
+
+        let mut r_sign: [u32; 8] = [0; 8];
+        let mut s_sign: [u32; 8] = [0; 8];
+        unwrap!(pka.ecdsa_sign_deterministic(&NIST_P256, &X, &H, &mut r_sign, &mut s_sign));
+        defmt::assert_eq!(r_sign, R);
+        defmt::assert_eq!(s_sign, S);
+    }
+
+    #[test]
+    fn ram_zeroed_after_ecdsa_sign(pka: &mut Pka) {
+        let mut r_sign: [u32; 8] = [0; 8];
+        let mut s_sign: [u32; 8] = [0; 8];
+        unwrap!(pka.ecdsa_sign(
+            &NIST_P256,
+            &INTEGER,
+            &PRIVATE_KEY,
+            &HASH,
+            &mut r_sign,
+            &mut s_sign,
+        ));
+        defmt::assert!(pka_ram_is_zero());
+    }
+
+    #[test]
+    fn ram_zeroed_after_ecdh(pka: &mut Pka) {
+        let mut secret: Secret<8> = Secret::new([0; 8]);
+        unwrap!(pka.ecdh_shared_secret(&NIST_P256, &PRIVATE_KEY, &PUB_KEY, &mut secret));
+        defmt::assert!(pka_ram_is_zero());
+    }
+
+    #[test]
+    fn ram_zeroed_after_rsa_crt(pka: &mut Pka) {
+        let mut sig: Secret<64> = Secret::new([0; 64]);
+        unwrap!(pka.rsa_crt(&RSA_CRT_KEY, &RSA_SIG, &mut sig));
+        defmt::assert!(pka_ram_is_zero());
+    }
 }
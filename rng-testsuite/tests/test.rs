@@ -6,7 +6,7 @@ use defmt_rtt as _; // global logger
 use panic_probe as _;
 use stm32wl_hal::{
     pac, rcc,
-    rng::{rand_core::RngCore, Clk, Rng},
+    rng::{rand_core::RngCore, Clk, ClkDiv, Config, Error, Health, RetryPolicy, Rng},
 };
 
 /// This is not a cryptographically secure validation, this only ensures that
@@ -100,4 +100,93 @@ mod tests {
         unwrap!(rng.try_fill_u8(&mut bytes));
         validate_randomness(&bytes)
     }
+
+    #[test]
+    fn retry_policy(rng: &mut Rng) {
+        defmt::assert_eq!(rng.retry_policy(), RetryPolicy::default());
+
+        const POLICY: RetryPolicy = RetryPolicy::new().set_seed_retries(8);
+        rng.set_retry_policy(POLICY);
+        defmt::assert_eq!(rng.retry_policy(), POLICY);
+        rng.set_retry_policy(RetryPolicy::default());
+    }
+
+    #[test]
+    fn health(rng: &mut Rng) {
+        rng.reset_health();
+        defmt::assert_eq!(rng.health(), Health::default());
+
+        let mut bytes: [u8; 1024] = [0; 1024];
+        unwrap!(rng.try_fill_u8(&mut bytes));
+
+        // errors are not expected at room temperature, every error that did
+        // occur must have been recovered
+        let health: Health = rng.health();
+        defmt::assert_eq!(health.seed_errors(), health.seed_recoveries());
+        defmt::assert_eq!(health.clock_errors(), health.clock_recoveries());
+        defmt::assert_eq!(rng.seed_error_stat(), health.seed_recoveries());
+    }
+
+    #[test]
+    fn config(rng: &mut Rng) {
+        defmt::assert_eq!(rng.config(), Config::CERTIFIED);
+        defmt::assert!(!rng.is_config_locked());
+
+        const CONFIG: Config = Config::CERTIFIED.set_clk_div(ClkDiv::Div2);
+        unwrap!(rng.set_config(CONFIG));
+        defmt::assert_eq!(rng.config(), CONFIG);
+
+        let mut bytes: [u8; 35] = [0; 35];
+        unwrap!(rng.try_fill_u8(&mut bytes));
+        validate_randomness(&bytes);
+
+        unwrap!(rng.set_config(Config::CERTIFIED));
+        defmt::assert_eq!(rng.config(), Config::CERTIFIED);
+    }
+
+    #[test]
+    fn set_config_keeps_ie(rng: &mut Rng) {
+        // safety: only the IE bit is changed, the IRQ is masked in the NVIC
+        let regs: &pac::rng::RegisterBlock = unsafe { &*pac::RNG::PTR };
+        regs.cr.modify(|_, w| w.ie().set_bit());
+
+        unwrap!(rng.set_config(Config::CERTIFIED.set_clk_div(ClkDiv::Div2)));
+        defmt::assert!(regs.cr.read().ie().bit_is_set());
+        unwrap!(rng.set_config(Config::CERTIFIED));
+        defmt::assert!(regs.cr.read().ie().bit_is_set());
+
+        regs.cr.modify(|_, w| w.ie().clear_bit());
+    }
+
+    #[test]
+    fn clock_error(rng: &mut Rng) {
+        // 48 MHz / 32768 is far below the HCLK / 32 clock checker threshold
+        const SLOW: Config = Config::CERTIFIED.set_clk_div(ClkDiv::Div32768);
+        unwrap!(rng.set_config(SLOW));
+        rng.reset_health();
+
+        // words generated before the clock error was detected may be read
+        // before the error is reported
+        let mut result: Result<u32, Error> = Ok(0);
+        for _ in 0..8 {
+            result = rng.try_u32();
+            if result.is_err() {
+                break;
+            }
+        }
+        defmt::assert_eq!(result, Err(Error::Clock));
+
+        let health: Health = rng.health();
+        defmt::assert_eq!(health.clock_errors(), 1);
+        defmt::assert_eq!(health.clock_recoveries(), 0);
+
+        // the clock error clears when the RNG clock is fast enough again
+        unwrap!(rng.set_config(Config::CERTIFIED));
+        let mut bytes: [u8; 35] = [0; 35];
+        unwrap!(rng.try_fill_u8(&mut bytes));
+        validate_randomness(&bytes);
+
+        let health: Health = rng.health();
+        defmt::assert_eq!(health.clock_errors() - 1, health.clock_recoveries());
+    }
 }
@@ -1,6 +1,7 @@
 #![no_std]
 #![no_main]
 
+use core::time::Duration;
 use defmt::unwrap;
 use defmt_rtt as _; // global logger
 use panic_probe as _;
@@ -8,7 +9,8 @@ use stm32wl_hal::{
     chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, Timelike},
     pac::{self, DWT},
     rcc::{self, pulse_reset_backup_domain, setup_lsi, LsiPre},
-    rtc::{self, Rtc},
+    rtc::{self, Alarm, AlarmCfg, CalError, CalWindow, Calibration, Mode, Rtc},
+    tamp::{Tamp, Tamper, TamperCfg, NUM_BACKUP},
     util::reset_cycle_count,
 };
 
@@ -90,6 +92,32 @@ fn test_set_date_time_with_clk(clk: rtc::Clk) {
     // defmt::assert!(after > before);
 }
 
+fn wait_for_alarm(rtc: &Rtc, alarm: Alarm, timeout_millis: u32) -> bool {
+    let start: u32 = DWT::get_cycle_count();
+    loop {
+        if rtc.is_alarm_pending(alarm) {
+            return true;
+        }
+        let elapsed: u32 = DWT::get_cycle_count() - start;
+        if elapsed > CYC_PER_MILLI * timeout_millis {
+            return false;
+        }
+    }
+}
+
+fn wait_for_tamper(tamp: &Tamp, tamper: Tamper, timeout_millis: u32) -> bool {
+    let start: u32 = DWT::get_cycle_count();
+    loop {
+        if tamp.is_pending(tamper) {
+            return true;
+        }
+        let elapsed: u32 = DWT::get_cycle_count() - start;
+        if elapsed > CYC_PER_MILLI * timeout_millis {
+            return false;
+        }
+    }
+}
+
 #[defmt_test::tests]
 mod tests {
     use super::*;
@@ -142,4 +170,260 @@ mod tests {
         while ta.rcc.cr.read().hserdy().is_not_ready() {}
         test_set_date_time_with_clk(rtc::Clk::Hse)
     }
+
+    #[test]
+    fn alarm(ta: &mut TestArgs) {
+        unsafe { pulse_reset_backup_domain(&mut ta.rcc, &mut ta.pwr) };
+        unsafe { setup_lsi(&mut ta.rcc, LsiPre::DIV1) };
+
+        let mut dp: pac::Peripherals = unsafe { pac::Peripherals::steal() };
+        let mut rtc: Rtc = unsafe { Rtc::new(dp.RTC, rtc::Clk::Lsi, &mut dp.PWR, &mut dp.RCC) };
+
+        let date: NaiveDate = NaiveDate::from_ymd(2021, 10, 20);
+        rtc.set_date_time(date.and_hms(12, 0, 0));
+
+        const ALARM_A: AlarmCfg = AlarmCfg::new().set_seconds(1);
+        const ALARM_B: AlarmCfg = AlarmCfg::new().set_hours(13).set_minutes(0).set_seconds(0);
+        rtc.set_alarm(Alarm::A, &ALARM_A);
+        rtc.set_alarm(Alarm::B, &ALARM_B);
+        defmt::assert_eq!(rtc.alarm(Alarm::A), ALARM_A);
+        defmt::assert_eq!(rtc.alarm(Alarm::B), ALARM_B);
+        defmt::assert!(rtc.is_alarm_enabled(Alarm::A));
+        defmt::assert!(rtc.is_alarm_enabled(Alarm::B));
+        defmt::assert!(!rtc.is_alarm_pending(Alarm::A));
+
+        defmt::assert!(wait_for_alarm(&rtc, Alarm::A, 2000));
+        defmt::assert!(!rtc.is_alarm_pending(Alarm::B));
+
+        Rtc::clear_alarm(Alarm::A);
+        defmt::assert!(!rtc.is_alarm_pending(Alarm::A));
+
+        rtc.disable_alarm(Alarm::A);
+        rtc.disable_alarm(Alarm::B);
+        defmt::assert!(!rtc.is_alarm_enabled(Alarm::A));
+        defmt::assert!(!rtc.is_alarm_enabled(Alarm::B));
+    }
+
+    #[test]
+    fn wakeup_timer(ta: &mut TestArgs) {
+        unsafe { pulse_reset_backup_domain(&mut ta.rcc, &mut ta.pwr) };
+        unsafe { setup_lsi(&mut ta.rcc, LsiPre::DIV1) };
+
+        let mut dp: pac::Peripherals = unsafe { pac::Peripherals::steal() };
+        let mut rtc: Rtc = unsafe { Rtc::new(dp.RTC, rtc::Clk::Lsi, &mut dp.PWR, &mut dp.RCC) };
+
+        // 3200 LSI cycles, exact with RTCCLK / 16
+        const PERIOD: Duration = Duration::from_millis(100);
+        let period: Duration = rtc.setup_wakeup_timer(PERIOD, &ta.rcc);
+        defmt::assert_eq!(period, PERIOD);
+        defmt::assert!(rtc.is_wakeup_timer_enabled());
+
+        for _ in 0..3 {
+            let start: u32 = DWT::get_cycle_count();
+            loop {
+                if rtc.is_wakeup_timer_pending() {
+                    break;
+                }
+                let elapsed: u32 = DWT::get_cycle_count() - start;
+                defmt::assert!(elapsed < CYC_PER_MILLI * 200, "wakeup timer timeout");
+            }
+            Rtc::clear_wakeup_timer();
+            defmt::assert!(!rtc.is_wakeup_timer_pending());
+        }
+
+        // the wakeup timer is periodic, the next period starts when the
+        // previous period ends, not when the flag is cleared
+        let start: u32 = DWT::get_cycle_count();
+        while !rtc.is_wakeup_timer_pending() {}
+        let elapsed: u32 = DWT::get_cycle_count() - start;
+        defmt::assert!(elapsed > CYC_PER_MILLI * 80);
+        defmt::assert!(elapsed < CYC_PER_MILLI * 120);
+
+        rtc.disable_wakeup_timer();
+        defmt::assert!(!rtc.is_wakeup_timer_enabled());
+    }
+
+    #[test]
+    fn binary_and_mixed_mode(ta: &mut TestArgs) {
+        unsafe { pulse_reset_backup_domain(&mut ta.rcc, &mut ta.pwr) };
+        unsafe { setup_lsi(&mut ta.rcc, LsiPre::DIV1) };
+
+        let mut dp: pac::Peripherals = unsafe { pac::Peripherals::steal() };
+        let mut rtc: Rtc = unsafe { Rtc::new(dp.RTC, rtc::Clk::Lsi, &mut dp.PWR, &mut dp.RCC) };
+
+        let date: NaiveDate = NaiveDate::from_ymd(2021, 10, 20);
+        rtc.set_date_time(date.and_hms(12, 0, 0));
+        defmt::assert_eq!(rtc.mode(), Mode::Bcd);
+        defmt::assert_eq!(rtc.binary_counter(), None);
+
+        rtc.set_mode(Mode::Mixed, &ta.rcc);
+        defmt::assert_eq!(rtc.mode(), Mode::Mixed);
+        let hz: u32 = rtc.binary_counter_hz(&ta.rcc);
+        defmt::assert_eq!(hz, 256);
+
+        let start: u32 = unwrap!(rtc.binary_counter());
+        let start_dt: NaiveDateTime = unwrap!(rtc.date_time());
+
+        // delay 500ms
+        let start_cyc: u32 = DWT::get_cycle_count();
+        while DWT::get_cycle_count() - start_cyc < CYC_PER_MILLI * 500 {}
+
+        let elapsed: u32 = unwrap!(rtc.binary_counter()).wrapping_sub(start);
+        defmt::debug!("binary counter elapsed {} ticks", elapsed);
+        defmt::assert!((115..=141).contains(&elapsed));
+
+        let elapsed_ms: i64 = (unwrap!(rtc.date_time()) - start_dt).num_milliseconds();
+        defmt::debug!("date_time elapsed {} ms", elapsed_ms);
+        defmt::assert!((450..=550).contains(&elapsed_ms));
+
+        rtc.set_mode(Mode::Binary, &ta.rcc);
+        defmt::assert_eq!(rtc.mode(), Mode::Binary);
+        defmt::assert!(rtc.date_time().is_none());
+        let a: u32 = unwrap!(rtc.binary_counter());
+        let start_cyc: u32 = DWT::get_cycle_count();
+        while DWT::get_cycle_count() - start_cyc < CYC_PER_MILLI * 20 {}
+        defmt::assert!(unwrap!(rtc.binary_counter()).wrapping_sub(a) > 0);
+
+        rtc.set_mode(Mode::Bcd, &ta.rcc);
+        defmt::assert_eq!(rtc.binary_counter(), None);
+    }
+
+    #[test]
+    fn calibration_and_shift(ta: &mut TestArgs) {
+        unsafe { pulse_reset_backup_domain(&mut ta.rcc, &mut ta.pwr) };
+        unsafe { setup_lsi(&mut ta.rcc, LsiPre::DIV1) };
+
+        let mut dp: pac::Peripherals = unsafe { pac::Peripherals::steal() };
+        let mut rtc: Rtc = unsafe { Rtc::new(dp.RTC, rtc::Clk::Lsi, &mut dp.PWR, &mut dp.RCC) };
+        defmt::assert_eq!(rtc.calibration(), Calibration::NONE);
+
+        let cal: Calibration = unwrap!(Calibration::from_ppm_error(-20.0, CalWindow::Sec16));
+        rtc.set_calibration(&cal);
+        defmt::assert_eq!(rtc.calibration(), cal);
+        rtc.set_calibration(&Calibration::NONE);
+        defmt::assert_eq!(rtc.calibration(), Calibration::NONE);
+
+        let date: NaiveDate = NaiveDate::from_ymd(2021, 10, 20);
+        rtc.set_date_time(date.and_hms(12, 0, 0));
+        // wait for the calendar to start
+        while rtc.date_time().is_none() {}
+
+        let before: NaiveDateTime = unwrap!(rtc.date_time());
+        // 1 / 256 second resolution with the LSI
+        let shift: Duration = unwrap!(rtc.shift_ahead(Duration::from_millis(500)));
+        defmt::assert_eq!(shift, Duration::from_millis(500));
+        let after: NaiveDateTime = unwrap!(rtc.date_time());
+        let elapsed_ms: i64 = (after - before).num_milliseconds();
+        defmt::debug!("shift ahead elapsed {} ms", elapsed_ms);
+        defmt::assert!((490..=520).contains(&elapsed_ms));
+
+        let before: NaiveDateTime = unwrap!(rtc.date_time());
+        let shift: Duration = unwrap!(rtc.shift_back(Duration::from_millis(250)));
+        defmt::assert_eq!(shift, Duration::from_millis(250));
+        let after: NaiveDateTime = unwrap!(rtc.date_time());
+        let elapsed_ms: i64 = (after - before).num_milliseconds();
+        defmt::debug!("shift back elapsed {} ms", elapsed_ms);
+        defmt::assert!((-260..=-230).contains(&elapsed_ms));
+
+        defmt::assert_eq!(
+            rtc.shift_ahead(Duration::from_secs(1)),
+            Err(CalError::ShiftTooLong)
+        );
+        rtc.set_mode(Mode::Binary, &ta.rcc);
+        defmt::assert_eq!(
+            rtc.shift_back(Duration::from_millis(10)),
+            Err(CalError::ShiftMode)
+        );
+        rtc.set_mode(Mode::Bcd, &ta.rcc);
+    }
+
+    #[test]
+    fn backup_registers(ta: &mut TestArgs) {
+        unsafe { pulse_reset_backup_domain(&mut ta.rcc, &mut ta.pwr) };
+
+        let mut dp: pac::Peripherals = unsafe { pac::Peripherals::steal() };
+        let mut tamp: Tamp = Tamp::new(dp.TAMP, &mut dp.PWR, &mut dp.RCC);
+        defmt::assert_eq!(tamp.backups(), [0; NUM_BACKUP]);
+
+        (0..NUM_BACKUP).for_each(|idx| tamp.set_backup(idx, 0xA5A5_0000 | idx as u32));
+        (0..NUM_BACKUP)
+            .for_each(|idx| defmt::assert_eq!(tamp.backup(idx), 0xA5A5_0000 | idx as u32));
+
+        // survives a new driver, but not a backup domain reset
+        let mut tamp: Tamp = unsafe { Tamp::steal() };
+        defmt::assert_eq!(
+            tamp.backup(NUM_BACKUP - 1),
+            0xA5A5_0000 | (NUM_BACKUP - 1) as u32
+        );
+        tamp.erase_backups();
+        defmt::assert_eq!(tamp.backups(), [0; NUM_BACKUP]);
+
+        tamp.set_backup(0, 0xDEAD_BEEF);
+        unsafe { pulse_reset_backup_domain(&mut ta.rcc, &mut ta.pwr) };
+        defmt::assert_eq!(tamp.backup(0), 0);
+    }
+
+    #[test]
+    fn tamper_enable(ta: &mut TestArgs) {
+        unsafe { pulse_reset_backup_domain(&mut ta.rcc, &mut ta.pwr) };
+
+        let mut dp: pac::Peripherals = unsafe { pac::Peripherals::steal() };
+        let mut tamp: Tamp = Tamp::new(dp.TAMP, &mut dp.PWR, &mut dp.RCC);
+
+        const NO_ERASE: TamperCfg = TamperCfg::new().set_erase(false);
+        tamp.set_backup(0, 0x1234_5678);
+
+        for tamper in [Tamper::CalendarOverflow, Tamper::CounterOverflow] {
+            defmt::assert!(!tamp.is_enabled(tamper));
+            tamp.enable(tamper, &NO_ERASE);
+            defmt::assert!(tamp.is_enabled(tamper));
+            defmt::assert!(!tamp.is_pending(tamper));
+            tamp.disable(tamper);
+            defmt::assert!(!tamp.is_enabled(tamper));
+            Tamp::clear(tamper);
+        }
+
+        defmt::assert_eq!(tamp.backup(0), 0x1234_5678);
+    }
+
+    // the calendar overflow internal tamper is generated when the calendar
+    // wraps from 2099-12-31 23:59:59 to 2000-01-01 00:00:00
+    #[test]
+    fn tamper_erase(ta: &mut TestArgs) {
+        unsafe { pulse_reset_backup_domain(&mut ta.rcc, &mut ta.pwr) };
+        unsafe { setup_lsi(&mut ta.rcc, LsiPre::DIV1) };
+
+        let mut dp: pac::Peripherals = unsafe { pac::Peripherals::steal() };
+        let mut rtc: Rtc = unsafe { Rtc::new(dp.RTC, rtc::Clk::Lsi, &mut dp.PWR, &mut dp.RCC) };
+        let mut tamp: Tamp = Tamp::new(dp.TAMP, &mut dp.PWR, &mut dp.RCC);
+        let date: NaiveDate = NaiveDate::from_ymd(2099, 12, 31);
+
+        for erase in [true, false] {
+            tamp.set_backup(0, 0x1234_5678);
+            rtc.set_date_time(date.and_hms(23, 59, 58));
+
+            let cfg: TamperCfg = TamperCfg::new().set_erase(erase);
+            tamp.enable(Tamper::CalendarOverflow, &cfg);
+            defmt::assert!(tamp.is_enabled(Tamper::CalendarOverflow));
+            defmt::assert!(!tamp.is_pending(Tamper::CalendarOverflow));
+
+            defmt::assert!(wait_for_tamper(&tamp, Tamper::CalendarOverflow, 3000));
+            defmt::assert_eq!(unwrap!(rtc.date()).year(), 2000);
+
+            if erase {
+                // access to the backup registers is blocked while the flag is set
+                defmt::assert_eq!(tamp.backups(), [0; NUM_BACKUP]);
+            } else {
+                defmt::assert_eq!(tamp.backup(0), 0x1234_5678);
+            }
+
+            tamp.disable(Tamper::CalendarOverflow);
+            defmt::assert!(!tamp.is_enabled(Tamper::CalendarOverflow));
+            Tamp::clear(Tamper::CalendarOverflow);
+            defmt::assert!(!tamp.is_pending(Tamper::CalendarOverflow));
+
+            let expected: u32 = if erase { 0 } else { 0x1234_5678 };
+            defmt::assert_eq!(tamp.backup(0), expected);
+        }
+    }
 }